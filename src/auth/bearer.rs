@@ -1 +1,323 @@
 //! Bearer token (app-only) authentication implementation
+//!
+//! App-only authentication identifies the app rather than a user, and gives
+//! access to public data: tweet lookup and search, user lookup, the filtered
+//! and sample streams, and so on. It cannot act on behalf of a user.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use x_api_client::auth::bearer::BearerTokenProvider;
+//! use std::sync::Arc;
+//!
+//! // Exchange the app's consumer key and secret for a bearer token
+//! let provider = BearerTokenProvider::from_credentials("consumer_key", "consumer_secret").await?;
+//! let client = Client::builder().auth(Arc::new(provider.clone())).build()?;
+//!
+//! // Invalidate the token when the app shuts down
+//! provider.invalidate().await?;
+//! ```
+
+use crate::util::lock_unpoisoned;
+use async_trait::async_trait;
+
+use crate::auth::{AuthProvider, OAuth2Http};
+use crate::client::{DEFAULT_BASE_URL, ReqwestClient};
+use crate::endpoints::paths;
+use crate::error::{Error, Result};
+use serde::Deserialize;
+use std::sync::{Arc, Mutex};
+
+/// Grant type requesting an app-only token for the app's own credentials
+pub const CLIENT_CREDENTIALS_GRANT: &str = "client_credentials";
+
+/// Token type of app-only tokens
+const BEARER_TOKEN_TYPE: &str = "bearer";
+
+/// OAuth 2.0 app-only bearer token provider
+///
+/// Adds `Authorization: Bearer <token>` to every request. Endpoints that
+/// need a user context, such as [`USERS_ME`](paths::USERS_ME) and direct
+/// messages, are not supported.
+///
+/// Clones share the token, so invalidating it through one clone stops all
+/// of them from authenticating.
+#[derive(Clone)]
+pub struct BearerTokenProvider {
+    /// The token, `None` once invalidated
+    token: Arc<Mutex<Option<String>>>,
+
+    /// App consumer key and secret, needed to invalidate the token
+    credentials: Option<(String, String)>,
+
+    /// Base URL of the OAuth 2.0 endpoints
+    base_url: String,
+
+    /// Client for the OAuth 2.0 endpoints
+    http: OAuth2Http,
+}
+
+/// Response of the token endpoint
+#[derive(Deserialize)]
+struct TokenResponse {
+    token_type: String,
+    access_token: String,
+}
+
+impl BearerTokenProvider {
+    /// Create a provider for a bearer token minted in the developer portal
+    pub fn new(token: impl Into<String>) -> Self {
+        Self {
+            token: Arc::new(Mutex::new(Some(token.into()))),
+            credentials: None,
+            base_url: DEFAULT_BASE_URL.to_string(),
+            http: OAuth2Http::default(),
+        }
+    }
+
+    /// Obtain a bearer token with the OAuth 2.0 client credentials grant
+    ///
+    /// `client_id` and `client_secret` are the app's consumer key and
+    /// secret. They are kept to [`invalidate`](Self::invalidate) the token.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the token endpoint rejects the credentials or
+    /// does not return a bearer token.
+    pub async fn from_credentials(
+        client_id: impl Into<String>,
+        client_secret: impl Into<String>,
+    ) -> Result<Self> {
+        Self::from_credentials_at(DEFAULT_BASE_URL, client_id, client_secret).await
+    }
+
+    /// Like [`from_credentials`](Self::from_credentials), against the token
+    /// endpoint of `base_url`
+    pub async fn from_credentials_at(
+        base_url: &str,
+        client_id: impl Into<String>,
+        client_secret: impl Into<String>,
+    ) -> Result<Self> {
+        Self::obtain(OAuth2Http::default(), base_url, client_id, client_secret).await
+    }
+
+    /// Like [`from_credentials_at`](Self::from_credentials_at), sending the
+    /// token requests through `http`
+    ///
+    /// The client is kept to [`invalidate`](Self::invalidate) the token, so
+    /// its timeouts and transport apply to both requests.
+    pub async fn from_credentials_with(
+        http: ReqwestClient,
+        base_url: &str,
+        client_id: impl Into<String>,
+        client_secret: impl Into<String>,
+    ) -> Result<Self> {
+        Self::obtain(OAuth2Http::new(http), base_url, client_id, client_secret).await
+    }
+
+    /// Run the client credentials grant through `http`
+    async fn obtain(
+        http: OAuth2Http,
+        base_url: &str,
+        client_id: impl Into<String>,
+        client_secret: impl Into<String>,
+    ) -> Result<Self> {
+        let credentials = (client_id.into(), client_secret.into());
+        let url = format!("{}{}", base_url, paths::OAUTH2_TOKEN);
+        let body = http
+            .post_form(
+                &url,
+                Some((&credentials.0, &credentials.1)),
+                &[("grant_type", CLIENT_CREDENTIALS_GRANT)],
+            )
+            .await?;
+
+        let token: TokenResponse = serde_json::from_str(&body)
+            .map_err(|e| Error::OAuth(format!("Invalid token response from {}: {}", url, e)))?;
+        if !token.token_type.eq_ignore_ascii_case(BEARER_TOKEN_TYPE) {
+            return Err(Error::OAuth(format!(
+                "Unexpected token type '{}' from {}",
+                token.token_type, url
+            )));
+        }
+        Ok(Self {
+            token: Arc::new(Mutex::new(Some(token.access_token))),
+            credentials: Some(credentials),
+            base_url: base_url.to_string(),
+            http,
+        })
+    }
+
+    /// Invalidate the token, after which this provider refuses to authenticate
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Config`] if the provider was not created with
+    /// [`from_credentials`](Self::from_credentials),
+    /// [`Error::Authentication`] if the token was already invalidated, or the
+    /// API error if the invalidation fails (the token then stays usable).
+    pub async fn invalidate(&self) -> Result<()> {
+        let (client_id, client_secret) = self.credentials.as_ref().ok_or_else(|| {
+            Error::Config(
+                "Invalidating a bearer token requires the app's client credentials; \
+                 create the provider with from_credentials()"
+                    .to_string(),
+            )
+        })?;
+        let token = self.token()?;
+        let url = format!("{}{}", self.base_url, paths::OAUTH2_INVALIDATE_TOKEN);
+        self.http
+            .post_form(
+                &url,
+                Some((client_id, client_secret)),
+                &[("access_token", &token)],
+            )
+            .await?;
+
+        *lock_unpoisoned(&self.token) = None;
+        Ok(())
+    }
+
+    /// The current token, unless invalidated
+    fn token(&self) -> Result<String> {
+        lock_unpoisoned(&self.token)
+            .clone()
+            .ok_or_else(|| Error::Authentication("Bearer token has been invalidated".to_string()))
+    }
+
+    /// Check if the given endpoint requires a user context
+    fn is_user_context_only(endpoint: &str) -> bool {
+        endpoint == paths::USERS_ME
+            || [paths::DM_CONVERSATIONS, paths::DM_EVENTS]
+                .iter()
+                .any(|root| endpoint == *root || paths::is_nested_under(endpoint, root))
+    }
+}
+
+#[async_trait]
+impl AuthProvider for BearerTokenProvider {
+    async fn authenticate(&self, mut req: reqwest::Request) -> Result<reqwest::Request> {
+        if !self.supports_endpoint(req.url().path()) {
+            return Err(Error::Authentication(format!(
+                "Endpoint '{}' does not support app-only authentication",
+                req.url().path()
+            )));
+        }
+
+        let token = self.token()?;
+        req.headers_mut().insert(
+            reqwest::header::AUTHORIZATION,
+            reqwest::header::HeaderValue::from_str(&format!("Bearer {}", token))
+                .map_err(|e| Error::Authentication(format!("Invalid bearer token: {}", e)))?,
+        );
+        Ok(req)
+    }
+
+    fn supports_endpoint(&self, endpoint: &str) -> bool {
+        !Self::is_user_context_only(endpoint)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Client;
+    use wiremock::matchers::{body_string, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    /// HTTP Basic credentials of client `ck` with secret `cs`
+    const BASIC_CK_CS: &str = "Basic Y2s6Y3M=";
+
+    async fn mount_token(server: &MockServer) {
+        Mock::given(method("POST"))
+            .and(path("/oauth2/token"))
+            .and(header("authorization", BASIC_CK_CS))
+            .and(body_string("grant_type=client_credentials"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "token_type": "bearer",
+                "access_token": "AAAA-app-token"
+            })))
+            .expect(1)
+            .mount(server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_acquired_token_used_for_requests() {
+        let server = MockServer::start().await;
+        mount_token(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/2/tweets/20"))
+            .and(header("authorization", "Bearer AAAA-app-token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {"id": "20", "text": "just setting up my twttr"}
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = BearerTokenProvider::from_credentials_at(&server.uri(), "ck", "cs")
+            .await
+            .unwrap();
+        assert!(!provider.supports_endpoint(paths::USERS_ME));
+        assert!(provider.supports_endpoint(paths::TWEETS_SEARCH_STREAM));
+
+        let client = Client::builder()
+            .auth(Arc::new(provider))
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+        let tweet = client.get_tweet("20").await.unwrap();
+        assert_eq!(tweet.text, "just setting up my twttr");
+    }
+
+    #[tokio::test]
+    async fn test_rejected_credentials() {
+        let server = MockServer::start().await;
+        Mock::given(path("/oauth2/token"))
+            .respond_with(ResponseTemplate::new(403).set_body_json(serde_json::json!({
+                "errors": [{"code": 99, "message": "Unable to verify your credentials"}]
+            })))
+            .mount(&server)
+            .await;
+
+        let result = BearerTokenProvider::from_credentials_at(&server.uri(), "ck", "bad").await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_invalidated_token_refuses_to_authenticate() {
+        let server = MockServer::start().await;
+        mount_token(&server).await;
+        Mock::given(method("POST"))
+            .and(path("/oauth2/invalidate_token"))
+            .and(header("authorization", BASIC_CK_CS))
+            .and(body_string("access_token=AAAA-app-token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "access_token": "AAAA-app-token"
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let http = ReqwestClient::new().unwrap();
+        let provider = BearerTokenProvider::from_credentials_with(http, &server.uri(), "ck", "cs")
+            .await
+            .unwrap();
+        let clone = provider.clone();
+        provider.invalidate().await.unwrap();
+
+        let request = reqwest::Request::new(
+            reqwest::Method::GET,
+            format!("{}/2/tweets/20", server.uri()).parse().unwrap(),
+        );
+        assert!(matches!(
+            clone.authenticate(request).await,
+            Err(Error::Authentication(_))
+        ));
+        assert!(matches!(
+            BearerTokenProvider::new("minted").invalidate().await,
+            Err(Error::Config(_))
+        ));
+    }
+}
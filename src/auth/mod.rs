@@ -9,8 +9,10 @@
 //! methods, enabling flexible authentication strategies via dependency injection
 //! and trait objects.
 
-use crate::error::Result;
+use crate::client::{HttpClient, ReqwestClient};
+use crate::error::{Result, parse_api_error};
 use async_trait::async_trait;
+use std::sync::{Arc, OnceLock};
 
 /// Authentication provider trait for X API v2
 ///
@@ -104,6 +106,58 @@ pub mod bearer;
 pub mod oauth1;
 pub mod oauth2;
 
+/// HTTP client of an OAuth 2.0 provider, reused for every token request
+///
+/// Built with defaults on first use unless one was supplied. Clones share
+/// it, and with it the connection pool.
+#[derive(Clone, Default)]
+pub(crate) struct OAuth2Http(Arc<OnceLock<ReqwestClient>>);
+
+impl OAuth2Http {
+    pub(crate) fn new(http: ReqwestClient) -> Self {
+        Self(Arc::new(OnceLock::from(http)))
+    }
+
+    /// The client, building it if this is the first use
+    fn get(&self) -> Result<&ReqwestClient> {
+        if let Some(http) = self.0.get() {
+            return Ok(http);
+        }
+        let http = ReqwestClient::new()?;
+        Ok(self.0.get_or_init(|| http))
+    }
+
+    /// POST a form to an OAuth 2.0 endpoint and return the response body
+    ///
+    /// `basic` credentials (client ID and secret) are sent with HTTP Basic
+    /// authentication.
+    ///
+    /// # Errors
+    ///
+    /// Returns the API error if the endpoint responds with a non-success
+    /// status.
+    pub(crate) async fn post_form(
+        &self,
+        url: &str,
+        basic: Option<(&str, &str)>,
+        form: &[(&str, &str)],
+    ) -> Result<String> {
+        let http = self.get()?;
+        let mut request = http.inner().post(url).form(form);
+        if let Some((client_id, client_secret)) = basic {
+            request = request.basic_auth(client_id, Some(client_secret));
+        }
+
+        let response = http.execute(request.build()?).await?;
+        let status = response.status();
+        let body = response.text().await?;
+        if !status.is_success() {
+            return Err(parse_api_error(status.as_u16(), &body));
+        }
+        Ok(body)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -220,6 +274,16 @@ mod tests {
         assert!(!provider.supports_endpoint("/2/lists"));
     }
 
+    #[test]
+    fn test_oauth2_http_client_reused() {
+        let http = OAuth2Http::default();
+        let clone = http.clone();
+        assert!(std::ptr::eq(http.get().unwrap(), clone.get().unwrap()));
+
+        let http = OAuth2Http::new(ReqwestClient::new().unwrap());
+        assert!(std::ptr::eq(http.get().unwrap(), http.get().unwrap()));
+    }
+
     #[test]
     fn test_auth_provider_send_sync() {
         // Verify AuthProvider is Send + Sync
@@ -32,7 +32,14 @@ use async_trait::async_trait;
 use oauth1_request as oauth;
 
 use crate::auth::AuthProvider;
-use crate::error::{Error, Result};
+use crate::builder::request::FORM_CONTENT_TYPE;
+use crate::client::{DEFAULT_BASE_URL, HttpClient, ReqwestClient};
+use crate::endpoints::paths;
+use crate::error::{Error, Result, parse_api_error};
+use serde::Deserialize;
+
+/// Callback value selecting the PIN-based (out-of-band) flow
+pub const OOB_CALLBACK: &str = "oob";
 
 /// OAuth 1.0a authentication provider
 ///
@@ -55,7 +62,7 @@ use crate::error::{Error, Result};
 /// - `/2/users/:id/following` - Following/unfollowing
 /// - `/2/dm_conversations` - Direct messages
 ///
-/// For app-only endpoints (public data), use `OAuth2BearerProvider` instead.
+/// For app-only endpoints (public data), use [`BearerTokenProvider`](crate::auth::bearer::BearerTokenProvider) instead.
 #[derive(Clone)]
 pub struct OAuth1Provider {
     /// OAuth 1.0a credentials token
@@ -98,6 +105,50 @@ impl OAuth1Provider {
         Self { token }
     }
 
+    /// Signing builder for this provider's credentials
+    fn signer(&self) -> oauth::Builder<'_, oauth::HmacSha1, &str> {
+        let mut builder = oauth::Builder::with_token(self.token.as_ref(), oauth::HMAC_SHA1);
+        builder.version(true);
+        builder
+    }
+
+    /// Request URI as used in the signature base string (without query or fragment)
+    fn signing_uri(url: &reqwest::Url) -> String {
+        let mut uri = url.clone();
+        uri.set_query(None);
+        uri.set_fragment(None);
+        uri.into()
+    }
+
+    /// Collect the request parameters covered by the signature
+    ///
+    /// Per RFC 5849 section 3.4.1.3, these are the query parameters plus the
+    /// body parameters of `application/x-www-form-urlencoded` requests.
+    /// Other body types (e.g. JSON) are not signed.
+    fn signature_params(req: &reqwest::Request) -> Result<oauth::ParameterList<String, String>> {
+        let mut params: Vec<(String, String)> = req
+            .url()
+            .query_pairs()
+            .map(|(k, v)| (k.into_owned(), v.into_owned()))
+            .collect();
+
+        let is_form = req
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.starts_with(FORM_CONTENT_TYPE));
+        if is_form {
+            let body = req.body().and_then(|b| b.as_bytes()).ok_or_else(|| {
+                Error::Authentication("Cannot sign a streaming form body".to_string())
+            })?;
+            let form: Vec<(String, String)> = serde_urlencoded::from_bytes(body)
+                .map_err(|e| Error::Authentication(format!("Invalid form body: {}", e)))?;
+            params.extend(form);
+        }
+
+        Ok(oauth::ParameterList::new(params))
+    }
+
     /// Check if the given endpoint is a user-context endpoint requiring OAuth 1.0a
     ///
     /// This is a helper method used by `supports_endpoint` to determine if an
@@ -105,11 +156,14 @@ impl OAuth1Provider {
     fn is_user_context_endpoint(endpoint: &str) -> bool {
         // App-only endpoints that should NOT use OAuth 1.0a (must check first)
         const EXCLUDED_PATTERNS: &[&str] = &[
-            "/2/tweets/search",   // Tweet search endpoints
-            "/2/tweets/sample",   // Sample stream
-            "/2/tweets/count",    // Tweet counts
-            "/2/compliance/jobs", // Compliance
-            "/2/openapi.json",    // OpenAPI spec
+            paths::TWEETS_SEARCH,            // Tweet search endpoints
+            paths::TWEETS_SAMPLE,            // Sample stream
+            paths::TWEETS_COUNTS,            // Tweet counts
+            paths::COMPLIANCE_JOBS,          // Compliance
+            paths::USAGE_TWEETS,             // Project usage
+            paths::TWEETS_COMPLIANCE_STREAM, // Tweet compliance stream
+            paths::USERS_COMPLIANCE_STREAM,  // User compliance stream
+            paths::OPENAPI_SPEC,             // OpenAPI spec
         ];
 
         // Check exclusions first
@@ -120,32 +174,30 @@ impl OAuth1Provider {
             return false;
         }
 
-        // Exact match patterns for user-context endpoints
-        const EXACT_PATTERNS: &[&str] = &[
-            "/2/tweets",           // Creating tweets (POST)
-            "/2/dm_conversations", // Direct messages
-            "/2/dm_events",        // DM events
-            "/2/lists",            // List management
+        // Collection roots matched exactly and for nested resources
+        const ROOT_PATTERNS: &[&str] = &[
+            paths::TWEETS,           // Creating tweets (POST), DELETE /2/tweets/:id
+            paths::DM_CONVERSATIONS, // Direct messages
+            paths::DM_EVENTS,        // DM events
+            paths::LISTS,            // List management
         ];
 
-        // Prefix patterns for user-context endpoints
-        const PREFIX_PATTERNS: &[&str] = &[
-            "/2/tweets/",           // Individual tweet operations (DELETE /2/tweets/:id)
-            "/2/users/",            // User actions (likes, follows, blocks, mutes)
-            "/2/dm_conversations/", // DM conversation operations
-            "/2/dm_events/",        // DM event operations
-            "/2/lists/",            // List operations
+        // Roots matched for nested resources only
+        const NESTED_ONLY_PATTERNS: &[&str] = &[
+            paths::USERS, // User actions (likes, follows, blocks, mutes)
+            paths::V1_1,  // Legacy v1.1 endpoints (form-encoded)
         ];
 
         // Check exact matches
-        if EXACT_PATTERNS.contains(&endpoint) {
+        if ROOT_PATTERNS.contains(&endpoint) {
             return true;
         }
 
-        // Then check prefix matches
-        PREFIX_PATTERNS
+        // Then check nested matches
+        ROOT_PATTERNS
             .iter()
-            .any(|pattern| endpoint.starts_with(pattern))
+            .chain(NESTED_ONLY_PATTERNS)
+            .any(|root| paths::is_nested_under(endpoint, root))
     }
 }
 
@@ -164,22 +216,25 @@ impl AuthProvider for OAuth1Provider {
             )));
         }
 
+        if !matches!(
+            *method,
+            reqwest::Method::GET
+                | reqwest::Method::POST
+                | reqwest::Method::PUT
+                | reqwest::Method::DELETE
+        ) {
+            return Err(Error::Authentication(format!(
+                "HTTP method '{}' is not supported for OAuth 1.0a",
+                method
+            )));
+        }
+
         // Generate OAuth 1.0a Authorization header
         // The oauth1-request crate generates the signature and formats the header
-        let authorization_header = match *method {
-            reqwest::Method::GET => oauth::get(url.as_str(), &(), &self.token, oauth::HMAC_SHA1),
-            reqwest::Method::POST => oauth::post(url.as_str(), &(), &self.token, oauth::HMAC_SHA1),
-            reqwest::Method::PUT => oauth::put(url.as_str(), &(), &self.token, oauth::HMAC_SHA1),
-            reqwest::Method::DELETE => {
-                oauth::delete(url.as_str(), &(), &self.token, oauth::HMAC_SHA1)
-            }
-            _ => {
-                return Err(Error::Authentication(format!(
-                    "HTTP method '{}' is not supported for OAuth 1.0a",
-                    method
-                )));
-            }
-        };
+        let params = Self::signature_params(&req)?;
+        let authorization_header =
+            self.signer()
+                .authorize(method.as_str(), Self::signing_uri(url), &params);
 
         // Inject the Authorization header into the request
         req.headers_mut().insert(
@@ -196,6 +251,211 @@ impl AuthProvider for OAuth1Provider {
     }
 }
 
+/// Temporary credentials from the first step of the three-legged flow
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct RequestToken {
+    /// Request token, passed to the authorization page
+    #[serde(rename = "oauth_token")]
+    pub token: String,
+
+    /// Request token secret, needed to exchange the token
+    #[serde(rename = "oauth_token_secret")]
+    pub secret: String,
+
+    /// Whether the callback was accepted
+    #[serde(
+        rename = "oauth_callback_confirmed",
+        default,
+        deserialize_with = "deserialize_flag"
+    )]
+    pub callback_confirmed: bool,
+}
+
+/// User credentials from the last step of the three-legged flow
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct AccessToken {
+    /// User access token
+    #[serde(rename = "oauth_token")]
+    pub token: String,
+
+    /// User access token secret
+    #[serde(rename = "oauth_token_secret")]
+    pub secret: String,
+
+    /// ID of the user who authorized the app
+    #[serde(default)]
+    pub user_id: Option<String>,
+
+    /// Username of the user who authorized the app
+    #[serde(default)]
+    pub screen_name: Option<String>,
+}
+
+fn deserialize_flag<'de, D>(deserializer: D) -> std::result::Result<bool, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Ok(String::deserialize(deserializer)? == "true")
+}
+
+/// OAuth 1.0a three-legged (PIN or callback) flow for obtaining user tokens
+///
+/// 1. [`request_token`](Self::request_token) obtains temporary credentials
+/// 2. The user visits [`authorize_url`](Self::authorize_url) and approves the app
+/// 3. [`access_token`](Self::access_token) exchanges the request token and the
+///    verifier (PIN or `oauth_verifier` callback parameter) for user tokens
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use x_api_client::auth::oauth1::{OAuth1Flow, OAuth1Provider, OOB_CALLBACK};
+///
+/// let flow = OAuth1Flow::new("consumer_key", "consumer_secret")?;
+/// let request_token = flow.request_token(OOB_CALLBACK).await?;
+/// println!("Authorize at {}", flow.authorize_url(&request_token)?);
+///
+/// let access = flow.access_token(&request_token, &pin).await?;
+/// let provider = OAuth1Provider::new("consumer_key", "consumer_secret", access.token, access.secret);
+/// ```
+#[derive(Clone)]
+pub struct OAuth1Flow {
+    consumer_key: String,
+    consumer_secret: String,
+    http: ReqwestClient,
+    base_url: String,
+}
+
+impl OAuth1Flow {
+    /// Create a flow for an app's consumer credentials
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be created.
+    pub fn new(
+        consumer_key: impl Into<String>,
+        consumer_secret: impl Into<String>,
+    ) -> Result<Self> {
+        Ok(Self {
+            consumer_key: consumer_key.into(),
+            consumer_secret: consumer_secret.into(),
+            http: ReqwestClient::new()?,
+            base_url: DEFAULT_BASE_URL.to_string(),
+        })
+    }
+
+    /// Set the base URL of the OAuth endpoints
+    ///
+    /// Default: `"https://api.twitter.com"`
+    pub fn base_url(mut self, url: impl Into<String>) -> Self {
+        self.base_url = url.into();
+        self
+    }
+
+    /// Obtain a request token
+    ///
+    /// `callback` is the URL the user is redirected to after authorizing, or
+    /// [`OOB_CALLBACK`] for the PIN-based flow.
+    pub async fn request_token(&self, callback: &str) -> Result<RequestToken> {
+        let client =
+            oauth::Credentials::new(self.consumer_key.as_str(), self.consumer_secret.as_str());
+        let mut signer = oauth::Builder::<_, _, &str>::new(client, oauth::HMAC_SHA1);
+        signer.callback(callback).version(true);
+        self.token_request(paths::OAUTH_REQUEST_TOKEN, &signer)
+            .await
+    }
+
+    /// URL of the page where the user authorizes the app
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the configured base URL is invalid.
+    pub fn authorize_url(&self, request_token: &RequestToken) -> Result<String> {
+        let mut url = self.url(paths::OAUTH_AUTHORIZE)?;
+        url.query_pairs_mut()
+            .append_pair("oauth_token", &request_token.token);
+        Ok(url.into())
+    }
+
+    /// Exchange an authorized request token for user access tokens
+    ///
+    /// `verifier` is the PIN shown to the user, or the `oauth_verifier`
+    /// parameter passed to the callback.
+    pub async fn access_token(
+        &self,
+        request_token: &RequestToken,
+        verifier: &str,
+    ) -> Result<AccessToken> {
+        let token = oauth::Token::from_parts(
+            self.consumer_key.as_str(),
+            self.consumer_secret.as_str(),
+            request_token.token.as_str(),
+            request_token.secret.as_str(),
+        );
+        let mut signer = oauth::Builder::with_token(token, oauth::HMAC_SHA1);
+        signer.verifier(verifier).version(true);
+        self.token_request(paths::OAUTH_ACCESS_TOKEN, &signer).await
+    }
+
+    fn url(&self, path: &str) -> Result<reqwest::Url> {
+        let url = format!("{}{}", self.base_url, path);
+        url.parse()
+            .map_err(|e| Error::Config(format!("Invalid URL {}: {}", url, e)))
+    }
+
+    /// POST a signed token request and parse the form-encoded response
+    async fn token_request<T>(
+        &self,
+        path: &str,
+        signer: &oauth::Builder<'_, oauth::HmacSha1, &str, &str>,
+    ) -> Result<T>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let url = self.url(path)?;
+        let authorization = signer.authorize("POST", url.as_str(), &());
+
+        let mut request = reqwest::Request::new(reqwest::Method::POST, url);
+        request.headers_mut().insert(
+            reqwest::header::AUTHORIZATION,
+            reqwest::header::HeaderValue::from_str(&authorization)
+                .map_err(|e| Error::OAuth(format!("Invalid OAuth header: {}", e)))?,
+        );
+
+        let response = self.http.execute(request).await?;
+        let status = response.status();
+        let body = response.text().await?;
+        if !status.is_success() {
+            return Err(parse_api_error(status.as_u16(), &body));
+        }
+        serde_urlencoded::from_str(&body)
+            .map_err(|e| Error::OAuth(format!("Invalid token response from {}: {}", path, e)))
+    }
+}
+
+/// Obtain an OAuth 1.0a request token (see [`OAuth1Flow::request_token`])
+pub async fn oauth1_request_token(
+    consumer_key: impl Into<String>,
+    consumer_secret: impl Into<String>,
+    callback: &str,
+) -> Result<RequestToken> {
+    OAuth1Flow::new(consumer_key, consumer_secret)?
+        .request_token(callback)
+        .await
+}
+
+/// Exchange an authorized request token for access tokens (see
+/// [`OAuth1Flow::access_token`])
+pub async fn oauth1_access_token(
+    consumer_key: impl Into<String>,
+    consumer_secret: impl Into<String>,
+    request_token: &RequestToken,
+    verifier: &str,
+) -> Result<AccessToken> {
+    OAuth1Flow::new(consumer_key, consumer_secret)?
+        .access_token(request_token, verifier)
+        .await
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -370,6 +630,92 @@ mod tests {
         assert!(auth_header.contains("oauth_signature="));
     }
 
+    #[tokio::test]
+    async fn test_signature_generation_get_request_with_query() {
+        let provider = OAuth1Provider::new("ck", "cs", "at", "ats");
+
+        let req = reqwest::Request::new(
+            reqwest::Method::GET,
+            "https://api.twitter.com/2/users/123/likes?max_results=5&tweet.fields=created_at"
+                .parse()
+                .unwrap(),
+        );
+
+        let authenticated_req = provider.authenticate(req).await.unwrap();
+
+        // The query stays on the request URL while being covered by the signature
+        assert_eq!(
+            authenticated_req.url().query(),
+            Some("max_results=5&tweet.fields=created_at")
+        );
+        let auth_header = authenticated_req
+            .headers()
+            .get(reqwest::header::AUTHORIZATION)
+            .unwrap()
+            .to_str()
+            .unwrap();
+        assert!(auth_header.contains("oauth_signature="));
+        assert!(!auth_header.contains("max_results"));
+    }
+
+    #[test]
+    fn test_form_body_participates_in_signature() {
+        // Reference request from X's "Creating a signature" documentation
+        let provider = OAuth1Provider::new(
+            "xvz1evFS4wEEPTGEFPHBog",
+            "kAcSOqF21Fu85e7zjz7ZN2U4ZRhfV3WpwPAoE3Z7kBw",
+            "370773112-GmHxMAgYyLbNEtIKZeRNFsMKPR9EyMZeS9weJAEb",
+            "LswwdoUaIvS8ltyTt5jkRh4J50vUPVVHtR2YPi5kE",
+        );
+        let body = crate::builder::request::Body::form([(
+            "status",
+            "Hello Ladies + Gentlemen, a signed OAuth request!",
+        )]);
+
+        let mut req = reqwest::Request::new(
+            reqwest::Method::POST,
+            "https://api.twitter.com/1.1/statuses/update.json?include_entities=true"
+                .parse()
+                .unwrap(),
+        );
+        *req.body_mut() = Some(body.to_bytes().unwrap().into());
+        req.headers_mut().insert(
+            reqwest::header::CONTENT_TYPE,
+            reqwest::header::HeaderValue::from_static(body.content_type()),
+        );
+
+        let params = OAuth1Provider::signature_params(&req).unwrap();
+        assert_eq!(params.as_ref().len(), 2);
+
+        let mut signer = provider.signer();
+        signer
+            .nonce("kYjzVBB8Y0ZFabxSWbWovY3uYSQ2pTgmZeNu2VS4cg")
+            .timestamp(std::num::NonZeroU64::new(1318622958));
+        let header = signer.authorize("POST", OAuth1Provider::signing_uri(req.url()), &params);
+
+        assert!(
+            header.contains(r#"oauth_signature="hCtSmYh%2BiHYCEqBWrE7C7hYmtUk%3D""#),
+            "unexpected header: {}",
+            header
+        );
+    }
+
+    #[test]
+    fn test_json_body_not_included_in_signature() {
+        let mut req = reqwest::Request::new(
+            reqwest::Method::POST,
+            "https://api.twitter.com/2/tweets".parse().unwrap(),
+        );
+        *req.body_mut() = Some(br#"{"text":"hello"}"#.to_vec().into());
+        req.headers_mut().insert(
+            reqwest::header::CONTENT_TYPE,
+            reqwest::header::HeaderValue::from_static("application/json"),
+        );
+
+        let params = OAuth1Provider::signature_params(&req).unwrap();
+        assert!(params.as_ref().is_empty());
+    }
+
     #[tokio::test]
     async fn test_signature_generation_delete_request() {
         let provider = OAuth1Provider::new("ck", "cs", "at", "ats");
@@ -469,4 +815,70 @@ mod tests {
         // Headers should be different due to unique nonce and timestamp
         assert_ne!(header1, header2);
     }
+
+    #[tokio::test]
+    async fn test_three_legged_flow() {
+        use wiremock::matchers::{header_regex, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/oauth/request_token"))
+            .and(header_regex("authorization", r#"oauth_callback="oob""#))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                "oauth_token=req-token&oauth_token_secret=req-secret&oauth_callback_confirmed=true",
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/oauth/access_token"))
+            .and(header_regex("authorization", r#"oauth_token="req-token""#))
+            .and(header_regex("authorization", r#"oauth_verifier="1234567""#))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                "oauth_token=42-user-token&oauth_token_secret=user-secret&user_id=42&screen_name=rustacean",
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let flow = OAuth1Flow::new("ck", "cs").unwrap().base_url(server.uri());
+
+        let request_token = flow.request_token(OOB_CALLBACK).await.unwrap();
+        assert_eq!(
+            request_token,
+            RequestToken {
+                token: "req-token".to_string(),
+                secret: "req-secret".to_string(),
+                callback_confirmed: true,
+            }
+        );
+        assert_eq!(
+            flow.authorize_url(&request_token).unwrap(),
+            format!("{}/oauth/authorize?oauth_token=req-token", server.uri())
+        );
+
+        let access = flow.access_token(&request_token, "1234567").await.unwrap();
+        assert_eq!(access.token, "42-user-token");
+        assert_eq!(access.secret, "user-secret");
+        assert_eq!(access.user_id.as_deref(), Some("42"));
+        assert_eq!(access.screen_name.as_deref(), Some("rustacean"));
+    }
+
+    #[tokio::test]
+    async fn test_request_token_rejected() {
+        use wiremock::matchers::path;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(path("/oauth/request_token"))
+            .respond_with(ResponseTemplate::new(401).set_body_json(serde_json::json!({
+                "errors": [{"code": 32, "message": "Could not authenticate you."}]
+            })))
+            .mount(&server)
+            .await;
+
+        let flow = OAuth1Flow::new("ck", "bad").unwrap().base_url(server.uri());
+        assert!(flow.request_token(OOB_CALLBACK).await.is_err());
+    }
 }
@@ -1 +1,215 @@
 //! OAuth 2.0 PKCE authentication implementation
+//!
+//! User access tokens obtained through the OAuth 2.0 authorization code flow
+//! with PKCE act on behalf of the user who authorized the app, limited to the
+//! scopes they granted. [`OAuth2UserProvider`] authenticates requests with
+//! such a token and can revoke it, e.g. when the user logs out.
+
+use crate::util::lock_unpoisoned;
+use async_trait::async_trait;
+
+use crate::auth::{AuthProvider, OAuth2Http};
+use crate::client::{DEFAULT_BASE_URL, ReqwestClient};
+use crate::endpoints::paths;
+use crate::error::{Error, Result};
+use std::sync::{Arc, Mutex};
+
+/// Token type hint sent when revoking an access token
+const ACCESS_TOKEN_HINT: &str = "access_token";
+
+/// OAuth 2.0 user-context token provider
+///
+/// Adds `Authorization: Bearer <token>` to every request. App-only
+/// endpoints (full-archive search and counts, the filtered and sample
+/// streams, compliance jobs) are not supported.
+///
+/// Clones share the token, so revoking it through one clone stops all of
+/// them from authenticating.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use x_api_client::auth::oauth2::OAuth2UserProvider;
+///
+/// let provider = OAuth2UserProvider::new("client_id", access_token);
+/// let client = Client::builder().auth(Arc::new(provider.clone())).build()?;
+///
+/// // On logout
+/// provider.revoke().await?;
+/// ```
+#[derive(Clone)]
+pub struct OAuth2UserProvider {
+    client_id: String,
+    client_secret: Option<String>,
+    /// The access token, `None` once revoked
+    token: Arc<Mutex<Option<String>>>,
+    base_url: String,
+    http: OAuth2Http,
+}
+
+impl OAuth2UserProvider {
+    /// Create a provider for a user access token issued to `client_id`
+    pub fn new(client_id: impl Into<String>, access_token: impl Into<String>) -> Self {
+        Self {
+            client_id: client_id.into(),
+            client_secret: None,
+            token: Arc::new(Mutex::new(Some(access_token.into()))),
+            base_url: DEFAULT_BASE_URL.to_string(),
+            http: OAuth2Http::default(),
+        }
+    }
+
+    /// Set the client secret of a confidential client
+    ///
+    /// Confidential clients authenticate token revocation with HTTP Basic
+    /// authentication; public clients only send their client ID.
+    pub fn client_secret(mut self, secret: impl Into<String>) -> Self {
+        self.client_secret = Some(secret.into());
+        self
+    }
+
+    /// Set the base URL of the OAuth 2.0 endpoints
+    ///
+    /// Default: `"https://api.twitter.com"`
+    pub fn base_url(mut self, url: impl Into<String>) -> Self {
+        self.base_url = url.into();
+        self
+    }
+
+    /// Send requests to the OAuth 2.0 endpoints through `http`
+    ///
+    /// Default: a client with default settings, built on first use
+    pub fn http_client(mut self, http: ReqwestClient) -> Self {
+        self.http = OAuth2Http::new(http);
+        self
+    }
+
+    /// Revoke the access token, after which this provider refuses to
+    /// authenticate
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Authentication`] if the token was already revoked,
+    /// or the API error if the revocation fails (the token then stays
+    /// usable).
+    pub async fn revoke(&self) -> Result<()> {
+        let token = self.token()?;
+        let url = format!("{}{}", self.base_url, paths::OAUTH2_REVOKE);
+        let basic = self
+            .client_secret
+            .as_deref()
+            .map(|secret| (self.client_id.as_str(), secret));
+        self.http
+            .post_form(
+                &url,
+                basic,
+                &[
+                    ("token", &token),
+                    ("client_id", &self.client_id),
+                    ("token_type_hint", ACCESS_TOKEN_HINT),
+                ],
+            )
+            .await?;
+
+        *lock_unpoisoned(&self.token) = None;
+        Ok(())
+    }
+
+    /// The current access token, unless revoked
+    fn token(&self) -> Result<String> {
+        lock_unpoisoned(&self.token)
+            .clone()
+            .ok_or_else(|| Error::Authentication("OAuth 2.0 token has been revoked".to_string()))
+    }
+
+    /// Check if the given endpoint only accepts app-only authentication
+    fn is_app_only_endpoint(endpoint: &str) -> bool {
+        const APP_ONLY_PATTERNS: &[&str] = &[
+            paths::TWEETS_SEARCH_ALL,
+            paths::TWEETS_SEARCH_STREAM,
+            paths::TWEETS_SAMPLE,
+            paths::TWEETS_COUNTS_RECENT,
+            paths::TWEETS_COUNTS_ALL,
+            paths::COMPLIANCE_JOBS,
+            paths::USAGE_TWEETS,
+            paths::TWEETS_COMPLIANCE_STREAM,
+            paths::USERS_COMPLIANCE_STREAM,
+        ];
+        APP_ONLY_PATTERNS
+            .iter()
+            .any(|&pattern| endpoint.starts_with(pattern))
+    }
+}
+
+#[async_trait]
+impl AuthProvider for OAuth2UserProvider {
+    async fn authenticate(&self, mut req: reqwest::Request) -> Result<reqwest::Request> {
+        if !self.supports_endpoint(req.url().path()) {
+            return Err(Error::Authentication(format!(
+                "Endpoint '{}' does not support OAuth 2.0 user context authentication",
+                req.url().path()
+            )));
+        }
+
+        let token = self.token()?;
+        req.headers_mut().insert(
+            reqwest::header::AUTHORIZATION,
+            reqwest::header::HeaderValue::from_str(&format!("Bearer {}", token))
+                .map_err(|e| Error::Authentication(format!("Invalid access token: {}", e)))?,
+        );
+        Ok(req)
+    }
+
+    fn supports_endpoint(&self, endpoint: &str) -> bool {
+        !Self::is_app_only_endpoint(endpoint)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{body_string, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_revoked_token_refuses_to_authenticate() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/2/oauth2/revoke"))
+            .and(body_string(
+                "token=user-token&client_id=client-id&token_type_hint=access_token",
+            ))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({"revoked": true})),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = OAuth2UserProvider::new("client-id", "user-token")
+            .base_url(server.uri())
+            .http_client(ReqwestClient::new().unwrap());
+        let request = || {
+            reqwest::Request::new(
+                reqwest::Method::GET,
+                format!("{}/2/users/me", server.uri()).parse().unwrap(),
+            )
+        };
+
+        let authenticated = provider.authenticate(request()).await.unwrap();
+        assert_eq!(
+            authenticated.headers()[reqwest::header::AUTHORIZATION],
+            "Bearer user-token"
+        );
+
+        provider.revoke().await.unwrap();
+        assert!(matches!(
+            provider.authenticate(request()).await,
+            Err(Error::Authentication(_))
+        ));
+        assert!(matches!(
+            provider.revoke().await,
+            Err(Error::Authentication(_))
+        ));
+    }
+}
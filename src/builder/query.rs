@@ -1 +1,1415 @@
 //! Query parameter builders
+//!
+//! Every options type accepts `extra_query(key, value)` as an escape hatch
+//! for parameters the crate does not model yet. Extra parameters are
+//! appended after the typed ones and, like any query parameter, are covered
+//! by the OAuth 1.0a signature.
+
+use crate::error::{Error, Result};
+use crate::models::common::Lang;
+use crate::models::space::SpaceState;
+use crate::pagination::cursor::{PageQuery, PaginationSpec};
+use chrono::{DateTime, SecondsFormat, TimeDelta, Utc};
+use std::collections::BTreeMap;
+use std::ops::RangeInclusive;
+
+/// Query parameter setting the page size
+pub(crate) const MAX_RESULTS_PARAM: &str = "max_results";
+
+/// Page sizes accepted by recent search
+pub const SEARCH_RECENT_MAX_RESULTS: RangeInclusive<u32> = 10..=100;
+
+/// Page sizes accepted by full-archive search
+pub const SEARCH_ALL_MAX_RESULTS: RangeInclusive<u32> = 10..=500;
+
+/// Page sizes accepted by user timelines and mentions
+pub const TIMELINE_MAX_RESULTS: RangeInclusive<u32> = 5..=100;
+
+/// Page sizes accepted by follower and following lookups
+pub const FOLLOWS_MAX_RESULTS: RangeInclusive<u32> = 1..=1000;
+
+/// Page sizes accepted by List lookups and List tweets
+pub const LISTS_MAX_RESULTS: RangeInclusive<u32> = 1..=100;
+
+/// Page sizes accepted by direct message event lookups
+pub const DM_EVENTS_MAX_RESULTS: RangeInclusive<u32> = 1..=100;
+
+/// Result counts accepted by Space search
+pub const SPACE_SEARCH_MAX_RESULTS: RangeInclusive<u32> = 1..=100;
+
+/// Query parameter with a search query
+pub(crate) const QUERY_PARAM: &str = "query";
+
+/// Query parameter filtering Spaces by state
+pub(crate) const STATE_PARAM: &str = "state";
+
+/// Query parameter with the oldest time of a search window
+pub(crate) const START_TIME_PARAM: &str = "start_time";
+
+/// Query parameter with the newest time of a search window
+pub(crate) const END_TIME_PARAM: &str = "end_time";
+
+/// Query parameter selecting the bucket size of tweet counts
+pub(crate) const GRANULARITY_PARAM: &str = "granularity";
+
+/// Query parameter selecting the order of search results
+pub(crate) const SORT_ORDER_PARAM: &str = "sort_order";
+
+/// How far back recent search reaches
+pub const RECENT_SEARCH_WINDOW: TimeDelta = TimeDelta::days(7);
+
+/// Query parameter listing IDs for a bulk lookup
+pub(crate) const IDS_PARAM: &str = "ids";
+
+/// Maximum number of distinct IDs in one bulk lookup
+pub const MAX_LOOKUP_IDS: usize = 100;
+
+/// Query parameter listing expansions
+pub const EXPANSIONS_PARAM: &str = "expansions";
+
+/// Query parameter selecting Tweet fields
+pub const TWEET_FIELDS_PARAM: &str = "tweet.fields";
+
+/// Query parameter selecting User fields
+pub const USER_FIELDS_PARAM: &str = "user.fields";
+
+/// Query parameter selecting Media fields
+pub const MEDIA_FIELDS_PARAM: &str = "media.fields";
+
+/// Query parameter selecting Poll fields
+pub const POLL_FIELDS_PARAM: &str = "poll.fields";
+
+/// Query parameter selecting Place fields
+pub const PLACE_FIELDS_PARAM: &str = "place.fields";
+
+/// Query parameter selecting Space fields
+pub const SPACE_FIELDS_PARAM: &str = "space.fields";
+
+/// Query parameter selecting List fields
+pub const LIST_FIELDS_PARAM: &str = "list.fields";
+
+/// Query parameter selecting direct message event fields
+pub const DM_EVENT_FIELDS_PARAM: &str = "dm_event.fields";
+
+/// Tweet field naming the app a tweet was posted with
+pub(crate) const SOURCE_FIELD: &str = "source";
+
+/// Options for Space search
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SpaceSearchOptions {
+    state: Option<SpaceState>,
+    max_results: Option<u32>,
+    fields: Option<Fields>,
+    extra_query: Vec<(String, String)>,
+}
+
+impl SpaceSearchOptions {
+    /// Create options using the endpoint defaults (all states)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a query parameter not modeled by these options
+    pub fn extra_query(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.extra_query.push((key.into(), value.into()));
+        self
+    }
+
+    /// Only return Spaces in this state
+    pub fn state(mut self, state: SpaceState) -> Self {
+        self.state = Some(state);
+        self
+    }
+
+    /// Set the maximum number of Spaces returned
+    pub fn max_results(mut self, max_results: u32) -> Self {
+        self.max_results = Some(max_results);
+        self
+    }
+
+    /// Request fields and expansions
+    pub fn fields(mut self, fields: Fields) -> Self {
+        self.fields = Some(fields);
+        self
+    }
+
+    /// Check `max_results` against [`SPACE_SEARCH_MAX_RESULTS`]
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidRequest`] naming the allowed range if
+    /// `max_results` is outside it.
+    pub fn validate(&self) -> Result<()> {
+        validate_max_results(self.max_results, &SPACE_SEARCH_MAX_RESULTS)
+    }
+
+    /// Query parameters for a search for `query`
+    pub(crate) fn to_query(&self, query: &str) -> Vec<(String, String)> {
+        let mut params = vec![(QUERY_PARAM.to_string(), query.to_string())];
+        if let Some(state) = &self.state {
+            params.push((STATE_PARAM.to_string(), state.as_query_value().to_string()));
+        }
+        if let Some(max_results) = self.max_results {
+            params.push((MAX_RESULTS_PARAM.to_string(), max_results.to_string()));
+        }
+        if let Some(fields) = &self.fields {
+            params.extend(fields.to_query());
+        }
+        params.extend(self.extra_query.iter().cloned());
+        params
+    }
+}
+
+/// Tweet archive searched by a query
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SearchScope {
+    /// Tweets from the last 7 days
+    Recent,
+    /// The full archive
+    All,
+}
+
+/// Search operators that only full-archive search accepts
+///
+/// Recent search rejects these with HTTP 400.
+pub const FULL_ARCHIVE_ONLY_OPERATORS: &[&str] = &[
+    "bounding_box",
+    "place",
+    "place_country",
+    "point_radius",
+    "sample",
+];
+
+/// Search operator matching the language X classified a tweet as
+const LANG_OPERATOR: &str = "lang";
+
+/// Builder for search query strings
+///
+/// Clauses are joined with spaces, which the API treats as AND. Use
+/// [`validate_for`](Self::validate_for) to reject operators the target
+/// archive does not support before spending a request on them.
+///
+/// # Example
+///
+/// ```rust
+/// use x_api_client::builder::query::{QueryBuilder, SearchScope};
+///
+/// let query = QueryBuilder::new()
+///     .term("\"launch day\"")
+///     .operator("from", "XDevelopers")
+///     .operator("place_country", "US");
+/// assert_eq!(query.build(), "\"launch day\" from:XDevelopers place_country:US");
+/// assert!(query.validate_for(SearchScope::All).is_ok());
+/// assert!(query.validate_for(SearchScope::Recent).is_err());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QueryBuilder {
+    clauses: Vec<String>,
+}
+
+impl QueryBuilder {
+    /// Create an empty query
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a raw clause (keyword, quoted phrase, group, or operator)
+    pub fn term(mut self, term: impl Into<String>) -> Self {
+        self.clauses.push(term.into());
+        self
+    }
+
+    /// Add an operator clause `name:value`
+    pub fn operator(mut self, name: &str, value: impl AsRef<str>) -> Self {
+        self.clauses.push(format!("{}:{}", name, value.as_ref()));
+        self
+    }
+
+    /// Add a `lang:` operator restricting results to `lang`
+    pub fn lang(self, lang: &Lang) -> Self {
+        self.operator(LANG_OPERATOR, lang)
+    }
+
+    /// Render the query string
+    pub fn build(&self) -> String {
+        self.clauses.join(" ")
+    }
+
+    /// Check that every operator in the query is supported by `scope`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidRequest`] if the query is empty, or if it
+    /// uses one of the [`FULL_ARCHIVE_ONLY_OPERATORS`] and `scope` is
+    /// [`SearchScope::Recent`].
+    pub fn validate_for(&self, scope: SearchScope) -> Result<()> {
+        let query = self.build();
+        if query.trim().is_empty() {
+            return Err(Error::InvalidRequest("Search query is empty".to_string()));
+        }
+        if scope == SearchScope::Recent {
+            if let Some(operator) = query_operators(&query)
+                .find(|operator| FULL_ARCHIVE_ONLY_OPERATORS.contains(operator))
+            {
+                return Err(Error::InvalidRequest(format!(
+                    "Operator '{}:' is only supported by full-archive search",
+                    operator
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Names of the `name:value` operators in a search query
+///
+/// Text inside quoted phrases is skipped, as are negation and grouping
+/// characters in front of an operator.
+fn query_operators(query: &str) -> impl Iterator<Item = &str> {
+    query
+        .split('"')
+        .step_by(2)
+        .flat_map(str::split_whitespace)
+        .filter_map(|token| {
+            let (name, _) = token.trim_start_matches(['-', '(']).split_once(':')?;
+            (!name.is_empty()).then_some(name)
+        })
+}
+
+/// Options for tweet search
+///
+/// Time bounds are sent as RFC 3339 timestamps with second precision.
+///
+/// # Example
+///
+/// ```rust
+/// use chrono::{TimeZone, Utc};
+/// use x_api_client::builder::query::{SearchOptions, SearchScope};
+///
+/// let opts = SearchOptions::new()
+///     .start_time(Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap())
+///     .end_time(Utc.with_ymd_and_hms(2024, 1, 2, 0, 0, 0).unwrap());
+/// assert!(opts.validate(SearchScope::All, Utc::now()).is_ok());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchOptions {
+    start_time: Option<DateTime<Utc>>,
+    end_time: Option<DateTime<Utc>>,
+    max_results: Option<u32>,
+    sort_order: Option<SortOrder>,
+    fields: Option<Fields>,
+    next_token: Option<String>,
+    extra_query: Vec<(String, String)>,
+}
+
+impl SearchOptions {
+    /// Create options using the endpoint defaults
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a query parameter not modeled by these options
+    pub fn extra_query(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.extra_query.push((key.into(), value.into()));
+        self
+    }
+
+    /// Only match tweets created at or after `time`
+    pub fn start_time(mut self, time: DateTime<Utc>) -> Self {
+        self.start_time = Some(time);
+        self
+    }
+
+    /// Only match tweets created before `time`
+    pub fn end_time(mut self, time: DateTime<Utc>) -> Self {
+        self.end_time = Some(time);
+        self
+    }
+
+    /// Set the number of results requested per page
+    pub fn max_results(mut self, max_results: u32) -> Self {
+        self.max_results = Some(max_results);
+        self
+    }
+
+    /// Set the order of results (the API default is [`SortOrder::Recency`])
+    pub fn sort_order(mut self, sort_order: SortOrder) -> Self {
+        self.sort_order = Some(sort_order);
+        self
+    }
+
+    /// Request fields and expansions
+    pub fn fields(mut self, fields: Fields) -> Self {
+        self.fields = Some(fields);
+        self
+    }
+
+    /// Start from the page at `token` (a `meta.next_token` from an earlier
+    /// response) instead of the first page
+    pub fn next_token(mut self, token: impl Into<String>) -> Self {
+        self.next_token = Some(token.into());
+        self
+    }
+
+    /// Get the configured start time
+    pub fn get_start_time(&self) -> Option<DateTime<Utc>> {
+        self.start_time
+    }
+
+    /// Get the configured end time
+    pub fn get_end_time(&self) -> Option<DateTime<Utc>> {
+        self.end_time
+    }
+
+    /// Get the token of the page to start from
+    pub fn get_next_token(&self) -> Option<&str> {
+        self.next_token.as_deref()
+    }
+
+    /// Check the time bounds for a search of `scope` made at `now`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidRequest`] if the start time is not before the
+    /// end time, if `max_results` is outside [`SEARCH_RECENT_MAX_RESULTS`]
+    /// or [`SEARCH_ALL_MAX_RESULTS`] for the scope, or, for
+    /// [`SearchScope::Recent`], if the window is longer than
+    /// [`RECENT_SEARCH_WINDOW`] or starts before it.
+    pub fn validate(&self, scope: SearchScope, now: DateTime<Utc>) -> Result<()> {
+        let page_sizes = match scope {
+            SearchScope::Recent => SEARCH_RECENT_MAX_RESULTS,
+            SearchScope::All => SEARCH_ALL_MAX_RESULTS,
+        };
+        validate_max_results(self.max_results, &page_sizes)?;
+        validate_window(self.start_time, self.end_time, scope, now)
+    }
+
+    /// Query parameters for the page of a search for `query` at the
+    /// configured [`next_token`](Self::next_token)
+    pub(crate) fn to_query(&self, query: &str) -> Vec<(String, String)> {
+        self.page_query(query)
+            .page(PaginationSpec::NEXT_TOKEN, self.get_next_token())
+    }
+
+    /// Query parameters for any page of a search for `query`
+    pub(crate) fn page_query(&self, query: &str) -> PageQuery {
+        let mut params = vec![(QUERY_PARAM.to_string(), query.to_string())];
+        if let Some(start) = self.start_time {
+            params.push((START_TIME_PARAM.to_string(), format_time(start)));
+        }
+        if let Some(end) = self.end_time {
+            params.push((END_TIME_PARAM.to_string(), format_time(end)));
+        }
+        if let Some(max_results) = self.max_results {
+            params.push((MAX_RESULTS_PARAM.to_string(), max_results.to_string()));
+        }
+        if let Some(sort_order) = self.sort_order {
+            params.push((
+                SORT_ORDER_PARAM.to_string(),
+                sort_order.as_query_value().to_string(),
+            ));
+        }
+        if let Some(fields) = &self.fields {
+            params.extend(fields.to_query());
+        }
+        PageQuery::new(params, &self.extra_query)
+    }
+}
+
+/// Order of search results
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum SortOrder {
+    /// Newest tweets first (the API default)
+    #[default]
+    Recency,
+    /// Most relevant tweets first
+    Relevancy,
+}
+
+impl SortOrder {
+    /// Value of the `sort_order` query parameter
+    pub fn as_query_value(self) -> &'static str {
+        match self {
+            SortOrder::Recency => "recency",
+            SortOrder::Relevancy => "relevancy",
+        }
+    }
+}
+
+/// Bucket size of tweet counts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum Granularity {
+    /// Per-minute counts
+    Minute,
+    /// Per-hour counts (the API default)
+    #[default]
+    Hour,
+    /// Per-day counts
+    Day,
+}
+
+impl Granularity {
+    /// Value of the `granularity` query parameter
+    pub fn as_query_value(self) -> &'static str {
+        match self {
+            Granularity::Minute => "minute",
+            Granularity::Hour => "hour",
+            Granularity::Day => "day",
+        }
+    }
+}
+
+/// Options for tweet counts
+///
+/// Time bounds follow the same rules as [`SearchOptions`].
+///
+/// # Example
+///
+/// ```rust
+/// use x_api_client::builder::query::{CountOptions, Granularity};
+///
+/// let opts = CountOptions::new().granularity(Granularity::Day);
+/// assert_eq!(opts.get_granularity(), Some(Granularity::Day));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CountOptions {
+    start_time: Option<DateTime<Utc>>,
+    end_time: Option<DateTime<Utc>>,
+    granularity: Option<Granularity>,
+    extra_query: Vec<(String, String)>,
+}
+
+impl CountOptions {
+    /// Create options using the endpoint defaults
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a query parameter not modeled by these options
+    pub fn extra_query(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.extra_query.push((key.into(), value.into()));
+        self
+    }
+
+    /// Only count tweets created at or after `time`
+    pub fn start_time(mut self, time: DateTime<Utc>) -> Self {
+        self.start_time = Some(time);
+        self
+    }
+
+    /// Only count tweets created before `time`
+    pub fn end_time(mut self, time: DateTime<Utc>) -> Self {
+        self.end_time = Some(time);
+        self
+    }
+
+    /// Set the bucket size
+    pub fn granularity(mut self, granularity: Granularity) -> Self {
+        self.granularity = Some(granularity);
+        self
+    }
+
+    /// Get the configured bucket size
+    pub fn get_granularity(&self) -> Option<Granularity> {
+        self.granularity
+    }
+
+    /// Check the time bounds for counts over `scope` made at `now`
+    ///
+    /// # Errors
+    ///
+    /// See [`SearchOptions::validate`].
+    pub fn validate(&self, scope: SearchScope, now: DateTime<Utc>) -> Result<()> {
+        validate_window(self.start_time, self.end_time, scope, now)
+    }
+
+    /// Query parameters for the first page of counts of tweets matching
+    /// `query`
+    pub(crate) fn to_query(&self, query: &str) -> Vec<(String, String)> {
+        self.page_query(query)
+            .page(PaginationSpec::NEXT_TOKEN, None)
+    }
+
+    /// Query parameters for any page of counts of tweets matching `query`
+    pub(crate) fn page_query(&self, query: &str) -> PageQuery {
+        let mut params = vec![(QUERY_PARAM.to_string(), query.to_string())];
+        if let Some(start) = self.start_time {
+            params.push((START_TIME_PARAM.to_string(), format_time(start)));
+        }
+        if let Some(end) = self.end_time {
+            params.push((END_TIME_PARAM.to_string(), format_time(end)));
+        }
+        if let Some(granularity) = self.granularity {
+            params.push((
+                GRANULARITY_PARAM.to_string(),
+                granularity.as_query_value().to_string(),
+            ));
+        }
+        PageQuery::new(params, &self.extra_query)
+    }
+}
+
+/// Check a `max_results` value against the range an endpoint accepts
+fn validate_max_results(max_results: Option<u32>, allowed: &RangeInclusive<u32>) -> Result<()> {
+    match max_results {
+        Some(n) if !allowed.contains(&n) => Err(Error::InvalidRequest(format!(
+            "max_results must be between {} and {}, got {}",
+            allowed.start(),
+            allowed.end(),
+            n
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Check the bounds of a search window (see [`SearchOptions::validate`])
+fn validate_window(
+    start_time: Option<DateTime<Utc>>,
+    end_time: Option<DateTime<Utc>>,
+    scope: SearchScope,
+    now: DateTime<Utc>,
+) -> Result<()> {
+    if let (Some(start), Some(end)) = (start_time, end_time) {
+        if start >= end {
+            return Err(Error::InvalidRequest(format!(
+                "start_time {} must be before end_time {}",
+                format_time(start),
+                format_time(end)
+            )));
+        }
+        if scope == SearchScope::Recent && end - start > RECENT_SEARCH_WINDOW {
+            return Err(Error::InvalidRequest(format!(
+                "Recent search window cannot exceed {} days",
+                RECENT_SEARCH_WINDOW.num_days()
+            )));
+        }
+    }
+    if let Some(start) = start_time {
+        if scope == SearchScope::Recent && start < now - RECENT_SEARCH_WINDOW {
+            return Err(Error::InvalidRequest(format!(
+                "Recent search start_time {} is older than {} days",
+                format_time(start),
+                RECENT_SEARCH_WINDOW.num_days()
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Format a time bound the way the API expects (`2024-01-01T00:00:00Z`)
+pub(crate) fn format_time(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// Fields parameter that applies to the objects an expansion includes
+///
+/// Returns `None` for expansions this crate does not know about.
+fn expansion_fields_param(expansion: &str) -> Option<&'static str> {
+    match expansion {
+        "author_id"
+        | "in_reply_to_user_id"
+        | "entities.mentions.username"
+        | "referenced_tweets.id.author_id"
+        | "sender_id"
+        | "participant_ids"
+        | "creator_id"
+        | "host_ids"
+        | "speaker_ids"
+        | "invited_user_ids"
+        | "owner_id" => Some(USER_FIELDS_PARAM),
+        "referenced_tweets.id"
+        | "edit_history_tweet_ids"
+        | "pinned_tweet_id"
+        | "most_recent_tweet_id"
+        | "attachments.media_source_tweet_id" => Some(TWEET_FIELDS_PARAM),
+        "attachments.media_keys" => Some(MEDIA_FIELDS_PARAM),
+        "attachments.poll_ids" => Some(POLL_FIELDS_PARAM),
+        "geo.place_id" => Some(PLACE_FIELDS_PARAM),
+        _ => None,
+    }
+}
+
+/// X API access tier, in increasing order of access
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum AccessTier {
+    /// Free tier
+    Free,
+    /// Basic tier
+    Basic,
+    /// Pro tier
+    Pro,
+    /// Enterprise tier
+    Enterprise,
+}
+
+/// Metrics fields the API rejects with HTTP 403 below [`ELEVATED_METRICS_TIER`]
+const ELEVATED_METRICS_FIELDS: &[&str] =
+    &["non_public_metrics", "organic_metrics", "promoted_metrics"];
+
+/// Lowest tier serving [`ELEVATED_METRICS_FIELDS`]
+const ELEVATED_METRICS_TIER: AccessTier = AccessTier::Pro;
+
+/// Lowest access tier at which `field` of the `param` fields parameter can
+/// be requested
+fn field_min_tier(param: &str, field: &str) -> AccessTier {
+    let has_metrics = param == TWEET_FIELDS_PARAM || param == MEDIA_FIELDS_PARAM;
+    if has_metrics && ELEVATED_METRICS_FIELDS.contains(&field) {
+        ELEVATED_METRICS_TIER
+    } else {
+        AccessTier::Free
+    }
+}
+
+/// How [`FieldsBuilder::build`] treats an expansion requested without any
+/// fields for the objects it includes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExpansionCheck {
+    /// Build anyway and report the problem in [`Fields::warnings`]
+    #[default]
+    Lenient,
+    /// Fail the build with [`Error::InvalidRequest`]
+    Strict,
+}
+
+/// How [`FieldsBuilder::build`] treats fields unavailable at the configured
+/// [`AccessTier`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TierCheck {
+    /// Drop the fields and report them in [`Fields::warnings`]
+    #[default]
+    Warn,
+    /// Fail the build with [`Error::InvalidRequest`]
+    Strict,
+}
+
+/// Builder for `expansions` and `*.fields` query parameters
+///
+/// An expansion only includes the default fields of the expanded objects
+/// unless the matching `*.fields` parameter is also set (e.g.
+/// `attachments.media_keys` without `media.fields` yields media with just a
+/// key and type). [`build`](Self::build) checks for this according to the
+/// configured [`ExpansionCheck`], and fields the configured
+/// [`access_tier`](Self::access_tier) cannot request according to the
+/// configured [`TierCheck`].
+///
+/// # Example
+///
+/// ```rust
+/// use x_api_client::builder::query::{ExpansionCheck, FieldsBuilder};
+///
+/// let fields = FieldsBuilder::new()
+///     .expansions(["attachments.media_keys"])
+///     .media_fields(["url", "alt_text"])
+///     .check(ExpansionCheck::Strict)
+///     .build()
+///     .unwrap();
+/// assert!(fields.warnings().is_empty());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FieldsBuilder {
+    expansions: Vec<String>,
+    fields: BTreeMap<&'static str, Vec<String>>,
+    check: ExpansionCheck,
+    access_tier: Option<AccessTier>,
+    tier_check: TierCheck,
+}
+
+impl FieldsBuilder {
+    /// Create an empty builder
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request expansions
+    pub fn expansions<I, S>(mut self, expansions: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        extend_unique(&mut self.expansions, expansions);
+        self
+    }
+
+    /// Select Tweet fields
+    pub fn tweet_fields<I, S>(self, fields: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.fields(TWEET_FIELDS_PARAM, fields)
+    }
+
+    /// Select User fields
+    pub fn user_fields<I, S>(self, fields: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.fields(USER_FIELDS_PARAM, fields)
+    }
+
+    /// Select Media fields
+    pub fn media_fields<I, S>(self, fields: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.fields(MEDIA_FIELDS_PARAM, fields)
+    }
+
+    /// Select Poll fields
+    pub fn poll_fields<I, S>(self, fields: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.fields(POLL_FIELDS_PARAM, fields)
+    }
+
+    /// Select Place fields
+    pub fn place_fields<I, S>(self, fields: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.fields(PLACE_FIELDS_PARAM, fields)
+    }
+
+    /// Select Space fields
+    pub fn space_fields<I, S>(self, fields: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.fields(SPACE_FIELDS_PARAM, fields)
+    }
+
+    /// Select List fields
+    pub fn list_fields<I, S>(self, fields: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.fields(LIST_FIELDS_PARAM, fields)
+    }
+
+    /// Select direct message event fields
+    pub fn dm_event_fields<I, S>(self, fields: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.fields(DM_EVENT_FIELDS_PARAM, fields)
+    }
+
+    /// Set how expansions without related fields are treated
+    ///
+    /// Default: [`ExpansionCheck::Lenient`]
+    pub fn check(mut self, check: ExpansionCheck) -> Self {
+        self.check = check;
+        self
+    }
+
+    /// Check fields against the access tier of the app
+    ///
+    /// Fields the tier cannot request (e.g. `non_public_metrics` below
+    /// [`AccessTier::Pro`]) would fail the whole request with HTTP 403; they
+    /// are dropped or rejected by [`build`](Self::build) according to the
+    /// configured [`TierCheck`]. Default: no tier check.
+    pub fn access_tier(mut self, tier: AccessTier) -> Self {
+        self.access_tier = Some(tier);
+        self
+    }
+
+    /// Set how fields unavailable at the access tier are treated
+    ///
+    /// Independent of [`check`](Self::check). Default: [`TierCheck::Warn`]
+    pub fn tier_check(mut self, tier_check: TierCheck) -> Self {
+        self.tier_check = tier_check;
+        self
+    }
+
+    /// Build the query parameters
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidRequest`] if:
+    /// - under [`TierCheck::Strict`], a field is unavailable at the
+    ///   configured access tier
+    /// - under [`ExpansionCheck::Strict`], an expansion is requested without
+    ///   any fields for the objects it includes
+    pub fn build(mut self) -> Result<Fields> {
+        let mut warnings = Vec::new();
+        if let Some(tier) = self.access_tier {
+            for (param, values) in self.fields.iter_mut() {
+                values.retain(|field| {
+                    let required = field_min_tier(param, field);
+                    let available = required <= tier;
+                    if !available {
+                        warnings.push(format!(
+                            "Field '{}={}' requires the {:?} access tier, app has {:?}",
+                            param, field, required, tier
+                        ));
+                    }
+                    available
+                });
+            }
+            self.fields.retain(|_, values| !values.is_empty());
+        }
+        if self.tier_check == TierCheck::Strict && !warnings.is_empty() {
+            return Err(Error::InvalidRequest(warnings.join("; ")));
+        }
+
+        let expansion_warnings: Vec<String> = self
+            .expansions
+            .iter()
+            .filter_map(|expansion| {
+                let param = expansion_fields_param(expansion)?;
+                (!self.fields.contains_key(param)).then(|| {
+                    format!(
+                        "Expansion '{}' requested without '{}'; expanded objects carry default fields only",
+                        expansion, param
+                    )
+                })
+            })
+            .collect();
+        if self.check == ExpansionCheck::Strict && !expansion_warnings.is_empty() {
+            return Err(Error::InvalidRequest(expansion_warnings.join("; ")));
+        }
+        warnings.extend(expansion_warnings);
+
+        Ok(Fields {
+            expansions: self.expansions,
+            fields: self.fields,
+            warnings,
+        })
+    }
+
+    fn fields<I, S>(mut self, param: &'static str, fields: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        extend_unique(self.fields.entry(param).or_default(), fields);
+        self
+    }
+}
+
+/// Validated `expansions` and `*.fields` query parameters
+///
+/// Created by [`FieldsBuilder::build`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Fields {
+    expansions: Vec<String>,
+    fields: BTreeMap<&'static str, Vec<String>>,
+    warnings: Vec<String>,
+}
+
+impl Fields {
+    /// Problems found by a lenient [`FieldsBuilder::build`]
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
+    /// Add values to the `param` fields parameter, skipping duplicates
+    pub(crate) fn with_values<I, S>(mut self, param: &'static str, values: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        extend_unique(self.fields.entry(param).or_default(), values);
+        self
+    }
+
+    /// Render as query parameters, expansions first
+    pub(crate) fn to_query(&self) -> Vec<(String, String)> {
+        let expansions = (!self.expansions.is_empty())
+            .then(|| (EXPANSIONS_PARAM.to_string(), self.expansions.join(",")));
+        expansions
+            .into_iter()
+            .chain(
+                self.fields
+                    .iter()
+                    .filter(|(_, values)| !values.is_empty())
+                    .map(|(param, values)| (param.to_string(), values.join(","))),
+            )
+            .collect()
+    }
+}
+
+/// Append values not already present, keeping first-seen order
+fn extend_unique<I, S>(target: &mut Vec<String>, values: I)
+where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+{
+    for value in values {
+        let value = value.into();
+        if !target.contains(&value) {
+            target.push(value);
+        }
+    }
+}
+
+/// Options for paginated endpoints
+///
+/// # Example
+///
+/// ```rust
+/// use x_api_client::builder::query::PaginationOptions;
+///
+/// let opts = PaginationOptions::new().max_results(100);
+/// assert_eq!(opts.get_max_results(), Some(100));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PaginationOptions {
+    max_results: Option<u32>,
+    pagination_token: Option<String>,
+    fields: Option<Fields>,
+    extra_query: Vec<(String, String)>,
+}
+
+impl PaginationOptions {
+    /// Create options using the endpoint defaults
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a query parameter not modeled by these options
+    pub fn extra_query(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.extra_query.push((key.into(), value.into()));
+        self
+    }
+
+    /// Set the number of results requested per page
+    pub fn max_results(mut self, max_results: u32) -> Self {
+        self.max_results = Some(max_results);
+        self
+    }
+
+    /// Start from the page identified by a previous response's `next_token`
+    pub fn pagination_token(mut self, token: impl Into<String>) -> Self {
+        self.pagination_token = Some(token.into());
+        self
+    }
+
+    /// Request fields and expansions
+    pub fn fields(mut self, fields: Fields) -> Self {
+        self.fields = Some(fields);
+        self
+    }
+
+    /// Get the configured page size
+    pub fn get_max_results(&self) -> Option<u32> {
+        self.max_results
+    }
+
+    /// Get the configured starting page token
+    pub fn get_pagination_token(&self) -> Option<&str> {
+        self.pagination_token.as_deref()
+    }
+
+    /// Check the page size against the range an endpoint accepts (e.g.
+    /// [`FOLLOWS_MAX_RESULTS`])
+    ///
+    /// Endpoints taking these options check it before sending anything.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidRequest`] naming the allowed range if
+    /// `max_results` is outside it.
+    pub fn validate(&self, allowed: RangeInclusive<u32>) -> Result<()> {
+        validate_max_results(self.max_results, &allowed)
+    }
+
+    /// Query parameters for the page at the configured
+    /// [`pagination_token`](Self::pagination_token)
+    pub(crate) fn to_query(&self) -> Vec<(String, String)> {
+        self.page_query().page(
+            PaginationSpec::PAGINATION_TOKEN,
+            self.get_pagination_token(),
+        )
+    }
+
+    /// Query parameters for any page
+    pub(crate) fn page_query(&self) -> PageQuery {
+        let typed = self
+            .max_results
+            .map(|n| (MAX_RESULTS_PARAM.to_string(), n.to_string()))
+            .into_iter()
+            .chain(self.fields.iter().flat_map(Fields::to_query))
+            .collect();
+        PageQuery::new(typed, &self.extra_query)
+    }
+}
+
+/// Options for bulk lookups by ID (e.g. [`Client::get_tweets`](crate::Client::get_tweets))
+///
+/// Duplicate IDs are only requested once. By default results come back in
+/// the order the API returns them, one entry per object found. With
+/// [`preserve_input_order`](Self::preserve_input_order), the results are
+/// aligned with the input instead: one entry per input ID, duplicates
+/// included, and `None` where the API returned nothing for that ID.
+///
+/// # Example
+///
+/// ```rust
+/// use x_api_client::builder::query::LookupOptions;
+///
+/// let opts = LookupOptions::new().preserve_input_order(true);
+/// assert!(opts.is_input_order_preserved());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LookupOptions {
+    preserve_input_order: bool,
+    fields: Option<Fields>,
+    extra_query: Vec<(String, String)>,
+}
+
+impl LookupOptions {
+    /// Create options returning results in API order
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a query parameter not modeled by these options
+    pub fn extra_query(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.extra_query.push((key.into(), value.into()));
+        self
+    }
+
+    /// Align results with the input IDs
+    pub fn preserve_input_order(mut self, preserve: bool) -> Self {
+        self.preserve_input_order = preserve;
+        self
+    }
+
+    /// Request fields and expansions
+    pub fn fields(mut self, fields: Fields) -> Self {
+        self.fields = Some(fields);
+        self
+    }
+
+    /// Also request `tweet.fields=source`, for [`Tweet::posted_via`](crate::models::tweet::Tweet::posted_via)
+    ///
+    /// Keeps any fields already requested.
+    pub fn with_source(mut self) -> Self {
+        let fields = self.fields.take().unwrap_or_default();
+        self.fields = Some(fields.with_values(TWEET_FIELDS_PARAM, [SOURCE_FIELD]));
+        self
+    }
+
+    /// Check whether results are aligned with the input IDs
+    pub fn is_input_order_preserved(&self) -> bool {
+        self.preserve_input_order
+    }
+
+    /// Get the requested fields
+    pub fn get_fields(&self) -> Option<&Fields> {
+        self.fields.as_ref()
+    }
+
+    /// Query parameters other than the IDs
+    pub(crate) fn to_query(&self) -> Vec<(String, String)> {
+        let mut params = self
+            .fields
+            .as_ref()
+            .map(Fields::to_query)
+            .unwrap_or_default();
+        params.extend(self.extra_query.iter().cloned());
+        params
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(day: u32, hour: u32) -> DateTime<Utc> {
+        use chrono::TimeZone;
+        Utc.with_ymd_and_hms(2024, 3, day, hour, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_search_time_bounds_rfc3339_seconds() {
+        let start = utc(1, 8) + TimeDelta::milliseconds(1_500);
+        let query = SearchOptions::new()
+            .start_time(start)
+            .end_time(utc(2, 0))
+            .to_query("rust");
+        assert_eq!(
+            query,
+            vec![
+                ("query".to_string(), "rust".to_string()),
+                ("start_time".to_string(), "2024-03-01T08:00:01Z".to_string()),
+                ("end_time".to_string(), "2024-03-02T00:00:00Z".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_search_sort_order() {
+        let sort_order_of = |query: Vec<(String, String)>| {
+            query
+                .iter()
+                .find(|(key, _)| key == "sort_order")
+                .map(|(_, value)| value.clone())
+        };
+
+        assert_eq!(sort_order_of(SearchOptions::new().to_query("rust")), None);
+        let relevancy = SearchOptions::new()
+            .sort_order(SortOrder::Relevancy)
+            .to_query("rust");
+        assert_eq!(sort_order_of(relevancy), Some("relevancy".to_string()));
+    }
+
+    #[test]
+    fn test_max_results_bounds() {
+        let now = utc(10, 0);
+        let err = SearchOptions::new()
+            .max_results(5)
+            .validate(SearchScope::Recent, now)
+            .unwrap_err();
+        assert!(
+            matches!(&err, Error::InvalidRequest(message) if message.contains("between 10 and 100"))
+        );
+        let archive_page = SearchOptions::new().max_results(500);
+        assert!(archive_page.validate(SearchScope::All, now).is_ok());
+        assert!(archive_page.validate(SearchScope::Recent, now).is_err());
+
+        let followers = PaginationOptions::new().max_results(1000);
+        assert!(followers.validate(FOLLOWS_MAX_RESULTS).is_ok());
+        assert!(followers.validate(TIMELINE_MAX_RESULTS).is_err());
+        assert!(
+            PaginationOptions::new()
+                .validate(TIMELINE_MAX_RESULTS)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_search_time_bounds_validation() {
+        let now = utc(20, 0);
+        let reversed = SearchOptions::new()
+            .start_time(utc(2, 0))
+            .end_time(utc(1, 0));
+        assert!(matches!(
+            reversed.validate(SearchScope::All, now),
+            Err(Error::InvalidRequest(_))
+        ));
+
+        let eight_days = SearchOptions::new()
+            .start_time(utc(1, 0))
+            .end_time(utc(9, 0));
+        assert!(eight_days.validate(SearchScope::All, now).is_ok());
+        assert!(matches!(
+            eight_days.validate(SearchScope::Recent, now),
+            Err(Error::InvalidRequest(_))
+        ));
+
+        let too_old = SearchOptions::new().start_time(utc(12, 0));
+        assert!(too_old.validate(SearchScope::Recent, now).is_err());
+        let recent = SearchOptions::new()
+            .start_time(utc(14, 0))
+            .end_time(utc(19, 0));
+        assert!(recent.validate(SearchScope::Recent, now).is_ok());
+    }
+
+    #[test]
+    fn test_query_operators_validated_for_scope() {
+        let query = QueryBuilder::new()
+            .term("(rust OR go)")
+            .operator("-is", "retweet")
+            .lang(&Lang::new("en").unwrap())
+            .term("point_radius:[2.355 48.857 16km]");
+        assert_eq!(
+            query.build(),
+            "(rust OR go) -is:retweet lang:en point_radius:[2.355 48.857 16km]"
+        );
+
+        assert!(query.validate_for(SearchScope::All).is_ok());
+        let err = query.validate_for(SearchScope::Recent).unwrap_err();
+        assert!(matches!(err, Error::InvalidRequest(ref msg) if msg.contains("point_radius")));
+
+        // Operators spelled inside quoted phrases are plain text
+        let phrase = QueryBuilder::new().term("\"sample:10 results\"");
+        assert!(phrase.validate_for(SearchScope::Recent).is_ok());
+
+        assert!(QueryBuilder::new().validate_for(SearchScope::All).is_err());
+    }
+
+    #[test]
+    fn test_pagination_query() {
+        assert!(PaginationOptions::new().to_query().is_empty());
+
+        let opts = PaginationOptions::new()
+            .extra_query("sort", "asc")
+            .max_results(50)
+            .pagination_token("abc");
+        assert_eq!(
+            opts.to_query(),
+            vec![
+                ("max_results".to_string(), "50".to_string()),
+                ("pagination_token".to_string(), "abc".to_string()),
+                ("sort".to_string(), "asc".to_string()),
+            ]
+        );
+        assert_eq!(opts.get_pagination_token(), Some("abc"));
+    }
+
+    #[test]
+    fn test_extra_query_appended_after_typed_params() {
+        let query = SearchOptions::new()
+            .extra_query("sort_order", "relevancy")
+            .max_results(10)
+            .next_token("next")
+            .to_query("rust");
+        assert_eq!(
+            query.last(),
+            Some(&("sort_order".to_string(), "relevancy".to_string()))
+        );
+        assert_eq!(query.len(), 4);
+    }
+
+    #[test]
+    fn test_expansion_with_related_fields() {
+        let fields = FieldsBuilder::new()
+            .expansions(["attachments.media_keys", "author_id"])
+            .media_fields(["url", "alt_text"])
+            .user_fields(["username"])
+            .tweet_fields(["created_at", "created_at"])
+            .check(ExpansionCheck::Strict)
+            .build()
+            .unwrap();
+
+        assert!(fields.warnings().is_empty());
+        assert_eq!(
+            fields.to_query(),
+            vec![
+                (
+                    "expansions".to_string(),
+                    "attachments.media_keys,author_id".to_string()
+                ),
+                ("media.fields".to_string(), "url,alt_text".to_string()),
+                ("tweet.fields".to_string(), "created_at".to_string()),
+                ("user.fields".to_string(), "username".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_nested_referenced_tweet_expansions() {
+        let fields = FieldsBuilder::new()
+            .expansions(["referenced_tweets.id", "referenced_tweets.id.author_id"])
+            .tweet_fields(["author_id"])
+            .user_fields(["username"])
+            .check(ExpansionCheck::Strict)
+            .build()
+            .unwrap();
+
+        assert!(fields.warnings().is_empty());
+        assert_eq!(
+            fields.to_query()[0],
+            (
+                "expansions".to_string(),
+                "referenced_tweets.id,referenced_tweets.id.author_id".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_expansion_without_fields() {
+        let builder = FieldsBuilder::new()
+            .expansions(["attachments.media_keys"])
+            .tweet_fields(["created_at"]);
+
+        let err = builder
+            .clone()
+            .check(ExpansionCheck::Strict)
+            .build()
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidRequest(ref msg) if msg.contains("media.fields")));
+
+        // Lenient builds still succeed but report the problem
+        let fields = builder.build().unwrap();
+        assert_eq!(fields.warnings().len(), 1);
+        assert!(fields.warnings()[0].contains("attachments.media_keys"));
+    }
+
+    #[test]
+    fn test_access_tier_filters_elevated_fields() {
+        let builder = FieldsBuilder::new()
+            .tweet_fields(["created_at", "non_public_metrics"])
+            .media_fields(["organic_metrics"])
+            .access_tier(AccessTier::Basic);
+
+        let fields = builder.clone().build().unwrap();
+        assert_eq!(
+            fields.to_query(),
+            vec![("tweet.fields".to_string(), "created_at".to_string())]
+        );
+        assert_eq!(fields.warnings().len(), 2);
+        assert!(
+            fields
+                .warnings()
+                .iter()
+                .any(|w| w.contains("tweet.fields=non_public_metrics"))
+        );
+
+        let err = builder
+            .clone()
+            .tier_check(TierCheck::Strict)
+            .build()
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidRequest(ref msg) if msg.contains("Pro access tier")));
+
+        let pro = builder.access_tier(AccessTier::Pro).build().unwrap();
+        assert!(pro.warnings().is_empty());
+        assert_eq!(pro.to_query().len(), 2);
+    }
+
+    #[test]
+    fn test_tier_check_independent_of_expansion_check() {
+        let builder = FieldsBuilder::new()
+            .expansions(["author_id"])
+            .tweet_fields(["non_public_metrics"])
+            .access_tier(AccessTier::Basic);
+
+        // Strict tier check, lenient expansion check: only the tier fails
+        let err = builder
+            .clone()
+            .tier_check(TierCheck::Strict)
+            .build()
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidRequest(ref msg)
+            if msg.contains("Pro access tier") && !msg.contains("author_id")));
+        let allowed = builder
+            .clone()
+            .tweet_fields(["created_at"])
+            .user_fields(["username"])
+            .access_tier(AccessTier::Pro)
+            .tier_check(TierCheck::Strict)
+            .build();
+        assert!(allowed.is_ok());
+
+        // Lenient tier check, strict expansion check: only the expansion fails
+        let err = builder
+            .clone()
+            .check(ExpansionCheck::Strict)
+            .build()
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidRequest(ref msg)
+            if msg.contains("author_id") && !msg.contains("access tier")));
+        let fields = builder
+            .user_fields(["username"])
+            .check(ExpansionCheck::Strict)
+            .build()
+            .unwrap();
+        assert_eq!(fields.warnings().len(), 1);
+        assert!(fields.warnings()[0].contains("Pro access tier"));
+    }
+}
@@ -1,13 +1,131 @@
 //! Request builders for X API endpoints
 
-use crate::models::common::{ReplySettings, TweetId};
+use crate::error::Result;
+use crate::models::common::{ReplySettings, TweetId, UserId};
 use serde::Serialize;
 
+/// Maximum tweet length, in characters, unless long-form posting is allowed
+pub const MAX_TWEET_CHARS: usize = 280;
+
+/// Maximum number of media attachments per tweet
+pub const MAX_MEDIA_IDS: usize = 4;
+
+/// Minimum number of poll options
+pub const MIN_POLL_OPTIONS: usize = 2;
+
+/// Maximum number of poll options
+pub const MAX_POLL_OPTIONS: usize = 4;
+
+/// Maximum length of a poll option, in characters
+pub const MAX_POLL_OPTION_CHARS: usize = 25;
+
+/// Minimum poll duration (5 minutes)
+pub const MIN_POLL_DURATION_MINUTES: u32 = 5;
+
+/// Maximum poll duration (7 days)
+pub const MAX_POLL_DURATION_MINUTES: u32 = 7 * 24 * 60;
+
+/// Content type of JSON request bodies
+pub const JSON_CONTENT_TYPE: &str = "application/json";
+
+/// Content type of form-encoded request bodies
+pub const FORM_CONTENT_TYPE: &str = "application/x-www-form-urlencoded";
+
+/// Request body together with its encoding
+///
+/// Most v2 endpoints take JSON, but some legacy and media endpoints require
+/// `application/x-www-form-urlencoded`. Form parameters are included in the
+/// OAuth 1.0a signature; JSON bodies are not.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum Body {
+    /// JSON body (`application/json`)
+    Json(serde_json::Value),
+
+    /// Form-encoded body (`application/x-www-form-urlencoded`)
+    Form(Vec<(String, String)>),
+}
+
+impl Body {
+    /// Create a JSON body from any serializable value
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `value` cannot be serialized to JSON.
+    pub fn json<T: Serialize + ?Sized>(value: &T) -> Result<Self> {
+        Ok(Body::Json(serde_json::to_value(value)?))
+    }
+
+    /// Create a form-encoded body from key-value pairs
+    pub fn form<I, K, V>(pairs: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        Body::Form(
+            pairs
+                .into_iter()
+                .map(|(k, v)| (k.into(), v.into()))
+                .collect(),
+        )
+    }
+
+    /// The `Content-Type` header value for this body
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Body::Json(_) => JSON_CONTENT_TYPE,
+            Body::Form(_) => FORM_CONTENT_TYPE,
+        }
+    }
+
+    /// Encode the body for sending
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the body cannot be encoded.
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        match self {
+            Body::Json(value) => Ok(serde_json::to_vec(value)?),
+            Body::Form(pairs) => serde_urlencoded::to_string(pairs)
+                .map(String::into_bytes)
+                .map_err(|e| {
+                    crate::error::Error::InvalidRequest(format!("Invalid form body: {}", e))
+                }),
+        }
+    }
+}
+
 /// Reply settings for a tweet (nested structure per X API v2 spec)
 #[derive(Debug, Clone, Serialize)]
 pub struct Reply {
     /// ID of the tweet being replied to
     pub in_reply_to_tweet_id: TweetId,
+
+    /// User IDs to exclude from the reply's auto-populated mentions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclude_reply_user_ids: Option<Vec<UserId>>,
+}
+
+/// Poll attached to a tweet
+#[derive(Debug, Clone, Serialize)]
+pub struct Poll {
+    /// Poll options (2 to 4, each at most 25 characters)
+    pub options: Vec<String>,
+
+    /// How long the poll stays open, in minutes (5 to 10080)
+    pub duration_minutes: u32,
+}
+
+/// Media attached to a tweet (nested structure per X API v2 spec)
+#[derive(Debug, Clone, Serialize)]
+pub struct Media {
+    /// IDs of uploaded media (at most 4)
+    pub media_ids: Vec<String>,
+
+    /// Users tagged in the attached images
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tagged_user_ids: Option<Vec<UserId>>,
 }
 
 /// Request to create a new tweet
@@ -23,7 +141,13 @@ pub struct TweetRequest {
     pub reply: Option<Reply>,
 
     /// Who can reply to this tweet
-    #[serde(skip_serializing_if = "Option::is_none")]
+    ///
+    /// Serialized with the API's create-tweet spelling; `Everyone` (the
+    /// default) is sent by omitting the field.
+    #[serde(
+        skip_serializing_if = "is_default_reply_settings",
+        serialize_with = "serialize_reply_settings"
+    )]
     pub reply_settings: Option<ReplySettings>,
 
     /// Direct message deep link
@@ -34,13 +158,25 @@ pub struct TweetRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub for_super_followers_only: Option<bool>,
 
-    /// Media attachment IDs
+    /// Media attachments
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub media_ids: Option<Vec<String>>,
+    pub media: Option<Media>,
+
+    /// Poll attached to the tweet
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub poll: Option<Poll>,
 
     /// ID of tweet being quoted
     #[serde(skip_serializing_if = "Option::is_none")]
     pub quote_tweet_id: Option<TweetId>,
+
+    /// Whether this is a promoted-only tweet, not shown on the author's timeline
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nullcast: Option<bool>,
+
+    /// URI of the card to attach (e.g. `card://1234567890`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub card_uri: Option<String>,
 }
 
 impl TweetRequest {
@@ -52,8 +188,11 @@ impl TweetRequest {
             reply_settings: None,
             direct_message_deep_link: None,
             for_super_followers_only: None,
-            media_ids: None,
+            media: None,
+            poll: None,
             quote_tweet_id: None,
+            nullcast: None,
+            card_uri: None,
         }
     }
 
@@ -68,11 +207,17 @@ impl TweetRequest {
 pub struct TweetRequestBuilder {
     text: Option<String>,
     reply_to_tweet_id: Option<TweetId>,
+    exclude_reply_user_ids: Option<Vec<UserId>>,
     reply_settings: Option<ReplySettings>,
     direct_message_deep_link: Option<String>,
     for_super_followers_only: Option<bool>,
     media_ids: Option<Vec<String>>,
+    tagged_user_ids: Option<Vec<UserId>>,
+    poll: Option<Poll>,
     quote_tweet_id: Option<TweetId>,
+    nullcast: Option<bool>,
+    card_uri: Option<String>,
+    allow_long_form: bool,
 }
 
 impl TweetRequestBuilder {
@@ -94,6 +239,18 @@ impl TweetRequestBuilder {
         self
     }
 
+    /// Exclude users from the reply's auto-populated mentions
+    ///
+    /// Only valid together with [`reply_to`](Self::reply_to).
+    pub fn exclude_reply_users<I, S>(mut self, user_ids: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<UserId>,
+    {
+        self.exclude_reply_user_ids = Some(user_ids.into_iter().map(Into::into).collect());
+        self
+    }
+
     /// Quote another tweet
     pub fn quote(mut self, tweet_id: impl Into<TweetId>) -> Self {
         self.quote_tweet_id = Some(tweet_id.into());
@@ -101,11 +258,42 @@ impl TweetRequestBuilder {
     }
 
     /// Add media attachments
+    ///
+    /// Sent as `{"media": {"media_ids": [...]}}`, the nested form the
+    /// create-tweet endpoint expects.
     pub fn media(mut self, media_ids: Vec<String>) -> Self {
         self.media_ids = Some(media_ids);
         self
     }
 
+    /// Tag users in the attached images
+    ///
+    /// Requires [`media`](Self::media).
+    pub fn tagged_users<I, S>(mut self, user_ids: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<UserId>,
+    {
+        self.tagged_user_ids = Some(user_ids.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Attach a poll
+    ///
+    /// A tweet can carry either media or a poll, and a poll cannot be
+    /// combined with a quote.
+    pub fn poll<I, S>(mut self, options: I, duration_minutes: u32) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.poll = Some(Poll {
+            options: options.into_iter().map(Into::into).collect(),
+            duration_minutes,
+        });
+        self
+    }
+
     /// Mark as super followers only
     pub fn super_followers_only(mut self) -> Self {
         self.for_super_followers_only = Some(true);
@@ -118,17 +306,49 @@ impl TweetRequestBuilder {
         self
     }
 
+    /// Post as a promoted-only (nullcast) tweet
+    ///
+    /// For advertisers: the tweet is only shown as an ad, not on the
+    /// author's timeline. Cannot be combined with
+    /// [`super_followers_only`](Self::super_followers_only).
+    pub fn nullcast(mut self) -> Self {
+        self.nullcast = Some(true);
+        self
+    }
+
+    /// Attach a card by URI (e.g. `card://1234567890`)
+    pub fn card_uri(mut self, uri: impl Into<String>) -> Self {
+        self.card_uri = Some(uri.into());
+        self
+    }
+
+    /// Allow text longer than [`MAX_TWEET_CHARS`]
+    ///
+    /// For accounts eligible for long-form posts. The text must still be
+    /// non-empty; the actual limit is enforced by the server.
+    pub fn allow_long_form(mut self) -> Self {
+        self.allow_long_form = true;
+        self
+    }
+
     /// Build the tweet request
     ///
     /// # Errors
     ///
     /// Returns an error if:
     /// - Text is not set
-    /// - Text is empty or exceeds 280 characters
+    /// - Text is empty, or exceeds 280 characters without
+    ///   [`allow_long_form`](Self::allow_long_form)
+    /// - Reply exclusions are set without a tweet to reply to
+    /// - More than 4 media IDs are attached, or users are tagged without media
+    /// - A poll is combined with media or a quote
+    /// - A nullcast tweet is marked super followers only
+    /// - The poll has fewer than 2 or more than 4 options, an option longer
+    ///   than 25 characters, or a duration outside 5 to 10080 minutes
     pub fn build(self) -> crate::error::Result<TweetRequest> {
-        let text = self.text.ok_or_else(|| {
-            crate::error::Error::Config("Tweet text is required".to_string())
-        })?;
+        let text = self
+            .text
+            .ok_or_else(|| crate::error::Error::Config("Tweet text is required".to_string()))?;
 
         // Validate tweet text length
         let char_count = text.chars().count();
@@ -137,31 +357,159 @@ impl TweetRequestBuilder {
                 "Tweet text cannot be empty".to_string(),
             ));
         }
-        if char_count > 280 {
+        if char_count > MAX_TWEET_CHARS && !self.allow_long_form {
             return Err(crate::error::Error::InvalidRequest(format!(
-                "Tweet text too long: {} characters (max 280)",
-                char_count
+                "Tweet text too long: {} characters (max {})",
+                char_count, MAX_TWEET_CHARS
             )));
         }
 
+        if self.exclude_reply_user_ids.is_some() && self.reply_to_tweet_id.is_none() {
+            return Err(crate::error::Error::InvalidRequest(
+                "exclude_reply_user_ids requires a tweet to reply to".to_string(),
+            ));
+        }
+
+        if let Some(settings) = &self.reply_settings {
+            if !settings.is_settable() {
+                return Err(crate::error::Error::InvalidRequest(format!(
+                    "Reply settings {:?} cannot be set on a new tweet",
+                    settings
+                )));
+            }
+        }
+
+        if let Some(media_ids) = &self.media_ids {
+            if media_ids.len() > MAX_MEDIA_IDS {
+                return Err(crate::error::Error::InvalidRequest(format!(
+                    "Too many media attachments: {} (max {})",
+                    media_ids.len(),
+                    MAX_MEDIA_IDS
+                )));
+            }
+        }
+
+        if self.tagged_user_ids.is_some() && self.media_ids.is_none() {
+            return Err(crate::error::Error::InvalidRequest(
+                "Tagged users require media attachments".to_string(),
+            ));
+        }
+
+        if let Some(poll) = &self.poll {
+            if self.media_ids.is_some() {
+                return Err(crate::error::Error::InvalidRequest(
+                    "A tweet cannot have both media and a poll".to_string(),
+                ));
+            }
+            if self.quote_tweet_id.is_some() {
+                return Err(crate::error::Error::InvalidRequest(
+                    "A tweet cannot have both a quote and a poll".to_string(),
+                ));
+            }
+            validate_poll(poll)?;
+        }
+
+        if self.nullcast == Some(true) && self.for_super_followers_only == Some(true) {
+            return Err(crate::error::Error::InvalidRequest(
+                "A nullcast tweet cannot be for super followers only".to_string(),
+            ));
+        }
+
+        let exclude_reply_user_ids = self.exclude_reply_user_ids;
+
         Ok(TweetRequest {
             text,
             reply: self.reply_to_tweet_id.map(|id| Reply {
                 in_reply_to_tweet_id: id,
+                exclude_reply_user_ids,
             }),
             reply_settings: self.reply_settings,
             direct_message_deep_link: self.direct_message_deep_link,
             for_super_followers_only: self.for_super_followers_only,
-            media_ids: self.media_ids,
+            media: self.media_ids.map(|media_ids| Media {
+                media_ids,
+                tagged_user_ids: self.tagged_user_ids,
+            }),
+            poll: self.poll,
             quote_tweet_id: self.quote_tweet_id,
+            nullcast: self.nullcast,
+            card_uri: self.card_uri,
         })
     }
 }
 
+/// Whether `reply_settings` is left out of a create-tweet body
+fn is_default_reply_settings(settings: &Option<ReplySettings>) -> bool {
+    matches!(settings, None | Some(ReplySettings::Everyone))
+}
+
+/// Serialize `reply_settings` with the create-tweet spelling
+fn serialize_reply_settings<S>(
+    settings: &Option<ReplySettings>,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    match settings.as_ref().and_then(ReplySettings::request_value) {
+        Some(value) => serializer.serialize_str(value),
+        None => Err(serde::ser::Error::custom(format!(
+            "reply settings {:?} cannot be sent",
+            settings
+        ))),
+    }
+}
+
+/// Validate poll option count, option length, and duration
+fn validate_poll(poll: &Poll) -> crate::error::Result<()> {
+    let count = poll.options.len();
+    if !(MIN_POLL_OPTIONS..=MAX_POLL_OPTIONS).contains(&count) {
+        return Err(crate::error::Error::InvalidRequest(format!(
+            "Poll must have {} to {} options, got {}",
+            MIN_POLL_OPTIONS, MAX_POLL_OPTIONS, count
+        )));
+    }
+    if let Some(option) = poll
+        .options
+        .iter()
+        .find(|option| option.chars().count() > MAX_POLL_OPTION_CHARS)
+    {
+        return Err(crate::error::Error::InvalidRequest(format!(
+            "Poll option too long: '{}' (max {} characters)",
+            option, MAX_POLL_OPTION_CHARS
+        )));
+    }
+    if !(MIN_POLL_DURATION_MINUTES..=MAX_POLL_DURATION_MINUTES).contains(&poll.duration_minutes) {
+        return Err(crate::error::Error::InvalidRequest(format!(
+            "Poll duration must be {} to {} minutes, got {}",
+            MIN_POLL_DURATION_MINUTES, MAX_POLL_DURATION_MINUTES, poll.duration_minutes
+        )));
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_form_body_encoding_and_content_type() {
+        let body = Body::form([("status", "Hello Ladies + Gentlemen"), ("lang", "en")]);
+        assert_eq!(body.content_type(), "application/x-www-form-urlencoded");
+        assert_eq!(
+            String::from_utf8(body.to_bytes().unwrap()).unwrap(),
+            "status=Hello+Ladies+%2B+Gentlemen&lang=en"
+        );
+    }
+
+    #[test]
+    fn test_json_body_encoding_and_content_type() {
+        let body = Body::json(&TweetRequest::new("Hello")).unwrap();
+        assert_eq!(body.content_type(), "application/json");
+        let value: serde_json::Value = serde_json::from_slice(&body.to_bytes().unwrap()).unwrap();
+        assert_eq!(value, serde_json::json!({"text": "Hello"}));
+    }
+
     #[test]
     fn test_tweet_request_new() {
         let request = TweetRequest::new("Hello, world!");
@@ -191,6 +539,41 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_reply_settings_serialize_settable_only() {
+        let body = |settings| {
+            let request = TweetRequest::builder()
+                .text("Hi")
+                .reply_settings(settings)
+                .build()
+                .unwrap();
+            serde_json::to_value(request).unwrap()
+        };
+
+        assert_eq!(
+            body(ReplySettings::MentionedUsers)["reply_settings"],
+            "mentionedUsers"
+        );
+        assert_eq!(
+            body(ReplySettings::Subscribers)["reply_settings"],
+            "subscribers"
+        );
+        assert!(
+            body(ReplySettings::Everyone)
+                .get("reply_settings")
+                .is_none()
+        );
+
+        let result = TweetRequest::builder()
+            .text("Hi")
+            .reply_settings(ReplySettings::Unknown("verified".to_string()))
+            .build();
+        assert!(matches!(
+            result,
+            Err(crate::error::Error::InvalidRequest(_))
+        ));
+    }
+
     #[test]
     fn test_tweet_request_builder_with_reply() {
         let request = TweetRequest::builder()
@@ -235,6 +618,22 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_long_form_skips_length_limit_only() {
+        let long_text = "a".repeat(500);
+        assert!(TweetRequest::builder().text(&long_text).build().is_err());
+
+        let request = TweetRequest::builder()
+            .text(&long_text)
+            .allow_long_form()
+            .build()
+            .unwrap();
+        assert_eq!(request.text.chars().count(), 500);
+
+        let empty = TweetRequest::builder().text("").allow_long_form().build();
+        assert!(matches!(empty, Err(crate::error::Error::InvalidRequest(_))));
+    }
+
     #[test]
     fn test_tweet_request_reply_serialization() {
         let request = TweetRequest::builder()
@@ -249,4 +648,176 @@ mod tests {
         assert!(json.contains("in_reply_to_tweet_id"));
         assert!(json.contains("1234"));
     }
+
+    #[test]
+    fn test_tweet_request_reply_with_exclusions_and_settings_shape() {
+        let request = TweetRequest::builder()
+            .text("Thread reply")
+            .reply_to("1234")
+            .exclude_reply_users(["111", "222"])
+            .reply_settings(ReplySettings::Following)
+            .build()
+            .unwrap();
+
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "text": "Thread reply",
+                "reply": {
+                    "in_reply_to_tweet_id": "1234",
+                    "exclude_reply_user_ids": ["111", "222"]
+                },
+                "reply_settings": "following"
+            })
+        );
+    }
+
+    #[test]
+    fn test_tweet_request_exclusions_without_reply_rejected() {
+        let result = TweetRequest::builder()
+            .text("Not a reply")
+            .exclude_reply_users(["111"])
+            .build();
+
+        assert!(matches!(
+            result,
+            Err(crate::error::Error::InvalidRequest(_))
+        ));
+    }
+
+    fn media_ids(count: usize) -> Vec<String> {
+        (1..=count).map(|i| format!("10000{}", i)).collect()
+    }
+
+    #[test]
+    fn test_four_media_ids_accepted() {
+        let request = TweetRequest::builder()
+            .text("Four pictures")
+            .media(media_ids(4))
+            .build()
+            .unwrap();
+        assert_eq!(request.media.unwrap().media_ids.len(), 4);
+    }
+
+    #[test]
+    fn test_tagged_media_serialized_as_nested_object() {
+        let request = TweetRequest::builder()
+            .text("Team photo")
+            .media(media_ids(1))
+            .tagged_users(["2244994945", "783214"])
+            .build()
+            .unwrap();
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(
+            json["media"],
+            serde_json::json!({
+                "media_ids": ["100001"],
+                "tagged_user_ids": ["2244994945", "783214"]
+            })
+        );
+    }
+
+    #[test]
+    fn test_tagged_users_without_media_rejected() {
+        let result = TweetRequest::builder()
+            .text("Nobody to tag")
+            .tagged_users(["783214"])
+            .build();
+        assert!(matches!(
+            result,
+            Err(crate::error::Error::InvalidRequest(_))
+        ));
+    }
+
+    #[test]
+    fn test_more_than_four_media_ids_rejected() {
+        let result = TweetRequest::builder()
+            .text("Five pictures")
+            .media(media_ids(5))
+            .build();
+        assert!(matches!(
+            result,
+            Err(crate::error::Error::InvalidRequest(_))
+        ));
+    }
+
+    #[test]
+    fn test_nullcast_and_card_uri_serialized() {
+        let request = TweetRequest::builder()
+            .text("Now on sale")
+            .nullcast()
+            .card_uri("card://1234567890")
+            .build()
+            .unwrap();
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["nullcast"], true);
+        assert_eq!(json["card_uri"], "card://1234567890");
+
+        let json = serde_json::to_value(TweetRequest::new("Organic")).unwrap();
+        assert!(json.get("nullcast").is_none());
+        assert!(json.get("card_uri").is_none());
+    }
+
+    #[test]
+    fn test_nullcast_super_followers_only_rejected() {
+        let result = TweetRequest::builder()
+            .text("Exclusive offer")
+            .nullcast()
+            .super_followers_only()
+            .build();
+        assert!(matches!(
+            result,
+            Err(crate::error::Error::InvalidRequest(_))
+        ));
+    }
+
+    #[test]
+    fn test_media_and_poll_rejected() {
+        let result = TweetRequest::builder()
+            .text("Pick one")
+            .media(media_ids(1))
+            .poll(["yes", "no"], 60)
+            .build();
+        assert!(matches!(
+            result,
+            Err(crate::error::Error::InvalidRequest(_))
+        ));
+    }
+
+    #[test]
+    fn test_quote_and_poll_rejected() {
+        let result = TweetRequest::builder()
+            .text("Pick one")
+            .quote("1234567890")
+            .poll(["yes", "no"], 60)
+            .build();
+        assert!(matches!(
+            result,
+            Err(crate::error::Error::InvalidRequest(_))
+        ));
+    }
+
+    #[test]
+    fn test_poll_validation() {
+        let build = |options: &[&str], duration: u32| {
+            TweetRequest::builder()
+                .text("Poll")
+                .poll(options.iter().copied(), duration)
+                .build()
+        };
+
+        assert!(build(&["yes"], 60).is_err());
+        assert!(build(&["a", "b", "c", "d", "e"], 60).is_err());
+        assert!(build(&["yes", "this option is far too long to fit"], 60).is_err());
+        assert!(build(&["yes", "no"], 4).is_err());
+        assert!(build(&["yes", "no"], 10_081).is_err());
+
+        let request = build(&["yes", "no"], 1440).unwrap();
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(
+            json["poll"],
+            serde_json::json!({"options": ["yes", "no"], "duration_minutes": 1440})
+        );
+    }
 }
@@ -0,0 +1,256 @@
+//! Replay of recorded HTTP interactions
+//!
+//! [`CassetteHttpClient`] serves responses from a cassette, a JSON file of
+//! recorded request/response pairs, so examples and integration tests can
+//! run offline and deterministically:
+//!
+//! ```json
+//! {
+//!   "interactions": [
+//!     {
+//!       "request": {"method": "GET", "path": "/2/tweets/20"},
+//!       "response": {"status": 200, "body": {"data": {"id": "20", "text": "just setting up my twttr"}}}
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! Requests are matched by method and path; the query string, headers, and
+//! body are ignored. Requests without a recording fail instead of reaching
+//! the network.
+
+use crate::Client;
+use crate::builder::request::JSON_CONTENT_TYPE;
+use crate::client::{ClientBuilder, HttpClient};
+use crate::error::{Error, Result};
+use crate::util::lock_unpoisoned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+/// Status of recorded responses that do not set one
+const DEFAULT_STATUS: u16 = 200;
+
+/// Recorded request/response pairs
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Cassette {
+    /// Interactions in recording order
+    pub interactions: Vec<Interaction>,
+}
+
+/// One recorded request and the response it received
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Interaction {
+    /// The request, as matched on replay
+    pub request: RecordedRequest,
+
+    /// The response to replay
+    pub response: RecordedResponse,
+}
+
+/// Method and path of a recorded request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedRequest {
+    /// HTTP method (e.g. `GET`)
+    pub method: String,
+
+    /// URL path (e.g. `/2/tweets/20`)
+    pub path: String,
+}
+
+/// A recorded response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedResponse {
+    /// HTTP status (default: 200)
+    #[serde(default = "default_status")]
+    pub status: u16,
+
+    /// Response headers
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub headers: HashMap<String, String>,
+
+    /// Response body: a string is sent as-is, any other JSON value is sent
+    /// serialized (with a JSON content type unless one is recorded)
+    #[serde(default)]
+    pub body: serde_json::Value,
+}
+
+fn default_status() -> u16 {
+    DEFAULT_STATUS
+}
+
+impl RecordedResponse {
+    /// Build the `reqwest::Response` replaying this recording
+    fn to_response(&self) -> Result<reqwest::Response> {
+        let mut builder = http::Response::builder().status(self.status);
+        for (name, value) in &self.headers {
+            builder = builder.header(name.as_str(), value.as_str());
+        }
+        let has_content_type = self
+            .headers
+            .keys()
+            .any(|name| name.eq_ignore_ascii_case(reqwest::header::CONTENT_TYPE.as_str()));
+
+        let body = match &self.body {
+            serde_json::Value::Null => String::new(),
+            serde_json::Value::String(text) => text.clone(),
+            value => {
+                if !has_content_type {
+                    builder = builder.header(reqwest::header::CONTENT_TYPE, JSON_CONTENT_TYPE);
+                }
+                value.to_string()
+            }
+        };
+
+        let response = builder
+            .body(body)
+            .map_err(|e| Error::Config(format!("Invalid recorded response: {}", e)))?;
+        Ok(reqwest::Response::from(response))
+    }
+}
+
+/// HTTP client replaying a [`Cassette`]
+///
+/// When several interactions match a request, they are replayed in
+/// recording order and the last one keeps being served.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use x_api_client::cassette::CassetteHttpClient;
+/// use x_api_client::client::ClientBuilder;
+///
+/// let http = CassetteHttpClient::from_file("tests/cassettes/tweet_lookup.json")?;
+/// let client = ClientBuilder::with_http(http)
+///     .oauth1("ck", "cs", "at", "ats")
+///     .build()?;
+/// let tweet = client.get_tweet("20").await?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct CassetteHttpClient {
+    cassette: Arc<Cassette>,
+    /// Number of times each interaction was served, by index
+    served: Arc<Mutex<Vec<usize>>>,
+}
+
+impl CassetteHttpClient {
+    /// Replay `cassette`
+    pub fn new(cassette: Cassette) -> Self {
+        let served = vec![0; cassette.interactions.len()];
+        Self {
+            cassette: Arc::new(cassette),
+            served: Arc::new(Mutex::new(served)),
+        }
+    }
+
+    /// Replay a cassette from its JSON representation
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `json` is not a valid cassette.
+    pub fn from_json(json: &str) -> Result<Self> {
+        Ok(Self::new(serde_json::from_str(json)?))
+    }
+
+    /// Replay the cassette stored at `path`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not a valid cassette.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+
+    /// Pick the interaction to replay for `method` and `path`
+    fn replay(&self, method: &str, path: &str) -> Option<&Interaction> {
+        let matching: Vec<usize> = self
+            .cassette
+            .interactions
+            .iter()
+            .enumerate()
+            .filter(|(_, interaction)| {
+                interaction.request.method.eq_ignore_ascii_case(method)
+                    && interaction.request.path == path
+            })
+            .map(|(index, _)| index)
+            .collect();
+
+        let mut served = lock_unpoisoned(&self.served);
+        let index = matching
+            .iter()
+            .copied()
+            .find(|&index| served[index] == 0)
+            .or_else(|| matching.last().copied())?;
+        served[index] += 1;
+        Some(&self.cassette.interactions[index])
+    }
+}
+
+impl HttpClient for CassetteHttpClient {
+    async fn execute(&self, request: reqwest::Request) -> Result<reqwest::Response> {
+        let method = request.method().as_str();
+        let path = request.url().path();
+        let interaction = self.replay(method, path).ok_or_else(|| {
+            Error::Config(format!("No recorded interaction for {} {}", method, path))
+        })?;
+        interaction.response.to_response()
+    }
+}
+
+impl ClientBuilder<CassetteHttpClient> {
+    /// Build the Client
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no authentication provider or HTTP client is
+    /// configured.
+    pub fn build(self) -> Result<Client<CassetteHttpClient>> {
+        self.build_with_custom_http()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TWEET_LOOKUP: &str = r#"{
+        "interactions": [
+            {
+                "request": {"method": "GET", "path": "/2/tweets/20"},
+                "response": {
+                    "status": 200,
+                    "body": {"data": {
+                        "id": "20",
+                        "text": "just setting up my twttr",
+                        "edit_history_tweet_ids": ["20"]
+                    }}
+                }
+            }
+        ]
+    }"#;
+
+    #[tokio::test]
+    async fn test_serves_recorded_tweet_lookup() {
+        let path =
+            std::env::temp_dir().join(format!("x-api-client-cassette-{}.json", std::process::id()));
+        std::fs::write(&path, TWEET_LOOKUP).unwrap();
+        let http = CassetteHttpClient::from_file(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        let client = ClientBuilder::with_http(http.unwrap())
+            .oauth1("ck", "cs", "at", "ats")
+            .build()
+            .unwrap();
+
+        let tweet = client.get_tweet("20").await.unwrap();
+        assert_eq!(tweet.text, "just setting up my twttr");
+
+        let err = client.get_tweet("21").await.unwrap_err();
+        assert!(
+            matches!(&err, Error::Config(msg) if msg == "No recorded interaction for GET /2/tweets/21"),
+            "{:?}",
+            err
+        );
+    }
+}
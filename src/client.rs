@@ -3,11 +3,59 @@
 //! Provides the primary interface for interacting with the X API v2
 
 use crate::auth::AuthProvider;
-use crate::error::Result;
+use crate::builder::query::{IDS_PARAM, LookupOptions, MAX_LOOKUP_IDS};
+use crate::builder::request::Body;
+use crate::endpoints::catalog::EndpointInfo;
+use crate::endpoints::paths;
+use crate::endpoints::usage::{UsageGuard, UsageGuardState};
+use crate::error::{Error, Result, parse_api_error};
+use crate::models::common::ApiResponse;
 use crate::rate_limit::RateLimitConfig;
+use crate::rate_limit::tracker::{RateLimitState, RateLimitTracker, endpoint_key};
+use crate::retry::budget::{RetryBudget, RetryBudgetTracker};
+use crate::retry::classifier::{RetryDecision, classify};
 use crate::retry::policy::RetryPolicy;
+use crate::util::cache::{CacheConfig, TtlCache};
+use crate::util::clock::{Clock, SystemClock};
+use crate::util::metrics::MetricsRecorder;
+use crate::util::redact::{BodyRedactor, DefaultRedactor, describe_request};
+use crate::util::serde_helpers::from_json_str;
+use backoff::backoff::Backoff;
+use reqwest::Method;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::env::VarError;
 use std::future::Future;
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
+
+/// Default base URL of the X API
+pub const DEFAULT_BASE_URL: &str = "https://api.twitter.com";
+
+/// Default total request timeout of [`ReqwestClient`]
+const DEFAULT_REQUEST_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Request timeout for streaming connections, effectively disabling the
+/// client-wide total timeout (the deadline saturates to the far future)
+const STREAM_REQUEST_TIMEOUT: std::time::Duration = std::time::Duration::MAX;
+
+/// Content coding requested for raw streams read without decompression
+const GZIP_ENCODING: &str = "gzip";
+
+/// Environment variable with the OAuth 1.0a consumer key
+pub const CONSUMER_KEY_VAR: &str = "X_CONSUMER_KEY";
+
+/// Environment variable with the OAuth 1.0a consumer secret
+pub const CONSUMER_SECRET_VAR: &str = "X_CONSUMER_SECRET";
+
+/// Environment variable with the OAuth 1.0a access token
+pub const ACCESS_TOKEN_VAR: &str = "X_ACCESS_TOKEN";
+
+/// Environment variable with the OAuth 1.0a access token secret
+pub const ACCESS_TOKEN_SECRET_VAR: &str = "X_ACCESS_TOKEN_SECRET";
+
+/// Environment variable with an app-only bearer token
+pub const BEARER_TOKEN_VAR: &str = "X_BEARER_TOKEN";
 
 /// HTTP client trait abstraction for testability and flexibility
 ///
@@ -29,6 +77,25 @@ pub trait HttpClient: Send + Sync {
         &self,
         request: reqwest::Request,
     ) -> impl Future<Output = Result<reqwest::Response>> + Send;
+
+    /// Send an HTTP request, leaving the response body compressed
+    ///
+    /// Used by raw streams with decompression disabled (see
+    /// [`StreamOptionsBuilder::decompress`](crate::streaming::StreamOptionsBuilder::decompress)).
+    ///
+    /// # Errors
+    ///
+    /// The default implementation returns [`Error::Config`], since whether
+    /// [`execute`](Self::execute) decompresses bodies is up to the client.
+    fn execute_undecoded(
+        &self,
+        request: reqwest::Request,
+    ) -> impl Future<Output = Result<reqwest::Response>> + Send {
+        let _ = request;
+        std::future::ready(Err(Error::Config(
+            "This HTTP client cannot leave response bodies compressed".to_string(),
+        )))
+    }
 }
 
 /// Default HTTP client implementation using reqwest
@@ -38,28 +105,86 @@ pub trait HttpClient: Send + Sync {
 #[derive(Clone)]
 pub struct ReqwestClient {
     client: reqwest::Client,
+
+    /// Same configuration without automatic decompression, unless the
+    /// client was supplied by the caller
+    undecoded: Option<Arc<UndecodedClient>>,
+}
+
+/// A reqwest client without automatic decompression, built on first use
+///
+/// Only raw streams with decompression disabled need it, so other clients
+/// never pay for a second connection pool.
+#[derive(Debug)]
+struct UndecodedClient {
+    options: ReqwestClientBuilder,
+    client: OnceLock<reqwest::Client>,
+}
+
+impl UndecodedClient {
+    fn new(options: ReqwestClientBuilder) -> Self {
+        Self {
+            options,
+            client: OnceLock::new(),
+        }
+    }
+
+    /// The client, building it if this is the first use
+    fn get(&self) -> Result<&reqwest::Client> {
+        if let Some(client) = self.client.get() {
+            return Ok(client);
+        }
+        let client = self.options.configure().gzip(false).build()?;
+        Ok(self.client.get_or_init(|| client))
+    }
 }
 
 impl ReqwestClient {
     /// Create a new ReqwestClient with default configuration
     pub fn new() -> Result<Self> {
-        let client = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(30))
-            .build()?;
+        Self::with_timeout(DEFAULT_REQUEST_TIMEOUT)
+    }
 
-        Ok(Self { client })
+    /// Create a builder for connection-level options
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use x_api_client::client::ReqwestClient;
+    /// use std::time::Duration;
+    ///
+    /// let http = ReqwestClient::builder()
+    ///     .connect_timeout(Duration::from_secs(2))
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn builder() -> ReqwestClientBuilder {
+        ReqwestClientBuilder::default()
     }
 
     /// Create a new ReqwestClient with custom reqwest::Client
+    ///
+    /// The client is used as is for every request, so its timeouts, proxies,
+    /// and default headers all apply. To layer `reqwest-middleware`
+    /// middleware, enable the `reqwest-middleware` feature and use
+    /// [`MiddlewareClient`](crate::middleware::MiddlewareClient) instead.
+    ///
+    /// Raw streams with decompression disabled are not available with a
+    /// supplied client, since its configuration cannot be copied.
     pub fn with_client(client: reqwest::Client) -> Self {
-        Self { client }
+        Self {
+            client,
+            undecoded: None,
+        }
     }
 
     /// Create a new ReqwestClient with custom timeout
     pub fn with_timeout(timeout: std::time::Duration) -> Result<Self> {
-        let client = reqwest::Client::builder().timeout(timeout).build()?;
-
-        Ok(Self { client })
+        ReqwestClientBuilder {
+            timeout,
+            ..ReqwestClientBuilder::default()
+        }
+        .into_client()
     }
 
     /// Get a reference to the underlying reqwest::Client
@@ -68,6 +193,112 @@ impl ReqwestClient {
     }
 }
 
+/// Builder for a [`ReqwestClient`] with connection-level options
+#[derive(Debug)]
+pub struct ReqwestClientBuilder {
+    timeout: std::time::Duration,
+    connect_timeout: Option<std::time::Duration>,
+    local_address: Option<std::net::IpAddr>,
+    resolve: Vec<(String, std::net::SocketAddr)>,
+}
+
+impl Default for ReqwestClientBuilder {
+    fn default() -> Self {
+        Self {
+            timeout: DEFAULT_REQUEST_TIMEOUT,
+            connect_timeout: None,
+            local_address: None,
+            resolve: Vec::new(),
+        }
+    }
+}
+
+impl ReqwestClientBuilder {
+    /// Set the total request timeout
+    ///
+    /// Default: 30 seconds
+    pub fn timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set the timeout for establishing a connection, separate from the
+    /// total request timeout
+    ///
+    /// Default: none (bounded only by the total timeout)
+    pub fn connect_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Bind outgoing connections to a local address (for multi-homed hosts)
+    pub fn local_address(mut self, address: std::net::IpAddr) -> Self {
+        self.local_address = Some(address);
+        self
+    }
+
+    /// Resolve `domain` to `address` instead of using DNS
+    ///
+    /// The port of `address` is ignored; the port of the request URL is used.
+    pub fn resolve(mut self, domain: impl Into<String>, address: std::net::SocketAddr) -> Self {
+        self.resolve.push((domain.into(), address));
+        self
+    }
+
+    /// Build the HTTP client
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - timeout or connect_timeout is zero
+    /// - connect_timeout exceeds timeout (it would never apply)
+    /// - the underlying reqwest client fails to build
+    pub fn build(self) -> Result<ReqwestClient> {
+        if self.timeout.is_zero() {
+            return Err(Error::Config(
+                "Request timeout must be non-zero".to_string(),
+            ));
+        }
+        if let Some(connect_timeout) = self.connect_timeout {
+            if connect_timeout.is_zero() {
+                return Err(Error::Config(
+                    "Connect timeout must be non-zero".to_string(),
+                ));
+            }
+            if connect_timeout > self.timeout {
+                return Err(Error::Config(format!(
+                    "Connect timeout ({:?}) exceeds request timeout ({:?})",
+                    connect_timeout, self.timeout
+                )));
+            }
+        }
+
+        self.into_client()
+    }
+
+    /// Build the client without validating the options
+    fn into_client(self) -> Result<ReqwestClient> {
+        Ok(ReqwestClient {
+            client: self.configure().build()?,
+            undecoded: Some(Arc::new(UndecodedClient::new(self))),
+        })
+    }
+
+    /// A reqwest builder with the configured options
+    fn configure(&self) -> reqwest::ClientBuilder {
+        let mut builder = reqwest::Client::builder()
+            .timeout(self.timeout)
+            .local_address(self.local_address);
+        if let Some(connect_timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(connect_timeout);
+        }
+        for (domain, address) in &self.resolve {
+            builder = builder.resolve(domain, *address);
+        }
+        builder
+    }
+}
+
 // Note: No Default implementation to avoid potential panics in library code.
 // Use ReqwestClient::new() instead, which returns Result for proper error handling.
 
@@ -76,6 +307,17 @@ impl HttpClient for ReqwestClient {
         let response = self.client.execute(request).await?;
         Ok(response)
     }
+
+    async fn execute_undecoded(&self, request: reqwest::Request) -> Result<reqwest::Response> {
+        let client = self.undecoded.as_ref().ok_or_else(|| {
+            Error::Config(
+                "Disabling decompression requires a ReqwestClient built by this crate, \
+                 not ReqwestClient::with_client"
+                    .to_string(),
+            )
+        })?;
+        Ok(client.get()?.execute(request).await?)
+    }
 }
 
 /// The main client for interacting with the X API v2
@@ -96,11 +338,48 @@ pub struct Client<H: HttpClient + Clone = ReqwestClient> {
     /// Rate limiting configuration
     rate_limit_config: RateLimitConfig,
 
+    /// Per-endpoint rate limit state, shared between clones
+    rate_limiter: Arc<RateLimitTracker>,
+
     /// Retry policy for failed requests
     retry_policy: RetryPolicy,
 
+    /// Client-wide retry budget, shared between clones (default: unlimited)
+    retry_budget: Option<Arc<RetryBudgetTracker>>,
+
     /// Base URL for the X API (default: <https://api.twitter.com>)
     base_url: String,
+
+    /// Time source for rate limit waits and retry backoff
+    clock: Arc<dyn Clock>,
+
+    /// Redacts headers and bodies in request logs
+    redactor: Arc<dyn BodyRedactor>,
+
+    /// Whether unknown fields in response models are rejected
+    strict_deserialization: bool,
+
+    /// Deadline for requests, set with [`Client::with_deadline`]
+    deadline: Option<Deadline>,
+
+    /// User IDs by lowercase username, shared between clones (default: none)
+    username_cache: Option<Arc<TtlCache<crate::models::common::UserId>>>,
+
+    /// Receives the latency and status of each attempt (default: none)
+    metrics: Option<Arc<dyn MetricsRecorder>>,
+
+    /// Refuses writes near the monthly cap, set with [`Client::usage_guard`]
+    usage_guard: Option<Arc<UsageGuardState>>,
+}
+
+/// Point in time requests must complete by
+#[derive(Debug, Clone, Copy)]
+struct Deadline {
+    /// When the deadline expires
+    at: chrono::DateTime<chrono::Utc>,
+
+    /// Time granted when the deadline was set, reported by [`Error::Timeout`]
+    budget: std::time::Duration,
 }
 
 impl Client<ReqwestClient> {
@@ -142,11 +421,98 @@ impl Client<ReqwestClient> {
             http: ReqwestClient::new()?,
             auth: Arc::new(auth),
             rate_limit_config: RateLimitConfig::default(),
+            rate_limiter: Arc::new(RateLimitTracker::new()),
             retry_policy: RetryPolicy::default(),
-            base_url: "https://api.twitter.com".to_string(),
+            retry_budget: None,
+            base_url: DEFAULT_BASE_URL.to_string(),
+            clock: Arc::new(SystemClock),
+            redactor: Arc::new(DefaultRedactor::new()),
+            strict_deserialization: false,
+            deadline: None,
+            username_cache: None,
+            metrics: None,
+            usage_guard: None,
         })
     }
 
+    /// Create a Client from credentials in environment variables
+    ///
+    /// Uses OAuth 1.0a when [`CONSUMER_KEY_VAR`], [`CONSUMER_SECRET_VAR`],
+    /// [`ACCESS_TOKEN_VAR`] and [`ACCESS_TOKEN_SECRET_VAR`] are all set, and
+    /// otherwise falls back to app-only authentication with
+    /// [`BEARER_TOKEN_VAR`]. Empty variables count as unset.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Config`] naming the missing variables if neither set
+    /// of credentials is complete, or if the OAuth 1.0a variables are only
+    /// partly set, and naming the variable if one is not valid Unicode.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use x_api_client::Client;
+    ///
+    /// let client = Client::from_env()?;
+    /// ```
+    pub fn from_env() -> Result<Self> {
+        Self::from_lookup(|name| std::env::var(name))
+    }
+
+    /// Create a Client from credentials found by `lookup`, as in
+    /// [`from_env`](Self::from_env)
+    pub(crate) fn from_lookup(
+        lookup: impl Fn(&str) -> std::result::Result<String, VarError>,
+    ) -> Result<Self> {
+        const OAUTH1_VARS: [&str; 4] = [
+            CONSUMER_KEY_VAR,
+            CONSUMER_SECRET_VAR,
+            ACCESS_TOKEN_VAR,
+            ACCESS_TOKEN_SECRET_VAR,
+        ];
+        let lookup = |name: &str| match lookup(name) {
+            Ok(value) => Ok(Some(value).filter(|value| !value.is_empty())),
+            Err(VarError::NotPresent) => Ok(None),
+            Err(VarError::NotUnicode(_)) => Err(Error::Config(format!(
+                "Environment variable {} is not valid Unicode",
+                name
+            ))),
+        };
+
+        let mut oauth1: [Option<String>; 4] = Default::default();
+        for (value, name) in oauth1.iter_mut().zip(OAUTH1_VARS) {
+            *value = lookup(name)?;
+        }
+        let missing: Vec<&str> = OAUTH1_VARS
+            .iter()
+            .zip(&oauth1)
+            .filter(|(_, value)| value.is_none())
+            .map(|(name, _)| *name)
+            .collect();
+
+        if let [Some(ck), Some(cs), Some(at), Some(ats)] = oauth1 {
+            return Self::new(ck, cs, at, ats);
+        }
+        if missing.len() < OAUTH1_VARS.len() {
+            return Err(Error::Config(format!(
+                "Incomplete OAuth 1.0a credentials, missing environment variables: {}",
+                missing.join(", ")
+            )));
+        }
+        match lookup(BEARER_TOKEN_VAR)? {
+            Some(token) => Self::builder()
+                .auth(Arc::new(crate::auth::bearer::BearerTokenProvider::new(
+                    token,
+                )))
+                .build(),
+            None => Err(Error::Config(format!(
+                "No credentials found, missing environment variables: {} (or {})",
+                missing.join(", "),
+                BEARER_TOKEN_VAR
+            ))),
+        }
+    }
+
     /// Create a new ClientBuilder for advanced configuration
     ///
     /// # Example
@@ -197,256 +563,854 @@ impl<H: HttpClient + Clone> Client<H> {
         &self.retry_policy
     }
 
-    /// Helper method to handle API responses and extract data
-    ///
-    /// Handles error status codes and parses the response into the expected type
-    async fn handle_response<T>(&self, response: reqwest::Response, resource_id: Option<&str>) -> Result<T>
-    where
-        T: serde::de::DeserializeOwned,
-    {
-        let status = response.status();
-
-        // Handle HTTP errors
-        if !status.is_success() {
-            // Special handling for 404
-            if status.as_u16() == 404 {
-                let msg = match resource_id {
-                    Some(id) => format!("Resource {} not found", id),
-                    None => "Resource not found".to_string(),
-                };
-                return Err(crate::error::Error::NotFound(msg));
-            }
+    /// Get the time source
+    pub(crate) fn clock(&self) -> &dyn Clock {
+        &*self.clock
+    }
 
-            // Try to parse X API error response
-            let error_text = response.text().await.unwrap_or_default();
+    /// Whether unknown fields in response models are rejected
+    pub(crate) fn strict_deserialization(&self) -> bool {
+        self.strict_deserialization
+    }
 
-            // Attempt to parse structured error from X API
-            if let Ok(api_err) = serde_json::from_str::<crate::models::common::ApiError>(&error_text) {
-                return Err(crate::error::Error::Api(Box::new(
-                    crate::error::ApiErrorDetail::new(
-                        api_err.code.unwrap_or_else(|| status.as_str().to_string()),
-                        api_err.message.clone(),
-                    )
-                    .with_status(status.as_u16()),
-                )));
-            }
+    /// Get the username cache, if configured
+    pub(crate) fn username_cache(&self) -> Option<&TtlCache<crate::models::common::UserId>> {
+        self.username_cache.as_deref()
+    }
 
-            // Fallback to simple error if parsing fails
-            return Err(crate::error::Error::Api(Box::new(
-                crate::error::ApiErrorDetail::new(
-                    status.as_str(),
-                    error_text,
-                )
-                .with_status(status.as_u16()),
-            )));
+    /// Scope a clone of this client to a deadline
+    ///
+    /// Requests sent through the returned client, including their retries
+    /// and rate limit waits, fail with [`Error::Timeout`] instead of running
+    /// past `deadline`. A wait that would end after the deadline fails right
+    /// away rather than sleeping first. The deadline is measured on the
+    /// client's [`Clock`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// // Inside a handler with a 2 second budget
+    /// let scoped = client.with_deadline(chrono::Utc::now() + chrono::Duration::seconds(2));
+    /// let tweet = scoped.get_tweet("20").await?;
+    /// ```
+    pub fn with_deadline(&self, deadline: chrono::DateTime<chrono::Utc>) -> Self {
+        let budget = (deadline - self.clock.now()).to_std().unwrap_or_default();
+        Self {
+            deadline: Some(Deadline {
+                at: deadline,
+                budget,
+            }),
+            ..self.clone()
         }
-
-        // Parse successful response
-        let response_text = response.text().await?;
-        let api_response: crate::models::common::ApiResponse<T> =
-            serde_json::from_str(&response_text)?;
-
-        // Extract data or return error if missing
-        api_response
-            .data
-            .ok_or_else(|| crate::error::Error::InvalidResponse("No data in API response".to_string()))
     }
 
-    // Tweet Endpoints
-
-    /// Post a new tweet
-    ///
-    /// # Arguments
+    /// Clone this client with a different auth provider
     ///
-    /// * `request` - The tweet content and options
+    /// The clone shares the HTTP connection pool, retry policy and budget,
+    /// and rate limit tracker with this client, so a multi-tenant service
+    /// can derive one client per user without rebuilding them. Limits of
+    /// user-context endpoints are counted per user by X; use
+    /// [`with_auth_isolated`](Self::with_auth_isolated) to track each
+    /// user's limits separately.
     ///
     /// # Example
     ///
     /// ```rust,ignore
-    /// use x_api_client::builder::request::TweetRequest;
+    /// use std::sync::Arc;
+    /// use x_api_client::auth::oauth1::OAuth1Provider;
     ///
-    /// let tweet = client.post_tweet(TweetRequest::new("Hello, world!")).await?;
-    /// println!("Posted tweet with ID: {}", tweet.id);
+    /// let alice = client.with_auth(Arc::new(OAuth1Provider::new("ck", "cs", "at", "ats")));
+    /// alice.post_tweet(TweetRequest::new("Hello from Alice")).await?;
     /// ```
-    pub async fn post_tweet(
-        &self,
-        request: crate::builder::request::TweetRequest,
-    ) -> Result<crate::models::tweet::Tweet> {
-        // Build the HTTP request
-        let url = format!("{}/2/tweets", self.base_url);
-        let mut http_request = reqwest::Request::new(
-            reqwest::Method::POST,
-            url.parse().map_err(|e| {
-                crate::error::Error::Config(format!("Invalid URL: {}", e))
-            })?,
-        );
-
-        // Set the JSON body
-        let body = serde_json::to_vec(&request)?;
-        *http_request.body_mut() = Some(body.into());
-        http_request.headers_mut().insert(
-            reqwest::header::CONTENT_TYPE,
-            reqwest::header::HeaderValue::from_static("application/json"),
-        );
-
-        // Authenticate the request
-        let http_request = self.auth.authenticate(http_request).await?;
+    pub fn with_auth(&self, auth: Arc<dyn AuthProvider>) -> Self {
+        Self {
+            auth,
+            ..self.clone()
+        }
+    }
 
-        // Execute the request and handle response
-        let response = self.http.execute(http_request).await?;
-        self.handle_response(response, None).await
+    /// Clone this client with a different auth provider and its own rate
+    /// limit tracker
+    ///
+    /// Like [`with_auth`](Self::with_auth), but rate limit state starts
+    /// empty and is not shared with this client.
+    pub fn with_auth_isolated(&self, auth: Arc<dyn AuthProvider>) -> Self {
+        Self {
+            rate_limiter: Arc::new(RateLimitTracker::new()),
+            ..self.with_auth(auth)
+        }
     }
 
-    /// Get a tweet by ID
+    /// Scope a clone of this client to fail fast on rate limits
     ///
-    /// # Arguments
+    /// Requests sent through the returned client fail with
+    /// [`Error::RateLimitExceeded`] instead of waiting for the window to
+    /// reset, even if the client's [`RateLimitConfig`] enables auto-wait.
+    /// Rate limit state stays shared with this client.
     ///
-    /// * `id` - The tweet ID
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// // A latency-sensitive handler: better no answer than a late one
+    /// match client.fail_fast_on_rate_limit().get_tweet("20").await {
+    ///     Err(Error::RateLimitExceeded { reset_at, .. }) => serve_stale(reset_at),
+    ///     other => other?,
+    /// }
+    /// ```
+    pub fn fail_fast_on_rate_limit(&self) -> Self {
+        Self {
+            rate_limit_config: self.rate_limit_config.without_auto_wait(),
+            ..self.clone()
+        }
+    }
+
+    /// Clone this client with writes guarded against the monthly tweet cap
+    ///
+    /// Requests other than `GET` sent through the returned client, and its
+    /// clones, fail with [`Error::UsageCapReached`] once the project's usage
+    /// reaches the guard's threshold; reads are unaffected. Usage is fetched
+    /// with `usage_auth`, which must support app-only authentication, on the
+    /// first write and whenever the last reading is older than the guard's
+    /// check interval.
     ///
     /// # Example
     ///
     /// ```rust,ignore
-    /// let tweet = client.get_tweet("1234567890").await?;
-    /// println!("Tweet: {}", tweet.text);
+    /// use std::sync::Arc;
+    /// use x_api_client::auth::bearer::BearerTokenProvider;
+    /// use x_api_client::endpoints::usage::UsageGuard;
+    ///
+    /// let guarded = client.usage_guard(
+    ///     UsageGuard::new(),
+    ///     Arc::new(BearerTokenProvider::new(bearer_token)),
+    /// );
+    /// match guarded.post_tweet(TweetRequest::new("Hello")).await {
+    ///     Err(Error::UsageCapReached { usage, cap }) => pause_posting(usage, cap),
+    ///     other => other?,
+    /// }
     /// ```
-    pub async fn get_tweet(
-        &self,
-        id: impl Into<crate::models::common::TweetId>,
-    ) -> Result<crate::models::tweet::Tweet> {
-        let id = id.into();
-        let url = format!("{}/2/tweets/{}", self.base_url, id);
+    pub fn usage_guard(&self, guard: UsageGuard, usage_auth: Arc<dyn AuthProvider>) -> Self {
+        Self {
+            usage_guard: Some(Arc::new(UsageGuardState::new(guard, usage_auth))),
+            ..self.clone()
+        }
+    }
 
-        let http_request = reqwest::Request::new(
-            reqwest::Method::GET,
-            url.parse().map_err(|e| {
-                crate::error::Error::Config(format!("Invalid URL: {}", e))
-            })?,
-        );
+    /// Time left before the deadline, `None` without a deadline
+    fn time_to_deadline(&self) -> Option<std::time::Duration> {
+        self.deadline.map(|deadline| {
+            (deadline.at - self.clock.now())
+                .to_std()
+                .unwrap_or_default()
+        })
+    }
 
-        // Authenticate the request
-        let http_request = self.auth.authenticate(http_request).await?;
+    /// Whether the deadline, if any, has passed
+    fn deadline_passed(&self) -> bool {
+        self.deadline
+            .is_some_and(|deadline| self.clock.now() >= deadline.at)
+    }
 
-        // Execute the request and handle response
-        let response = self.http.execute(http_request).await?;
-        self.handle_response(response, Some(&id)).await
+    /// Fail with [`Error::Timeout`] if waiting `wait` would overrun the deadline
+    fn ensure_wait_fits_deadline(&self, wait: std::time::Duration) -> Result<()> {
+        match (self.deadline, self.time_to_deadline()) {
+            (Some(deadline), Some(left)) if wait > left => Err(Error::Timeout(deadline.budget)),
+            _ => Ok(()),
+        }
     }
 
-    /// Delete a tweet by ID
-    ///
-    /// # Arguments
+    /// List all endpoints implemented by this client
     ///
-    /// * `id` - The tweet ID to delete
+    /// Each entry describes the path template, HTTP method, and whether the
+    /// endpoint requires app-only or user-context authentication.
     ///
     /// # Example
     ///
     /// ```rust,ignore
-    /// let response = client.delete_tweet("1234567890").await?;
-    /// assert!(response.deleted);
+    /// for endpoint in client.capabilities() {
+    ///     println!("{} {} ({:?})", endpoint.method, endpoint.path, endpoint.auth);
+    /// }
     /// ```
-    pub async fn delete_tweet(
-        &self,
-        id: impl Into<crate::models::common::TweetId>,
-    ) -> Result<crate::models::tweet::DeleteResponse> {
-        let id = id.into();
-        let url = format!("{}/2/tweets/{}", self.base_url, id);
-
-        let http_request = reqwest::Request::new(
-            reqwest::Method::DELETE,
-            url.parse().map_err(|e| {
-                crate::error::Error::Config(format!("Invalid URL: {}", e))
-            })?,
-        );
-
-        // Authenticate the request
-        let http_request = self.auth.authenticate(http_request).await?;
-
-        // Execute the request and handle response
-        let response = self.http.execute(http_request).await?;
-        self.handle_response(response, Some(&id)).await
+    pub fn capabilities(&self) -> Vec<EndpointInfo> {
+        crate::endpoints::catalog::implemented_endpoints()
     }
 
-    // User Endpoints
+    /// Check whether the configured auth provider can authenticate an endpoint
+    ///
+    /// Useful to verify up front that the provider covers the endpoints an
+    /// application intends to call.
+    pub fn supports_endpoint(&self, endpoint: &EndpointInfo) -> bool {
+        self.auth.supports_endpoint(&endpoint.path)
+    }
 
-    /// Get a user by their user ID
+    /// Check that the API is reachable, returning the round-trip latency
     ///
-    /// # Arguments
+    /// Sends an unauthenticated `HEAD` request for the public OpenAPI
+    /// document, so it works with any auth provider and does not count
+    /// against endpoint rate limits. Not retried.
     ///
-    /// * `id` - The user ID
+    /// # Errors
+    ///
+    /// Returns [`Error::Network`] if the API cannot be reached or answers
+    /// with a non-success status.
     ///
     /// # Example
     ///
     /// ```rust,ignore
-    /// let user = client.get_user("1234567890").await?;
-    /// println!("User: @{}", user.username);
+    /// let latency = client.ping().await?;
+    /// println!("API reachable in {:?}", latency);
     /// ```
-    pub async fn get_user(
-        &self,
-        id: impl Into<crate::models::common::UserId>,
-    ) -> Result<crate::models::user::User> {
-        let id = id.into();
-        let url = format!("{}/2/users/{}", self.base_url, id);
-
-        let http_request = reqwest::Request::new(
-            reqwest::Method::GET,
-            url.parse().map_err(|e| {
-                crate::error::Error::Config(format!("Invalid URL: {}", e))
-            })?,
-        );
-
-        // Authenticate the request
-        let http_request = self.auth.authenticate(http_request).await?;
-
-        // Execute the request and handle response
-        let response = self.http.execute(http_request).await?;
-        self.handle_response(response, Some(&id)).await
+    pub async fn ping(&self) -> Result<std::time::Duration> {
+        let request = self.build_request(&Method::HEAD, paths::OPENAPI_SPEC, &[], None)?;
+        let started = std::time::Instant::now();
+        self.http.execute(request).await?.error_for_status()?;
+        Ok(started.elapsed())
     }
 
-    /// Get a user by their username
+    /// Call an arbitrary endpoint with a GET request
+    ///
+    /// Escape hatch for endpoints the client does not wrap yet. The request
+    /// goes through the same pipeline as the typed endpoint methods
+    /// (authentication routing, rate limiting, and retries), and the whole
+    /// response body is deserialized into `T`.
     ///
     /// # Arguments
     ///
-    /// * `username` - The username (without @ symbol)
+    /// * `path` - Endpoint path without the base URL (e.g. `/2/users/123/muting`)
+    /// * `query` - Query parameters
     ///
     /// # Example
     ///
     /// ```rust,ignore
-    /// let user = client.get_user_by_username("jack").await?;
-    /// println!("User ID: {}", user.id);
+    /// use x_api_client::models::common::ApiResponse;
+    /// use x_api_client::models::user::User;
+    ///
+    /// let muted: ApiResponse<Vec<User>> = client
+    ///     .raw_get("/2/users/2244994945/muting", &[("max_results", "100")])
+    ///     .await?;
     /// ```
-    pub async fn get_user_by_username(
-        &self,
-        username: impl Into<String>,
-    ) -> Result<crate::models::user::User> {
-        let username = username.into();
-        let url = format!("{}/2/users/by/username/{}", self.base_url, username);
-
-        let http_request = reqwest::Request::new(
-            reqwest::Method::GET,
-            url.parse().map_err(|e| {
-                crate::error::Error::Config(format!("Invalid URL: {}", e))
-            })?,
-        );
-
-        // Authenticate the request
-        let http_request = self.auth.authenticate(http_request).await?;
-
-        // Execute the request and handle response
-        let response = self.http.execute(http_request).await?;
-        self.handle_response(response, Some(&username)).await
+    pub async fn raw_get<T>(&self, path: &str, query: &[(&str, &str)]) -> Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        self.raw_request(Method::GET, path, query, None).await
     }
-}
 
-/// Builder for configuring and constructing a Client
-///
-/// Provides a fluent API for setting up authentication, HTTP client configuration,
-/// and other client options.
+    /// Call an arbitrary endpoint with a JSON POST request
+    ///
+    /// Like [`raw_get`](Self::raw_get), but serializes `body` as the JSON
+    /// request body.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let response: serde_json::Value = client
+    ///     .raw_post("/2/users/2244994945/muting", &serde_json::json!({
+    ///         "target_user_id": "6253282"
+    ///     }))
+    ///     .await?;
+    /// ```
+    pub async fn raw_post<T, B>(&self, path: &str, body: &B) -> Result<T>
+    where
+        T: DeserializeOwned,
+        B: Serialize + ?Sized,
+    {
+        let body = Body::json(body)?;
+        self.raw_request(Method::POST, path, &[], Some(&body)).await
+    }
+
+    /// Call an arbitrary endpoint with any method, query, and body encoding
+    ///
+    /// The most general form of [`raw_get`](Self::raw_get) and
+    /// [`raw_post`](Self::raw_post), e.g. for endpoints that take a
+    /// form-encoded body.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use x_api_client::builder::request::Body;
+    /// use reqwest::Method;
+    ///
+    /// let body = Body::form([("status", "Hello")]);
+    /// let response: serde_json::Value = client
+    ///     .raw_request(Method::POST, "/1.1/statuses/update.json", &[], Some(&body))
+    ///     .await?;
+    /// ```
+    pub async fn raw_request<T>(
+        &self,
+        method: Method,
+        path: &str,
+        query: &[(&str, &str)],
+        body: Option<&Body>,
+    ) -> Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        let query: Vec<(String, String)> = query
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        let response = self.send_raw(method, path, &query, body, None).await?;
+        self.parse_json(response).await
+    }
+
+    /// Build and authenticate a request without sending it
+    ///
+    /// Returns the request exactly as it would go over the wire (URL, headers
+    /// including `Authorization`, and body), for troubleshooting signature
+    /// mismatches and other 401s.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use x_api_client::builder::request::Body;
+    /// use reqwest::Method;
+    ///
+    /// let body = Body::json(&serde_json::json!({"text": "Hello"}))?;
+    /// let request = client
+    ///     .build_signed_request(Method::POST, "/2/tweets", &[], Some(&body))
+    ///     .await?;
+    /// println!("{:?}", request.headers());
+    /// ```
+    pub async fn build_signed_request(
+        &self,
+        method: Method,
+        path: &str,
+        query: &[(&str, &str)],
+        body: Option<&Body>,
+    ) -> Result<reqwest::Request> {
+        self.ensure_auth_supports(path)?;
+        let query: Vec<(String, String)> = query
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        let request = self.build_request(&method, path, &query, body)?;
+        self.auth.authenticate(request).await
+    }
+
+    /// Send a request and parse the standard `{data, includes, meta, errors}` envelope
+    pub(crate) async fn send<T>(
+        &self,
+        method: Method,
+        path: &str,
+        query: &[(String, String)],
+        body: Option<&Body>,
+        resource_id: Option<&str>,
+    ) -> Result<ApiResponse<T>>
+    where
+        T: DeserializeOwned,
+    {
+        let response = self
+            .send_raw(method, path, query, body, resource_id)
+            .await?;
+        self.parse_json(response).await
+    }
+
+    /// Deserialize a successful response body
+    ///
+    /// Honors [`ClientBuilder::strict_deserialization`].
+    async fn parse_json<T: DeserializeOwned>(&self, response: reqwest::Response) -> Result<T> {
+        let text = response.text().await?;
+        Ok(from_json_str(&text, self.strict_deserialization)?)
+    }
+
+    /// Look up objects by ID in one request (`GET path?ids=`)
+    ///
+    /// See [`LookupOptions`] for how duplicates and ordering are handled.
+    /// `id_of` extracts the ID of a returned object.
+    pub(crate) async fn get_by_ids<T>(
+        &self,
+        path: &str,
+        ids: &[String],
+        opts: &LookupOptions,
+        id_of: fn(&T) -> &str,
+    ) -> Result<Vec<Option<T>>>
+    where
+        T: DeserializeOwned + Clone,
+    {
+        let mut unique: Vec<&str> = Vec::with_capacity(ids.len());
+        for id in ids {
+            if !unique.contains(&id.as_str()) {
+                unique.push(id);
+            }
+        }
+        if unique.is_empty() {
+            return Err(Error::InvalidRequest(
+                "At least one ID is required for a lookup".to_string(),
+            ));
+        }
+        if unique.len() > MAX_LOOKUP_IDS {
+            return Err(Error::InvalidRequest(format!(
+                "Too many IDs: {} (max {})",
+                unique.len(),
+                MAX_LOOKUP_IDS
+            )));
+        }
+
+        let mut query = vec![(IDS_PARAM.to_string(), unique.join(","))];
+        query.extend(opts.to_query());
+        let found = self
+            .send::<Vec<T>>(Method::GET, path, &query, None, None)
+            .await?
+            .data
+            .unwrap_or_default();
+
+        if !opts.is_input_order_preserved() {
+            return Ok(found.into_iter().map(Some).collect());
+        }
+        let by_id: HashMap<&str, &T> = found.iter().map(|item| (id_of(item), item)).collect();
+        Ok(ids
+            .iter()
+            .map(|id| by_id.get(id.as_str()).map(|&item| item.clone()))
+            .collect())
+    }
+
+    /// Send a request through the full pipeline and return the successful response
+    ///
+    /// Every endpoint goes through here. Applies, in order:
+    /// - Auth routing: endpoints the provider does not support are rejected
+    /// - The [`UsageGuard`], if set, for requests other than `GET`
+    /// - Per-endpoint rate limiting, waiting for the window to reset when
+    ///   `auto_wait` is enabled
+    /// - Authentication of each attempt (signatures are never reused)
+    /// - Retries of transient failures according to the [`RetryPolicy`],
+    ///   with backoff retries drawn from the [`RetryBudget`] if one is set
+    ///
+    /// Non-success statuses are mapped to errors; `resource_id` is used in
+    /// the [`Error::NotFound`] message.
+    pub(crate) async fn send_raw(
+        &self,
+        method: Method,
+        path: &str,
+        query: &[(String, String)],
+        body: Option<&Body>,
+        resource_id: Option<&str>,
+    ) -> Result<reqwest::Response> {
+        self.ensure_auth_supports(path)?;
+        if let Some(guard) = &self.usage_guard {
+            if method != Method::GET {
+                guard.check(self).await?;
+            }
+        }
+
+        let key = endpoint_key(&method, path);
+        let mut backoff = self.retry_policy.backoff();
+        let mut retries = 0;
+        if let Some(budget) = &self.retry_budget {
+            budget.record_request(self.clock.now());
+        }
+
+        loop {
+            self.wait_for_rate_limit(&key).await?;
+            let attempt = self.send_once(&method, path, &key, query, body, resource_id);
+            let result = match (self.deadline, self.time_to_deadline()) {
+                (Some(deadline), Some(left)) => match tokio::time::timeout(left, attempt).await {
+                    Ok(result) => result,
+                    // The deadline passed during the attempt
+                    Err(_) => return Err(Error::Timeout(deadline.budget)),
+                },
+                _ => attempt.await,
+            };
+            let error = match result {
+                Ok(response) => return Ok(response),
+                // A timed out attempt is retried like any other while time remains
+                Err(error @ Error::Timeout(_)) if self.deadline_passed() => return Err(error),
+                Err(error) => error,
+            };
+
+            if retries >= self.retry_policy.max_retries() {
+                return Err(error);
+            }
+            let delay = match classify(&error, self.clock.now()) {
+                RetryDecision::DoNotRetry => return Err(error),
+                RetryDecision::RetryAfter(_) if !self.rate_limit_config.auto_wait() => {
+                    return Err(error);
+                }
+                RetryDecision::RetryAfter(delay) => delay,
+                RetryDecision::Backoff => {
+                    if let Some(budget) = &self.retry_budget {
+                        if !budget.try_withdraw(self.clock.now()) {
+                            tracing::warn!(endpoint = %key, "Retry budget exhausted, not retrying");
+                            return Err(error);
+                        }
+                    }
+                    backoff
+                        .next_backoff()
+                        .unwrap_or_else(|| self.retry_policy.max_interval())
+                }
+            };
+
+            self.ensure_wait_fits_deadline(delay)?;
+            retries += 1;
+            self.clock.sleep(delay).await;
+        }
+    }
+
+    /// Wait out the rate limit window of `key` before an attempt
+    ///
+    /// Fails instead with [`Error::RateLimitExceeded`] without auto-wait, or
+    /// with [`Error::Timeout`] if the wait would overrun the deadline.
+    async fn wait_for_rate_limit(&self, key: &str) -> Result<()> {
+        if !self.rate_limit_config.per_endpoint_tracking() {
+            return Ok(());
+        }
+        if let Some(state) = self.rate_limiter.state(key) {
+            if let Some(wait) = state.wait_duration(self.clock.now()) {
+                if !self.rate_limit_config.auto_wait() {
+                    return Err(state.exceeded_error(key));
+                }
+                self.ensure_wait_fits_deadline(wait)?;
+                self.clock.sleep(wait).await;
+            }
+        }
+        Ok(())
+    }
+
+    /// Perform a single attempt of a request
+    async fn send_once(
+        &self,
+        method: &Method,
+        path: &str,
+        key: &str,
+        query: &[(String, String)],
+        body: Option<&Body>,
+        resource_id: Option<&str>,
+    ) -> Result<reqwest::Response> {
+        let tracking = self.rate_limit_config.per_endpoint_tracking();
+
+        let request = self.build_request(method, path, query, body)?;
+        let request = self.auth.authenticate(request).await?;
+        if tracing::enabled!(tracing::Level::DEBUG) {
+            tracing::debug!(
+                request = %describe_request(self.redactor.as_ref(), &request, body),
+                "Sending request"
+            );
+        }
+        let started = std::time::Instant::now();
+        let response = self.http.execute(request).await;
+        if let Some(metrics) = &self.metrics {
+            let status = response.as_ref().ok().map(reqwest::Response::status);
+            metrics.record(key, status, started.elapsed());
+        }
+        let response = response?;
+
+        let rate_limit = if tracking {
+            self.rate_limiter
+                .update_from_headers(key, response.headers())?
+        } else {
+            RateLimitState::from_headers(response.headers())?
+        };
+
+        check_status(response, key, rate_limit, resource_id).await
+    }
+
+    /// Open a long-lived streaming connection
+    ///
+    /// Unlike [`send_raw`](Self::send_raw), connection attempts are neither
+    /// rate limited nor retried here (reconnecting is the stream's concern),
+    /// and the HTTP client's total request timeout is lifted so the body can
+    /// be read indefinitely.
+    pub(crate) async fn open_stream(
+        &self,
+        path: &str,
+        query: &[(String, String)],
+    ) -> Result<reqwest::Response> {
+        self.open_stream_with(path, query, true).await
+    }
+
+    /// Like [`open_stream`](Self::open_stream), leaving the body compressed
+    /// unless `decompress` is set
+    ///
+    /// Without decompression, `gzip` is requested explicitly and the body is
+    /// passed through as received.
+    pub(crate) async fn open_stream_with(
+        &self,
+        path: &str,
+        query: &[(String, String)],
+        decompress: bool,
+    ) -> Result<reqwest::Response> {
+        self.ensure_auth_supports(path)?;
+
+        let mut request = self.build_request(&Method::GET, path, query, None)?;
+        *request.timeout_mut() = Some(STREAM_REQUEST_TIMEOUT);
+        let response = if decompress {
+            let request = self.auth.authenticate(request).await?;
+            self.http.execute(request).await?
+        } else {
+            request.headers_mut().insert(
+                reqwest::header::ACCEPT_ENCODING,
+                reqwest::header::HeaderValue::from_static(GZIP_ENCODING),
+            );
+            let request = self.auth.authenticate(request).await?;
+            self.http.execute_undecoded(request).await?
+        };
+
+        let key = endpoint_key(&Method::GET, path);
+        let rate_limit = RateLimitState::from_headers(response.headers())?;
+        check_status(response, &key, rate_limit, None).await
+    }
+
+    /// Whether the configured auth provider can authenticate `path`
+    pub(crate) fn auth_supports(&self, path: &str) -> bool {
+        self.auth.supports_endpoint(path)
+    }
+
+    /// Fail if the configured auth provider cannot authenticate `path`
+    fn ensure_auth_supports(&self, path: &str) -> Result<()> {
+        if !self.auth_supports(path) {
+            return Err(Error::Authentication(format!(
+                "Endpoint '{}' is not supported by the configured auth provider",
+                path
+            )));
+        }
+        Ok(())
+    }
+
+    /// Build an unauthenticated request for `path` relative to the base URL
+    fn build_request(
+        &self,
+        method: &Method,
+        path: &str,
+        query: &[(String, String)],
+        body: Option<&Body>,
+    ) -> Result<reqwest::Request> {
+        let mut url = reqwest::Url::parse(&format!("{}{}", self.base_url, path))
+            .map_err(|e| Error::Config(format!("Invalid URL: {}", e)))?;
+        if !query.is_empty() {
+            url.query_pairs_mut().extend_pairs(query);
+        }
+
+        let mut request = reqwest::Request::new(method.clone(), url);
+        if let Some(body) = body {
+            *request.body_mut() = Some(body.to_bytes()?.into());
+            request.headers_mut().insert(
+                reqwest::header::CONTENT_TYPE,
+                reqwest::header::HeaderValue::from_static(body.content_type()),
+            );
+        }
+        Ok(request)
+    }
+
+    // Tweet Endpoints
+
+    /// Post a new tweet
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The tweet content and options
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use x_api_client::builder::request::TweetRequest;
+    ///
+    /// let tweet = client.post_tweet(TweetRequest::new("Hello, world!")).await?;
+    /// println!("Posted tweet with ID: {}", tweet.id);
+    /// ```
+    pub async fn post_tweet(
+        &self,
+        request: crate::builder::request::TweetRequest,
+    ) -> Result<crate::models::tweet::Tweet> {
+        self.post_tweet_with_includes(request).await?.into_data()
+    }
+
+    /// Post a new tweet, returning the full response envelope
+    ///
+    /// Unlike [`post_tweet`](Self::post_tweet), this keeps any `includes`
+    /// returned alongside the created tweet.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The tweet content and options
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use x_api_client::builder::request::TweetRequest;
+    ///
+    /// let response = client
+    ///     .post_tweet_with_includes(TweetRequest::new("Hello, world!"))
+    ///     .await?;
+    /// let includes = response.includes;
+    /// ```
+    pub async fn post_tweet_with_includes(
+        &self,
+        request: crate::builder::request::TweetRequest,
+    ) -> Result<ApiResponse<crate::models::tweet::Tweet>> {
+        self.post_tweet_with_fields(request, None).await
+    }
+
+    /// Post a new tweet, requesting `fields` on the created tweet
+    ///
+    /// The create response only carries `id`, `text` and
+    /// `edit_history_tweet_ids` by default; `fields` are sent as query
+    /// parameters to return other fields (e.g. `created_at`,
+    /// `edit_controls`) and expansions in `includes`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use x_api_client::builder::query::FieldsBuilder;
+    /// use x_api_client::builder::request::TweetRequest;
+    ///
+    /// let fields = FieldsBuilder::new()
+    ///     .tweet_fields(["created_at", "edit_controls"])
+    ///     .build()?;
+    /// let response = client
+    ///     .post_tweet_with_fields(TweetRequest::new("Hello, world!"), Some(&fields))
+    ///     .await?;
+    /// ```
+    pub async fn post_tweet_with_fields(
+        &self,
+        request: crate::builder::request::TweetRequest,
+        fields: Option<&crate::builder::query::Fields>,
+    ) -> Result<ApiResponse<crate::models::tweet::Tweet>> {
+        let body = Body::json(&request)?;
+        let query = fields
+            .map(crate::builder::query::Fields::to_query)
+            .unwrap_or_default();
+        self.send(Method::POST, paths::TWEETS, &query, Some(&body), None)
+            .await
+    }
+
+    /// Get a tweet by ID
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The tweet ID
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let tweet = client.get_tweet("1234567890").await?;
+    /// println!("Tweet: {}", tweet.text);
+    /// ```
+    pub async fn get_tweet(
+        &self,
+        id: impl Into<crate::models::common::TweetId>,
+    ) -> Result<crate::models::tweet::Tweet> {
+        self.get_tweet_with_includes(id, None).await?.into_data()
+    }
+
+    /// Delete a tweet by ID
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The tweet ID to delete
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let response = client.delete_tweet("1234567890").await?;
+    /// assert!(response.deleted);
+    /// ```
+    pub async fn delete_tweet(
+        &self,
+        id: impl Into<crate::models::common::TweetId>,
+    ) -> Result<crate::models::tweet::DeleteResponse> {
+        let id = id.into();
+        self.send(Method::DELETE, &paths::tweet(&id), &[], None, Some(&id))
+            .await?
+            .into_data()
+    }
+
+    // User Endpoints
+
+    /// Get a user by their user ID
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The user ID
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let user = client.get_user("1234567890").await?;
+    /// println!("User: @{}", user.username);
+    /// ```
+    pub async fn get_user(
+        &self,
+        id: impl Into<crate::models::common::UserId>,
+    ) -> Result<crate::models::user::User> {
+        let id = id.into();
+        self.send(Method::GET, &paths::user(&id), &[], None, Some(&id))
+            .await?
+            .into_data()
+    }
+
+    /// Get a user by their username
+    ///
+    /// # Arguments
+    ///
+    /// * `username` - The username (without @ symbol)
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let user = client.get_user_by_username("jack").await?;
+    /// println!("User ID: {}", user.id);
+    /// ```
+    pub async fn get_user_by_username(
+        &self,
+        username: impl Into<String>,
+    ) -> Result<crate::models::user::User> {
+        let username = username.into();
+        let path = paths::user_by_username(&username);
+        self.send(Method::GET, &path, &[], None, Some(&username))
+            .await?
+            .into_data()
+    }
+}
+
+/// Map a non-success response to an error, passing successful responses through
+async fn check_status(
+    response: reqwest::Response,
+    endpoint: &str,
+    rate_limit: Option<RateLimitState>,
+    resource_id: Option<&str>,
+) -> Result<reqwest::Response> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+
+    // Special handling for 404
+    if status == reqwest::StatusCode::NOT_FOUND {
+        let msg = match resource_id {
+            Some(id) => format!("Resource {} not found", id),
+            None => "Resource not found".to_string(),
+        };
+        return Err(Error::NotFound(msg));
+    }
+
+    // 429 with rate limit headers carries the reset time to wait for
+    if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        if let Some(state) = rate_limit {
+            return Err(state.exceeded_error(endpoint));
+        }
+    }
+
+    let error_text = response.text().await?;
+    Err(parse_api_error(status.as_u16(), &error_text))
+}
+
+/// Builder for configuring and constructing a Client
+///
+/// Provides a fluent API for setting up authentication, HTTP client configuration,
+/// and other client options.
 pub struct ClientBuilder<H: HttpClient + Clone = ReqwestClient> {
     http: Option<H>,
     auth: Option<Arc<dyn AuthProvider>>,
     rate_limit_config: Option<RateLimitConfig>,
     retry_policy: Option<RetryPolicy>,
+    retry_budget: Option<RetryBudget>,
     base_url: Option<String>,
     timeout: Option<std::time::Duration>,
+    clock: Option<Arc<dyn Clock>>,
+    redactor: Option<Arc<dyn BodyRedactor>>,
+    strict_deserialization: bool,
+    username_cache: Option<CacheConfig>,
+    metrics: Option<Arc<dyn MetricsRecorder>>,
 }
 
 impl Default for ClientBuilder<ReqwestClient> {
@@ -456,13 +1420,43 @@ impl Default for ClientBuilder<ReqwestClient> {
             auth: None,
             rate_limit_config: None,
             retry_policy: None,
+            retry_budget: None,
             base_url: None,
             timeout: None,
+            clock: None,
+            redactor: None,
+            strict_deserialization: false,
+            username_cache: None,
+            metrics: None,
         }
     }
 }
 
 impl<H: HttpClient + Clone> ClientBuilder<H> {
+    /// Create a ClientBuilder around a custom HTTP client
+    ///
+    /// Use this for HTTP clients other than [`ReqwestClient`], such as
+    /// [`MiddlewareClient`](crate::middleware::MiddlewareClient),
+    /// [`CassetteHttpClient`](crate::cassette::CassetteHttpClient) or your
+    /// own [`HttpClient`] implementation, which is built with
+    /// [`build_with_custom_http`](Self::build_with_custom_http).
+    pub fn with_http(http: H) -> Self {
+        Self {
+            http: Some(http),
+            auth: None,
+            rate_limit_config: None,
+            retry_policy: None,
+            retry_budget: None,
+            base_url: None,
+            timeout: None,
+            clock: None,
+            redactor: None,
+            strict_deserialization: false,
+            username_cache: None,
+            metrics: None,
+        }
+    }
+
     /// Configure OAuth 1.0a authentication
     ///
     /// # Arguments
@@ -517,6 +1511,72 @@ impl<H: HttpClient + Clone> ClientBuilder<H> {
         self
     }
 
+    /// Cap retries across all requests with a [`RetryBudget`]
+    ///
+    /// Once the budget is exhausted, transient failures are returned without
+    /// retrying. Waits for rate limit windows to reset are not counted.
+    ///
+    /// Default: no budget (retries are bounded by the [`RetryPolicy`] only)
+    pub fn retry_budget(mut self, budget: RetryBudget) -> Self {
+        self.retry_budget = Some(budget);
+        self
+    }
+
+    /// Cache the user IDs found by [`Client::resolve_username`]
+    ///
+    /// Entries expire after the configured TTL, since a username can be
+    /// released and claimed by another account.
+    ///
+    /// Default: no cache (every call looks the username up)
+    pub fn username_cache(mut self, config: CacheConfig) -> Self {
+        self.username_cache = Some(config);
+        self
+    }
+
+    /// Set the time source used for rate limit waits and retry backoff
+    ///
+    /// Default: [`SystemClock`]. Use a
+    /// [`MockClock`](crate::util::clock::MockClock) for deterministic tests.
+    pub fn clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    /// Set how request headers and bodies are redacted in request logs
+    ///
+    /// Default: [`DefaultRedactor`], which masks credentials and message
+    /// text.
+    pub fn body_redactor(mut self, redactor: Arc<dyn BodyRedactor>) -> Self {
+        self.redactor = Some(redactor);
+        self
+    }
+
+    /// Report the latency and status of each request attempt to `recorder`
+    ///
+    /// Use [`LatencyHistogram`](crate::util::metrics::LatencyHistogram) to
+    /// compute per-endpoint percentiles in memory.
+    ///
+    /// Default: none
+    pub fn metrics_recorder(mut self, recorder: Arc<dyn MetricsRecorder>) -> Self {
+        self.metrics = Some(recorder);
+        self
+    }
+
+    /// Reject unknown fields in response models instead of capturing them
+    ///
+    /// By default, fields the crate does not model are kept in each model's
+    /// `additional_fields`. When enabled, in responses and stream messages
+    /// alike, a tweet, user, media, Space, list,
+    /// direct message event, place, or poll carrying a field the crate does
+    /// not know fails to deserialize with [`Error::Serialization`](crate::Error::Serialization),
+    /// so tests catch drift between the API and your field mapping.
+    ///
+    /// Default: `false`
+    pub fn strict_deserialization(mut self, strict: bool) -> Self {
+        self.strict_deserialization = strict;
+        self
+    }
+
     /// Set a custom HTTP client
     ///
     /// Note: If you set a custom HTTP client, the `timeout()` configuration will be ignored.
@@ -525,6 +1585,59 @@ impl<H: HttpClient + Clone> ClientBuilder<H> {
         self.http = Some(http);
         self
     }
+
+    /// Build the Client around the HTTP client set via
+    /// [`with_http`](Self::with_http) or [`http_client`](Self::http_client)
+    ///
+    /// Works with any [`HttpClient`]; timeouts are configured on the HTTP
+    /// client itself.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no authentication provider or HTTP client is
+    /// configured.
+    pub fn build_with_custom_http(mut self) -> Result<Client<H>> {
+        let http = self.http.take().ok_or_else(|| {
+            crate::error::Error::Config(
+                "No HTTP client configured. Use ClientBuilder::with_http()".to_string(),
+            )
+        })?;
+        self.finish(http)
+    }
+
+    /// Assemble the client around the resolved HTTP client
+    fn finish(self, http: H) -> Result<Client<H>> {
+        let auth = self.auth.ok_or_else(|| {
+            crate::error::Error::Config(
+                "No authentication provider configured. Use .oauth1() or .auth()".to_string(),
+            )
+        })?;
+
+        Ok(Client {
+            http,
+            auth,
+            rate_limit_config: self.rate_limit_config.unwrap_or_default(),
+            rate_limiter: Arc::new(RateLimitTracker::new()),
+            retry_policy: self.retry_policy.unwrap_or_default(),
+            retry_budget: self
+                .retry_budget
+                .map(|budget| Arc::new(RetryBudgetTracker::new(budget))),
+            base_url: self
+                .base_url
+                .unwrap_or_else(|| DEFAULT_BASE_URL.to_string()),
+            clock: self.clock.unwrap_or_else(|| Arc::new(SystemClock)),
+            redactor: self
+                .redactor
+                .unwrap_or_else(|| Arc::new(DefaultRedactor::new())),
+            strict_deserialization: self.strict_deserialization,
+            deadline: None,
+            username_cache: self
+                .username_cache
+                .map(|config| Arc::new(TtlCache::new(config))),
+            metrics: self.metrics,
+            usage_guard: None,
+        })
+    }
 }
 
 impl ClientBuilder<ReqwestClient> {
@@ -552,13 +1665,7 @@ impl ClientBuilder<ReqwestClient> {
     /// - No authentication provider is configured
     /// - HTTP client creation fails
     /// - Both timeout and custom HTTP client are configured (conflicting options)
-    pub fn build(self) -> Result<Client<ReqwestClient>> {
-        let auth = self.auth.ok_or_else(|| {
-            crate::error::Error::Config(
-                "No authentication provider configured. Use .oauth1() or .auth()".to_string(),
-            )
-        })?;
-
+    pub fn build(mut self) -> Result<Client<ReqwestClient>> {
         // Check for conflicting configuration
         if self.http.is_some() && self.timeout.is_some() {
             return Err(crate::error::Error::Config(
@@ -566,7 +1673,7 @@ impl ClientBuilder<ReqwestClient> {
             ));
         }
 
-        let http = if let Some(http) = self.http {
+        let http = if let Some(http) = self.http.take() {
             http
         } else if let Some(timeout) = self.timeout {
             ReqwestClient::with_timeout(timeout)?
@@ -574,15 +1681,7 @@ impl ClientBuilder<ReqwestClient> {
             ReqwestClient::new()?
         };
 
-        Ok(Client {
-            http,
-            auth,
-            rate_limit_config: self.rate_limit_config.unwrap_or_default(),
-            retry_policy: self.retry_policy.unwrap_or_default(),
-            base_url: self
-                .base_url
-                .unwrap_or_else(|| "https://api.twitter.com".to_string()),
-        })
+        self.finish(http)
     }
 }
 
@@ -590,15 +1689,10 @@ impl ClientBuilder<ReqwestClient> {
 mod tests {
     //! # Testing with HttpClient
     //!
-    //! For testing code that uses `HttpClient`, we recommend using the `wiremock` or `mockito`
-    //! crates to create HTTP mock servers. These provide proper HTTP response mocking with
-    //! full control over status codes, headers, and body content.
-    //!
-    //! Example with wiremock (add to dev-dependencies):
-    //! ```toml
-    //! [dev-dependencies]
-    //! wiremock = "0.6"
-    //! ```
+    //! For testing code that uses `HttpClient`, we use the `wiremock` crate (a dev-dependency)
+    //! to create HTTP mock servers. It provides proper HTTP response mocking with full control
+    //! over status codes, headers, and body content. Point the client at the mock server with
+    //! `ClientBuilder::base_url(server.uri())`.
 
     use super::*;
 
@@ -627,6 +1721,69 @@ mod tests {
         assert!(client.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_reqwest_client_builder() {
+        let client = ReqwestClient::builder()
+            .connect_timeout(std::time::Duration::from_millis(250))
+            .local_address(std::net::IpAddr::from([127, 0, 0, 1]))
+            .resolve(
+                "api.twitter.com",
+                std::net::SocketAddr::from(([127, 0, 0, 1], 443)),
+            )
+            .build();
+        assert!(client.is_ok());
+
+        let client = ReqwestClient::builder()
+            .timeout(std::time::Duration::from_secs(1))
+            .connect_timeout(std::time::Duration::from_secs(5))
+            .build();
+        assert!(matches!(client, Err(Error::Config(_))));
+    }
+
+    #[tokio::test]
+    async fn test_build_signed_request() {
+        let client = Client::new("ck", "cs", "at", "ats").unwrap();
+        let body = Body::json(&serde_json::json!({"text": "Hello"})).unwrap();
+
+        let request = client
+            .build_signed_request(Method::POST, paths::TWEETS, &[], Some(&body))
+            .await
+            .unwrap();
+
+        assert_eq!(request.method(), Method::POST);
+        assert_eq!(request.url().as_str(), "https://api.twitter.com/2/tweets");
+        let authorization = request.headers()[reqwest::header::AUTHORIZATION]
+            .to_str()
+            .unwrap();
+        assert!(authorization.starts_with("OAuth "));
+        assert_eq!(
+            request.body().and_then(|b| b.as_bytes()),
+            Some(br#"{"text":"Hello"}"#.as_slice())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_undecoded_client_built_on_first_use() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+        let request = || reqwest::Request::new(Method::GET, server.uri().parse().unwrap());
+
+        let http = ReqwestClient::new().unwrap();
+        let undecoded = Arc::clone(http.undecoded.as_ref().unwrap());
+        http.execute(request()).await.unwrap();
+        assert!(undecoded.client.get().is_none());
+
+        // Clones share the client once built
+        http.clone().execute_undecoded(request()).await.unwrap();
+        assert!(undecoded.client.get().is_some());
+    }
+
     #[test]
     fn test_reqwest_client_inner() {
         let client = ReqwestClient::new().unwrap();
@@ -718,6 +1875,729 @@ mod tests {
         let _base = client.base_url();
     }
 
+    #[test]
+    fn test_client_capabilities_include_create_tweet() {
+        use crate::endpoints::catalog::AuthKind;
+
+        let client = Client::new("ck", "cs", "at", "ats").unwrap();
+        let capabilities = client.capabilities();
+
+        let create_tweet = capabilities
+            .iter()
+            .find(|e| e.method == reqwest::Method::POST && e.path == "/2/tweets")
+            .expect("create-tweet endpoint should be listed");
+        assert_eq!(create_tweet.auth, AuthKind::UserContext);
+        assert!(client.supports_endpoint(create_tweet));
+    }
+
+    #[tokio::test]
+    async fn test_post_tweet_returns_full_tweet_and_includes() {
+        use crate::builder::request::TweetRequest;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/2/tweets"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+                "data": {
+                    "id": "1445880548472328192",
+                    "text": "Hello, world!",
+                    "edit_history_tweet_ids": ["1445880548472328192"]
+                },
+                "includes": {
+                    "users": [{"id": "2244994945", "name": "X Dev", "username": "XDevelopers"}]
+                }
+            })))
+            .mount(&server)
+            .await;
+
+        let client = Client::builder()
+            .oauth1("ck", "cs", "at", "ats")
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+
+        let tweet = client
+            .post_tweet(TweetRequest::new("Hello, world!"))
+            .await
+            .unwrap();
+        assert_eq!(tweet.id, "1445880548472328192");
+        assert_eq!(tweet.edit_history_tweet_ids, vec!["1445880548472328192"]);
+
+        let response = client
+            .post_tweet_with_includes(TweetRequest::new("Hello, world!"))
+            .await
+            .unwrap();
+        let users = response.includes.unwrap().users.unwrap();
+        assert_eq!(users[0].username, "XDevelopers");
+        assert_eq!(
+            response.data.unwrap().edit_history_tweet_ids,
+            vec!["1445880548472328192"]
+        );
+    }
+
+    #[test]
+    fn test_with_auth_shares_rate_limit_tracker() {
+        let client = Client::builder()
+            .oauth1("ck", "cs", "at", "ats")
+            .build()
+            .unwrap();
+        let alice = client.with_auth(Arc::new(crate::auth::oauth1::OAuth1Provider::new(
+            "ck",
+            "cs",
+            "alice",
+            "alice-secret",
+        )));
+        let bob = client.with_auth(Arc::new(crate::auth::bearer::BearerTokenProvider::new(
+            "token",
+        )));
+
+        assert!(Arc::ptr_eq(&alice.rate_limiter, &bob.rate_limiter));
+        assert!(Arc::ptr_eq(&alice.rate_limiter, &client.rate_limiter));
+        assert!(!alice.auth_supports(paths::TWEETS_SEARCH_STREAM));
+        assert!(bob.auth_supports(paths::TWEETS_SEARCH_STREAM));
+
+        let isolated = client.with_auth_isolated(alice.auth.clone());
+        assert!(!Arc::ptr_eq(&isolated.rate_limiter, &client.rate_limiter));
+    }
+
+    #[tokio::test]
+    async fn test_post_tweet_with_fields_requests_created_at() {
+        use crate::builder::query::FieldsBuilder;
+        use crate::builder::request::TweetRequest;
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/2/tweets"))
+            .and(query_param("tweet.fields", "created_at"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+                "data": {
+                    "id": "1445880548472328192",
+                    "text": "Hello, world!",
+                    "edit_history_tweet_ids": ["1445880548472328192"],
+                    "created_at": "2021-10-06T22:00:00.000Z"
+                }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = Client::builder()
+            .oauth1("ck", "cs", "at", "ats")
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+        let fields = FieldsBuilder::new()
+            .tweet_fields(["created_at"])
+            .build()
+            .unwrap();
+
+        let tweet = client
+            .post_tweet_with_fields(TweetRequest::new("Hello, world!"), Some(&fields))
+            .await
+            .unwrap()
+            .into_data()
+            .unwrap();
+        assert_eq!(
+            tweet.created_at.unwrap().to_rfc3339(),
+            "2021-10-06T22:00:00+00:00"
+        );
+    }
+
+    #[derive(Debug, serde::Deserialize)]
+    struct MutingResponse {
+        data: MutingData,
+    }
+
+    #[derive(Debug, serde::Deserialize)]
+    struct MutingData {
+        muting: bool,
+    }
+
+    fn mock_client(server: &wiremock::MockServer) -> Client {
+        Client::builder()
+            .oauth1("ck", "cs", "at", "ats")
+            .base_url(server.uri())
+            .retry_policy(
+                RetryPolicy::custom()
+                    .initial_interval(std::time::Duration::from_millis(1))
+                    .max_interval(std::time::Duration::from_millis(5))
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_raw_get_and_post_deserialize_custom_types() {
+        use wiremock::matchers::{body_json, header_exists, method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/users/123/muting"))
+            .and(query_param("max_results", "5"))
+            .and(header_exists("authorization"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {"muting": false}
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/2/users/123/muting"))
+            .and(body_json(serde_json::json!({"target_user_id": "456"})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {"muting": true}
+            })))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+
+        let response: MutingResponse = client
+            .raw_get("/2/users/123/muting", &[("max_results", "5")])
+            .await
+            .unwrap();
+        assert!(!response.data.muting);
+
+        let response: MutingResponse = client
+            .raw_post(
+                "/2/users/123/muting",
+                &serde_json::json!({"target_user_id": "456"}),
+            )
+            .await
+            .unwrap();
+        assert!(response.data.muting);
+    }
+
+    #[tokio::test]
+    async fn test_raw_request_sends_form_body() {
+        use wiremock::matchers::{body_string, header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/2/users/123/muting"))
+            .and(header("content-type", "application/x-www-form-urlencoded"))
+            .and(body_string("target_user_id=456"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {"muting": true}
+            })))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+        let body = Body::form([("target_user_id", "456")]);
+        let response: MutingResponse = client
+            .raw_request(Method::POST, "/2/users/123/muting", &[], Some(&body))
+            .await
+            .unwrap();
+        assert!(response.data.muting);
+    }
+
+    #[tokio::test]
+    async fn test_raw_request_reaches_legacy_form_endpoint() {
+        use wiremock::matchers::{body_string, header, header_regex, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/1.1/statuses/update.json"))
+            .and(header("content-type", "application/x-www-form-urlencoded"))
+            .and(header_regex("authorization", "^OAuth .*oauth_signature="))
+            .and(body_string("status=Hello"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"id_str": "1", "text": "Hello"})),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+        let body = Body::form([("status", "Hello")]);
+        let response: serde_json::Value = client
+            .raw_request(Method::POST, "/1.1/statuses/update.json", &[], Some(&body))
+            .await
+            .unwrap();
+        assert_eq!(response["id_str"], "1");
+    }
+
+    #[tokio::test]
+    async fn test_raw_get_rejects_endpoint_unsupported_by_auth() {
+        let server = wiremock::MockServer::start().await;
+        let client = mock_client(&server);
+
+        // App-only endpoint: OAuth 1.0a user context does not apply
+        let result: Result<serde_json::Value> = client
+            .raw_get("/2/tweets/search/recent", &[("query", "rust")])
+            .await;
+        assert!(matches!(result, Err(Error::Authentication(_))));
+        assert!(server.received_requests().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_raw_get_retries_server_errors() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/users/123/muting"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(2)
+            .with_priority(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/2/users/123/muting"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {"muting": true}
+            })))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+        let response: MutingResponse = client.raw_get("/2/users/123/muting", &[]).await.unwrap();
+        assert!(response.data.muting);
+        assert_eq!(server.received_requests().await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_strict_deserialization_rejects_unknown_tweet_fields() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/tweets/20"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {"id": "20", "text": "just setting up my twttr", "mood": "cheerful"}
+            })))
+            .mount(&server)
+            .await;
+
+        let tweet = mock_client(&server).get_tweet("20").await.unwrap();
+        assert_eq!(tweet.additional_fields["mood"], "cheerful");
+
+        let strict = Client::builder()
+            .oauth1("ck", "cs", "at", "ats")
+            .base_url(server.uri())
+            .strict_deserialization(true)
+            .build()
+            .unwrap();
+        let err = strict.get_tweet("20").await.unwrap_err();
+        assert!(
+            matches!(&err, Error::Serialization(e) if e.to_string().contains("unknown fields: mood")),
+            "{:?}",
+            err
+        );
+    }
+
+    #[tokio::test]
+    async fn test_retry_budget_stops_retries_once_exhausted() {
+        use crate::util::clock::MockClock;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/users/123/muting"))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&server)
+            .await;
+
+        let client = Client::builder()
+            .oauth1("ck", "cs", "at", "ats")
+            .base_url(server.uri())
+            .clock(Arc::new(MockClock::new(chrono::Utc::now())))
+            .retry_budget(
+                RetryBudget::custom()
+                    .ratio(0.1)
+                    .min_retries(0)
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap();
+
+        for _ in 0..20 {
+            let result: Result<MutingResponse> = client.raw_get("/2/users/123/muting", &[]).await;
+            assert!(result.is_err());
+        }
+
+        // 20 requests earn 2 retries, despite 3 allowed per request
+        assert_eq!(server.received_requests().await.unwrap().len(), 22);
+    }
+
+    #[tokio::test]
+    async fn test_metrics_recorder_sees_each_attempt() {
+        use reqwest::StatusCode;
+        use std::sync::Mutex;
+        use std::time::Duration;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        type Recorded = (String, Option<StatusCode>, Duration);
+
+        #[derive(Default)]
+        struct Recorder(Mutex<Vec<Recorded>>);
+
+        impl MetricsRecorder for Recorder {
+            fn record(&self, endpoint: &str, status: Option<StatusCode>, elapsed: Duration) {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push((endpoint.to_string(), status, elapsed));
+            }
+        }
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/tweets/1445078208190291973"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(
+                        serde_json::json!({"data": {"id": "1445078208190291973", "text": "hi"}}),
+                    )
+                    .set_delay(Duration::from_millis(5)),
+            )
+            .mount(&server)
+            .await;
+        let recorder = Arc::new(Recorder::default());
+        let client = Client::builder()
+            .oauth1("ck", "cs", "at", "ats")
+            .metrics_recorder(recorder.clone())
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+
+        client.get_tweet("1445078208190291973").await.unwrap();
+
+        let recorded = recorder.0.lock().unwrap();
+        assert_eq!(recorded.len(), 1);
+        let (endpoint, status, elapsed) = &recorded[0];
+        assert_eq!(endpoint, "GET /2/tweets/:id");
+        assert_eq!(*status, Some(StatusCode::OK));
+        assert!(*elapsed > Duration::ZERO);
+    }
+
+    #[test]
+    fn test_from_lookup_selects_provider() {
+        let env = |vars: &[(&'static str, &'static str)]| {
+            let vars: HashMap<&str, &str> = vars.iter().copied().collect();
+            move |name: &str| {
+                vars.get(name)
+                    .map(|value| value.to_string())
+                    .ok_or(VarError::NotPresent)
+            }
+        };
+        let oauth1 = [
+            (CONSUMER_KEY_VAR, "ck"),
+            (CONSUMER_SECRET_VAR, "cs"),
+            (ACCESS_TOKEN_VAR, "at"),
+            (ACCESS_TOKEN_SECRET_VAR, "ats"),
+        ];
+
+        // OAuth 1.0a is user context: it cannot call app-only endpoints
+        let client = Client::from_lookup(env(&oauth1)).unwrap();
+        assert!(client.auth_supports(paths::USERS_ME));
+        assert!(!client.auth_supports(paths::TWEETS_SEARCH_ALL));
+
+        let client = Client::from_lookup(env(&[(BEARER_TOKEN_VAR, "token")])).unwrap();
+        assert!(client.auth_supports(paths::TWEETS_SEARCH_ALL));
+        assert!(!client.auth_supports(paths::USERS_ME));
+
+        let partial = [(CONSUMER_KEY_VAR, "ck"), (ACCESS_TOKEN_VAR, "")];
+        let Err(Error::Config(message)) = Client::from_lookup(env(&partial)) else {
+            panic!("expected a config error");
+        };
+        assert!(message.contains("X_CONSUMER_SECRET, X_ACCESS_TOKEN, X_ACCESS_TOKEN_SECRET"));
+
+        let Err(Error::Config(message)) = Client::from_lookup(env(&[])) else {
+            panic!("expected a config error");
+        };
+        assert!(message.contains(BEARER_TOKEN_VAR));
+
+        // Undecodable values are reported rather than treated as unset
+        let not_unicode = |name: &str| match name {
+            BEARER_TOKEN_VAR => Err(VarError::NotUnicode("\u{fffd}".into())),
+            _ => Err(VarError::NotPresent),
+        };
+        let Err(Error::Config(message)) = Client::from_lookup(not_unicode) else {
+            panic!("expected a config error");
+        };
+        assert!(message.contains(BEARER_TOKEN_VAR) && message.contains("Unicode"));
+    }
+
+    #[tokio::test]
+    async fn test_ping() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/2/openapi.json"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let client = Client::builder()
+            .oauth1("ck", "cs", "at", "ats")
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+        assert!(client.ping().await.is_ok());
+
+        // Nothing listens on a port released right after binding it
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let unreachable = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+        let client = Client::builder()
+            .oauth1("ck", "cs", "at", "ats")
+            .base_url(unreachable)
+            .build()
+            .unwrap();
+        assert!(matches!(client.ping().await, Err(Error::Network(_))));
+    }
+
+    /// Transport that fails attempts slower than `attempt_timeout` with
+    /// [`Error::Timeout`]
+    #[derive(Clone)]
+    struct AttemptTimeoutHttp {
+        inner: ReqwestClient,
+        attempt_timeout: std::time::Duration,
+    }
+
+    impl HttpClient for AttemptTimeoutHttp {
+        async fn execute(&self, request: reqwest::Request) -> Result<reqwest::Response> {
+            tokio::time::timeout(self.attempt_timeout, self.inner.execute(request))
+                .await
+                .unwrap_or(Err(Error::Timeout(self.attempt_timeout)))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_timed_out_attempt_retried_before_deadline() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/users/123/muting"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"data": {"muting": true}}))
+                    .set_delay(std::time::Duration::from_secs(2)),
+            )
+            .up_to_n_times(1)
+            .with_priority(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/2/users/123/muting"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"data": {"muting": true}})),
+            )
+            .mount(&server)
+            .await;
+
+        let http = AttemptTimeoutHttp {
+            inner: ReqwestClient::new().unwrap(),
+            attempt_timeout: std::time::Duration::from_millis(100),
+        };
+        let client = ClientBuilder::with_http(http)
+            .oauth1("ck", "cs", "at", "ats")
+            .base_url(server.uri())
+            .retry_policy(
+                RetryPolicy::custom()
+                    .initial_interval(std::time::Duration::from_millis(1))
+                    .max_interval(std::time::Duration::from_millis(5))
+                    .build()
+                    .unwrap(),
+            )
+            .build_with_custom_http()
+            .unwrap()
+            .with_deadline(chrono::Utc::now() + chrono::Duration::seconds(10));
+
+        let response: MutingResponse = client.raw_get("/2/users/123/muting", &[]).await.unwrap();
+        assert!(response.data.muting);
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_deadline_shorter_than_rate_limit_wait_times_out() {
+        use crate::util::clock::{Clock, MockClock};
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let now = chrono::Utc::now();
+        let reset = (now + chrono::Duration::minutes(15)).timestamp();
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/users/123/muting"))
+            .respond_with(
+                ResponseTemplate::new(429)
+                    .insert_header("x-rate-limit-limit", "15")
+                    .insert_header("x-rate-limit-remaining", "0")
+                    .insert_header("x-rate-limit-reset", reset.to_string()),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let clock = Arc::new(MockClock::new(now));
+        let client = Client::builder()
+            .oauth1("ck", "cs", "at", "ats")
+            .base_url(server.uri())
+            .clock(clock.clone())
+            .build()
+            .unwrap();
+        let scoped = client.with_deadline(now + chrono::Duration::seconds(5));
+
+        let result: Result<MutingResponse> = scoped.raw_get("/2/users/123/muting", &[]).await;
+        assert!(matches!(
+            result,
+            Err(Error::Timeout(budget)) if budget == std::time::Duration::from_secs(5)
+        ));
+        // Failed before sleeping through the rate limit window
+        assert_eq!(clock.now(), now);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_endpoint_fails_fast_without_auto_wait() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let reset = (chrono::Utc::now() + chrono::Duration::minutes(15)).timestamp();
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/users/123/muting"))
+            .respond_with(
+                ResponseTemplate::new(429)
+                    .insert_header("x-rate-limit-limit", "15")
+                    .insert_header("x-rate-limit-remaining", "0")
+                    .insert_header("x-rate-limit-reset", reset.to_string()),
+            )
+            .mount(&server)
+            .await;
+
+        let client = Client::builder()
+            .oauth1("ck", "cs", "at", "ats")
+            .base_url(server.uri())
+            .rate_limit(RateLimitConfig::custom().auto_wait(false).build().unwrap())
+            .build()
+            .unwrap();
+
+        let result: Result<serde_json::Value> = client.raw_get("/2/users/123/muting", &[]).await;
+        match result {
+            Err(Error::RateLimitExceeded {
+                endpoint, limit, ..
+            }) => {
+                assert_eq!(endpoint, "GET /2/users/:id/muting");
+                assert_eq!(limit, 15);
+            }
+            other => panic!("Expected RateLimitExceeded, got {:?}", other),
+        }
+
+        // The tracked window now rejects calls before they reach the server
+        let result: Result<serde_json::Value> = client.raw_get("/2/users/456/muting", &[]).await;
+        assert!(matches!(result, Err(Error::RateLimitExceeded { .. })));
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_fail_fast_on_rate_limit_overrides_auto_wait() {
+        use crate::util::clock::MockClock;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let start = chrono::DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let reset = start + chrono::Duration::minutes(15);
+        let clock = MockClock::new(start);
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/users/123/muting"))
+            .respond_with(
+                ResponseTemplate::new(429)
+                    .insert_header("x-rate-limit-limit", "15")
+                    .insert_header("x-rate-limit-remaining", "0")
+                    .insert_header("x-rate-limit-reset", reset.timestamp().to_string()),
+            )
+            .mount(&server)
+            .await;
+
+        let client = Client::builder()
+            .oauth1("ck", "cs", "at", "ats")
+            .base_url(server.uri())
+            .clock(Arc::new(clock.clone()))
+            .build()
+            .unwrap();
+        assert!(client.rate_limit_config().auto_wait());
+
+        let fail_fast = client.fail_fast_on_rate_limit();
+        let result: Result<serde_json::Value> = fail_fast.raw_get("/2/users/123/muting", &[]).await;
+        assert!(matches!(
+            result,
+            Err(Error::RateLimitExceeded { reset_at, .. }) if reset_at == reset
+        ));
+        assert!(clock.sleeps().is_empty());
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+        assert!(client.rate_limit_config().auto_wait());
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_wait_uses_clock() {
+        use crate::util::clock::MockClock;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let start = chrono::DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let reset = start + chrono::Duration::seconds(42);
+        let clock = MockClock::new(start);
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/users/123/muting"))
+            .respond_with(
+                ResponseTemplate::new(429)
+                    .insert_header("x-rate-limit-limit", "15")
+                    .insert_header("x-rate-limit-remaining", "0")
+                    .insert_header("x-rate-limit-reset", reset.timestamp().to_string()),
+            )
+            .up_to_n_times(1)
+            .with_priority(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/2/users/123/muting"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {"muting": true}
+            })))
+            .mount(&server)
+            .await;
+
+        let client = Client::builder()
+            .oauth1("ck", "cs", "at", "ats")
+            .base_url(server.uri())
+            .clock(Arc::new(clock.clone()))
+            .build()
+            .unwrap();
+
+        let response: MutingResponse = client.raw_get("/2/users/123/muting", &[]).await.unwrap();
+        assert!(response.data.muting);
+
+        // Waited exactly until the reset time, then retried once
+        assert_eq!(clock.sleeps(), vec![std::time::Duration::from_secs(42)]);
+        assert_eq!(clock.now(), reset);
+    }
+
     #[test]
     fn test_client_builder_with_custom_auth() {
         let auth = Arc::new(crate::auth::oauth1::OAuth1Provider::new(
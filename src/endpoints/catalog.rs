@@ -0,0 +1,141 @@
+//! Catalog of implemented endpoints and their authentication requirements
+//!
+//! Used by [`Client::capabilities`](crate::Client::capabilities) for tooling,
+//! documentation generation, and checking that a configured auth provider
+//! covers the endpoints an application intends to call.
+
+use crate::endpoints::paths;
+use reqwest::Method;
+
+/// Authentication context an endpoint accepts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AuthKind {
+    /// App-only authentication (OAuth 2.0 bearer token)
+    AppOnly,
+    /// User-context authentication (OAuth 1.0a or OAuth 2.0 PKCE)
+    UserContext,
+    /// Either app-only or user-context authentication
+    Either,
+}
+
+/// Description of a single implemented endpoint
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EndpointInfo {
+    /// Path template with `:name` placeholders (e.g. `/2/tweets/:id`)
+    pub path: String,
+
+    /// HTTP method
+    pub method: Method,
+
+    /// Required authentication context
+    pub auth: AuthKind,
+}
+
+impl EndpointInfo {
+    fn new(method: Method, path: impl Into<String>, auth: AuthKind) -> Self {
+        Self {
+            path: path.into(),
+            method,
+            auth,
+        }
+    }
+}
+
+/// List every endpoint implemented by the client
+///
+/// Path templates are produced by the [`paths`] builders so the catalog
+/// cannot drift from the paths actually requested.
+pub(crate) fn implemented_endpoints() -> Vec<EndpointInfo> {
+    vec![
+        EndpointInfo::new(Method::POST, paths::TWEETS, AuthKind::UserContext),
+        EndpointInfo::new(Method::GET, paths::TWEETS, AuthKind::Either),
+        EndpointInfo::new(Method::GET, paths::tweet(":id"), AuthKind::Either),
+        EndpointInfo::new(Method::DELETE, paths::tweet(":id"), AuthKind::UserContext),
+        EndpointInfo::new(
+            Method::PUT,
+            paths::tweet_hidden(":id"),
+            AuthKind::UserContext,
+        ),
+        EndpointInfo::new(Method::GET, paths::USERS, AuthKind::Either),
+        EndpointInfo::new(Method::GET, paths::user(":id"), AuthKind::Either),
+        EndpointInfo::new(Method::GET, paths::USERS_ME, AuthKind::UserContext),
+        EndpointInfo::new(
+            Method::GET,
+            paths::user_by_username(":username"),
+            AuthKind::Either,
+        ),
+        EndpointInfo::new(Method::GET, paths::TWEETS_SEARCH_RECENT, AuthKind::Either),
+        EndpointInfo::new(Method::GET, paths::TWEETS_SEARCH_ALL, AuthKind::AppOnly),
+        EndpointInfo::new(Method::GET, paths::TWEETS_COUNTS_RECENT, AuthKind::AppOnly),
+        EndpointInfo::new(Method::GET, paths::TWEETS_COUNTS_ALL, AuthKind::AppOnly),
+        EndpointInfo::new(Method::GET, paths::TWEETS_SEARCH_STREAM, AuthKind::AppOnly),
+        EndpointInfo::new(
+            Method::GET,
+            paths::TWEETS_SAMPLE10_STREAM,
+            AuthKind::AppOnly,
+        ),
+        EndpointInfo::new(
+            Method::GET,
+            paths::TWEETS_COMPLIANCE_STREAM,
+            AuthKind::AppOnly,
+        ),
+        EndpointInfo::new(
+            Method::GET,
+            paths::USERS_COMPLIANCE_STREAM,
+            AuthKind::AppOnly,
+        ),
+        EndpointInfo::new(Method::GET, paths::SPACES, AuthKind::Either),
+        EndpointInfo::new(Method::GET, paths::SPACES_SEARCH, AuthKind::Either),
+        EndpointInfo::new(Method::GET, paths::space(":id"), AuthKind::Either),
+        EndpointInfo::new(Method::GET, paths::DM_EVENTS, AuthKind::UserContext),
+        EndpointInfo::new(Method::GET, paths::dm_event(":id"), AuthKind::UserContext),
+        EndpointInfo::new(
+            Method::DELETE,
+            paths::dm_event(":id"),
+            AuthKind::UserContext,
+        ),
+        EndpointInfo::new(
+            Method::POST,
+            paths::TWEETS_SEARCH_STREAM_RULES,
+            AuthKind::AppOnly,
+        ),
+        EndpointInfo::new(
+            Method::GET,
+            paths::TWEETS_SEARCH_STREAM_RULES,
+            AuthKind::AppOnly,
+        ),
+        EndpointInfo::new(
+            Method::GET,
+            paths::user_owned_lists(":id"),
+            AuthKind::Either,
+        ),
+        EndpointInfo::new(
+            Method::GET,
+            paths::user_followed_lists(":id"),
+            AuthKind::Either,
+        ),
+        EndpointInfo::new(Method::GET, paths::list_tweets(":id"), AuthKind::Either),
+        EndpointInfo::new(Method::GET, paths::USAGE_TWEETS, AuthKind::AppOnly),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catalog_has_no_duplicates() {
+        let endpoints = implemented_endpoints();
+        for (i, a) in endpoints.iter().enumerate() {
+            for b in &endpoints[i + 1..] {
+                assert!(
+                    !(a.method == b.method && a.path == b.path),
+                    "duplicate catalog entry: {} {}",
+                    a.method,
+                    a.path
+                );
+            }
+        }
+    }
+}
@@ -1 +1,195 @@
 //! Direct Message operations
+
+use crate::builder::query::{DM_EVENTS_MAX_RESULTS, Fields, PaginationOptions};
+use crate::client::{Client, HttpClient};
+use crate::endpoints::paths;
+use crate::error::Result;
+use crate::models::common::{ApiResponse, DmEventId};
+use crate::models::direct_message::DirectMessageEvent;
+use crate::models::tweet::DeleteResponse;
+use reqwest::Method;
+
+impl<H: HttpClient + Clone> Client<H> {
+    /// Get a page of the authenticated user's direct message events
+    ///
+    /// Returns the whole response so that expanded objects (senders, media,
+    /// shared tweets) are available in `includes` and the next page token in
+    /// `meta`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use x_api_client::builder::query::{FieldsBuilder, PaginationOptions};
+    ///
+    /// let fields = FieldsBuilder::new()
+    ///     .expansions(["sender_id", "attachments.media_keys", "referenced_tweets.id"])
+    ///     .dm_event_fields(["created_at", "sender_id", "text", "attachments"])
+    ///     .user_fields(["username"])
+    ///     .media_fields(["url"])
+    ///     .tweet_fields(["text"])
+    ///     .build()?;
+    /// let page = client.get_dm_events(&PaginationOptions::new(), Some(&fields)).await?;
+    /// let senders = page.includes.and_then(|i| i.users).unwrap_or_default();
+    /// ```
+    pub async fn get_dm_events(
+        &self,
+        page: &PaginationOptions,
+        fields: Option<&Fields>,
+    ) -> Result<ApiResponse<Vec<DirectMessageEvent>>> {
+        page.validate(DM_EVENTS_MAX_RESULTS)?;
+        let mut query = page.to_query();
+        if let Some(fields) = fields {
+            query.extend(fields.to_query());
+        }
+        self.send(Method::GET, paths::DM_EVENTS, &query, None, None)
+            .await
+    }
+
+    /// Get a single direct message event by ID
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::NotFound`](crate::Error::NotFound) if the event does
+    /// not exist or is not visible to the authenticated user.
+    pub async fn get_dm_event(&self, event_id: impl Into<DmEventId>) -> Result<DirectMessageEvent> {
+        let id = event_id.into();
+        self.send(Method::GET, &paths::dm_event(&id), &[], None, Some(&id))
+            .await?
+            .into_data()
+    }
+
+    /// Delete a direct message event sent by the authenticated user
+    pub async fn delete_dm_event(&self, event_id: impl Into<DmEventId>) -> Result<DeleteResponse> {
+        let id = event_id.into();
+        self.send(Method::DELETE, &paths::dm_event(&id), &[], None, Some(&id))
+            .await?
+            .into_data()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::query::FieldsBuilder;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn client(server: &MockServer) -> Client {
+        Client::builder()
+            .oauth1("ck", "cs", "at", "ats")
+            .base_url(server.uri())
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_get_dm_events_with_expansions() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/dm_events"))
+            .and(query_param(
+                "expansions",
+                "attachments.media_keys,referenced_tweets.id,sender_id",
+            ))
+            .and(query_param("dm_event.fields", "sender_id,text"))
+            .and(query_param("user.fields", "username"))
+            .and(query_param("max_results", "20"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [{
+                    "id": "1",
+                    "event_type": "MessageCreate",
+                    "text": "hi",
+                    "sender_id": "42"
+                }],
+                "includes": {
+                    "users": [{"id": "42", "name": "Ferris", "username": "ferris"}]
+                },
+                "meta": {"result_count": 1, "next_token": "next"}
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let fields = FieldsBuilder::new()
+            .expansions([
+                "attachments.media_keys",
+                "referenced_tweets.id",
+                "sender_id",
+            ])
+            .dm_event_fields(["sender_id", "text"])
+            .user_fields(["username"])
+            .build()
+            .unwrap();
+        let page = client(&server)
+            .get_dm_events(&PaginationOptions::new().max_results(20), Some(&fields))
+            .await
+            .unwrap();
+
+        let event = &page.data.as_ref().unwrap()[0];
+        let senders = page.includes.as_ref().unwrap().users.as_ref().unwrap();
+        assert_eq!(senders[0].id, event.sender_id.clone().unwrap());
+        assert_eq!(senders[0].username, "ferris");
+        assert_eq!(page.meta.unwrap().next_token.as_deref(), Some("next"));
+    }
+
+    #[tokio::test]
+    async fn test_get_dm_events_rejects_out_of_range_page_size() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/dm_events"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let err = client(&server)
+            .get_dm_events(&PaginationOptions::new().max_results(500), None)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, crate::error::Error::InvalidRequest(_)));
+    }
+
+    #[tokio::test]
+    async fn test_get_and_delete_dm_event() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/dm_events/7"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {"id": "7", "event_type": "MessageCreate", "text": "hi"}
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/2/dm_events/7"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"data": {"deleted": true}})),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = client(&server);
+        let event = client.get_dm_event("7").await.unwrap();
+        assert_eq!(event.text.as_deref(), Some("hi"));
+        assert!(client.delete_dm_event("7").await.unwrap().deleted);
+    }
+
+    #[tokio::test]
+    async fn test_get_dm_event_not_found() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/dm_events/404"))
+            .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+                "title": "Not Found Error",
+                "detail": "Could not find dm_event with id: [404].",
+                "type": "https://api.twitter.com/2/problems/resource-not-found"
+            })))
+            .mount(&server)
+            .await;
+
+        let err = client(&server).get_dm_event("404").await.unwrap_err();
+        assert!(matches!(err, crate::error::Error::NotFound(ref msg) if msg.contains("404")));
+    }
+}
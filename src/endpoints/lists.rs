@@ -1 +1,296 @@
 //! Lists operations
+
+use crate::builder::query::{LISTS_MAX_RESULTS, PaginationOptions};
+use crate::client::{Client, HttpClient};
+use crate::endpoints::paths;
+use crate::error::Result;
+use crate::models::common::{ApiResponse, ListId, UserId};
+use crate::models::list::List;
+use crate::models::tweet::Tweet;
+use crate::pagination::cursor::PaginationSpec;
+use reqwest::Method;
+use std::time::Duration;
+use tokio_stream::Stream;
+
+impl<H: HttpClient + Clone> Client<H> {
+    /// Get every List owned by a user
+    ///
+    /// Follows pagination until the last page. `opts` sets the page size and
+    /// an optional starting `pagination_token`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use x_api_client::builder::query::PaginationOptions;
+    ///
+    /// let lists = client
+    ///     .owned_lists("2244994945", &PaginationOptions::new().max_results(100))
+    ///     .await?;
+    /// ```
+    pub async fn owned_lists(
+        &self,
+        user_id: impl Into<UserId>,
+        opts: &PaginationOptions,
+    ) -> Result<Vec<List>> {
+        opts.validate(LISTS_MAX_RESULTS)?;
+        let user_id = user_id.into();
+        self.get_all_pages(
+            &paths::user_owned_lists(&user_id),
+            PaginationSpec::PAGINATION_TOKEN,
+            opts.page_query(),
+            opts.get_pagination_token().map(str::to_string),
+            Some(&user_id),
+        )
+        .await
+    }
+
+    /// Get every List a user follows
+    ///
+    /// Paginated like [`owned_lists`](Self::owned_lists).
+    pub async fn followed_lists(
+        &self,
+        user_id: impl Into<UserId>,
+        opts: &PaginationOptions,
+    ) -> Result<Vec<List>> {
+        opts.validate(LISTS_MAX_RESULTS)?;
+        let user_id = user_id.into();
+        self.get_all_pages(
+            &paths::user_followed_lists(&user_id),
+            PaginationSpec::PAGINATION_TOKEN,
+            opts.page_query(),
+            opts.get_pagination_token().map(str::to_string),
+            Some(&user_id),
+        )
+        .await
+    }
+
+    /// Get a page of the tweets in a List, newest first
+    ///
+    /// Returns the first page, or the page at the `pagination_token` set in
+    /// `opts`; the next page's token is in `meta.next_token`. Expansions
+    /// requested with [`PaginationOptions::fields`] come back in `includes`.
+    /// Public Lists can be read with app-only authentication.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use x_api_client::builder::query::{FieldsBuilder, PaginationOptions};
+    ///
+    /// let fields = FieldsBuilder::new().expansions(["author_id"]).build()?;
+    /// let page = client
+    ///     .list_tweets("84839422", &PaginationOptions::new().fields(fields))
+    ///     .await?;
+    /// for tweet in page.data.iter().flatten() {
+    ///     let author = page.includes.as_ref().and_then(|i| i.user(tweet.author_id.as_deref()?));
+    ///     println!("{:?}: {}", author.map(|u| &u.username), tweet.text);
+    /// }
+    /// ```
+    pub async fn list_tweets(
+        &self,
+        list_id: impl Into<ListId>,
+        opts: &PaginationOptions,
+    ) -> Result<ApiResponse<Vec<Tweet>>> {
+        opts.validate(LISTS_MAX_RESULTS)?;
+        let list_id = list_id.into();
+        self.send(
+            Method::GET,
+            &paths::list_tweets(&list_id),
+            &opts.to_query(),
+            None,
+            Some(&list_id),
+        )
+        .await
+    }
+
+    /// Stream every page of the tweets in a List
+    ///
+    /// Like [`list_tweets`](Self::list_tweets), following `next_token` as
+    /// the stream is polled. Each page keeps its own `includes`. Invalid
+    /// options are reported as the first item.
+    pub fn list_tweets_paged<'a>(
+        &'a self,
+        list_id: &str,
+        opts: &PaginationOptions,
+    ) -> impl Stream<Item = Result<ApiResponse<Vec<Tweet>>>> + 'a {
+        let path = paths::list_tweets(list_id);
+        let query = opts.page_query();
+        let first_token = opts.get_pagination_token().map(str::to_string);
+        let valid = opts.validate(LISTS_MAX_RESULTS);
+        async_stream::try_stream! {
+            valid?;
+            for await page in self.stream_pages(
+                &path,
+                PaginationSpec::PAGINATION_TOKEN,
+                query,
+                first_token,
+                Duration::ZERO,
+            ) {
+                yield page?;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path, query_param, query_param_is_missing};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn client(server: &MockServer) -> Client {
+        Client::builder()
+            .oauth1("ck", "cs", "at", "ats")
+            .base_url(server.uri())
+            .build()
+            .unwrap()
+    }
+
+    fn page(ids: &[&str], next_token: Option<&str>) -> ResponseTemplate {
+        let data: Vec<_> = ids
+            .iter()
+            .map(|id| serde_json::json!({"id": id, "name": format!("List {}", id)}))
+            .collect();
+        let mut meta = serde_json::json!({"result_count": ids.len()});
+        if let Some(token) = next_token {
+            meta["next_token"] = token.into();
+        }
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({"data": data, "meta": meta}))
+    }
+
+    #[tokio::test]
+    async fn test_owned_lists_follows_next_token() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/users/42/owned_lists"))
+            .and(query_param("max_results", "2"))
+            .and(query_param_is_missing("pagination_token"))
+            .respond_with(page(&["1", "2"], Some("page2")))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/2/users/42/owned_lists"))
+            .and(query_param("max_results", "2"))
+            .and(query_param("pagination_token", "page2"))
+            .respond_with(page(&["3"], None))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let lists = client(&server)
+            .owned_lists("42", &PaginationOptions::new().max_results(2))
+            .await
+            .unwrap();
+        let ids: Vec<_> = lists.iter().map(|l| l.id.as_str()).collect();
+        assert_eq!(ids, vec!["1", "2", "3"]);
+    }
+
+    #[tokio::test]
+    async fn test_followed_lists_starts_from_given_token() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/users/42/followed_lists"))
+            .and(query_param("pagination_token", "resume"))
+            .respond_with(page(&["7"], None))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let lists = client(&server)
+            .followed_lists("42", &PaginationOptions::new().pagination_token("resume"))
+            .await
+            .unwrap();
+        assert_eq!(lists.len(), 1);
+        assert_eq!(lists[0].id, "7");
+    }
+
+    #[tokio::test]
+    async fn test_empty_page_and_stuck_token() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/users/1/owned_lists"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"meta": {"result_count": 0}})),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/2/users/2/followed_lists"))
+            .respond_with(page(&["1"], Some("same")))
+            .mount(&server)
+            .await;
+
+        let client = client(&server);
+        assert!(
+            client
+                .owned_lists("1", &PaginationOptions::new())
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        let err = client
+            .followed_lists("2", &PaginationOptions::new())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, crate::error::Error::InvalidResponse(_)));
+    }
+
+    #[tokio::test]
+    async fn test_list_tweets_pages_with_author_expansion() {
+        use crate::builder::query::FieldsBuilder;
+        use tokio_stream::StreamExt;
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/lists/84/tweets"))
+            .and(query_param("expansions", "author_id"))
+            .and(query_param_is_missing("pagination_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [{"id": "1", "text": "first", "author_id": "12"}],
+                "includes": {"users": [{"id": "12", "name": "Jack", "username": "jack"}]},
+                "meta": {"result_count": 1, "next_token": "page2"}
+            })))
+            .expect(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/2/lists/84/tweets"))
+            .and(query_param("expansions", "author_id"))
+            .and(query_param("pagination_token", "page2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [{"id": "2", "text": "second", "author_id": "12"}],
+                "includes": {"users": [{"id": "12", "name": "Jack", "username": "jack"}]},
+                "meta": {"result_count": 1}
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = client(&server);
+        let fields = FieldsBuilder::new()
+            .expansions(["author_id"])
+            .build()
+            .unwrap();
+        let opts = PaginationOptions::new().fields(fields);
+
+        let page = client.list_tweets("84", &opts).await.unwrap();
+        assert_eq!(page.meta.unwrap().next_token.as_deref(), Some("page2"));
+        let author = page.includes.as_ref().unwrap().user("12").unwrap();
+        assert_eq!(author.username, "jack");
+
+        let pages: Vec<_> = client
+            .list_tweets_paged("84", &opts)
+            .collect::<Result<_>>()
+            .await
+            .unwrap();
+        let texts: Vec<_> = pages
+            .iter()
+            .flat_map(|page| page.data.iter().flatten())
+            .map(|tweet| tweet.text.as_str())
+            .collect();
+        assert_eq!(texts, vec!["first", "second"]);
+        assert!(pages.iter().all(|page| page.includes.is_some()));
+    }
+}
@@ -7,10 +7,15 @@
 //! - Lists (CRUD operations, membership)
 //! - Direct Messages (1-to-1 and group conversations)
 //! - Compliance endpoints
+//! - Project usage, with a guard against exceeding the monthly cap
 
+pub mod catalog;
 pub mod compliance;
 pub mod direct_messages;
 pub mod lists;
+pub mod paths;
+pub mod search;
 pub mod spaces;
 pub mod tweets;
+pub mod usage;
 pub mod users;
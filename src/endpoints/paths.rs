@@ -0,0 +1,196 @@
+//! Endpoint path constants and builders
+//!
+//! All request paths are defined here so that endpoint implementations and
+//! authentication routing (see [`OAuth1Provider`](crate::auth::oauth1::OAuth1Provider))
+//! share a single source of truth.
+//!
+//! Static paths are exposed as constants, parameterized paths as functions
+//! returning an owned `String`. Paths never include the base URL.
+
+/// Tweets collection (`POST /2/tweets`, `GET /2/tweets?ids=`)
+pub const TWEETS: &str = "/2/tweets";
+
+/// Tweet search endpoints root (recent, full-archive, filtered stream)
+pub const TWEETS_SEARCH: &str = "/2/tweets/search";
+
+/// Recent search (last 7 days)
+pub const TWEETS_SEARCH_RECENT: &str = "/2/tweets/search/recent";
+
+/// Full-archive search
+pub const TWEETS_SEARCH_ALL: &str = "/2/tweets/search/all";
+
+/// Filtered stream
+pub const TWEETS_SEARCH_STREAM: &str = "/2/tweets/search/stream";
+
+/// Filtered stream rule management
+pub const TWEETS_SEARCH_STREAM_RULES: &str = "/2/tweets/search/stream/rules";
+
+/// Tweet compliance stream
+pub const TWEETS_COMPLIANCE_STREAM: &str = "/2/tweets/compliance/stream";
+
+/// User compliance stream
+pub const USERS_COMPLIANCE_STREAM: &str = "/2/users/compliance/stream";
+
+/// Sample streams root (1% and 10% volume streams)
+pub const TWEETS_SAMPLE: &str = "/2/tweets/sample";
+
+/// 10% sample stream
+pub const TWEETS_SAMPLE10_STREAM: &str = "/2/tweets/sample10/stream";
+
+/// Tweet counts root (recent and full-archive)
+pub const TWEETS_COUNTS: &str = "/2/tweets/counts";
+
+/// Recent tweet counts (last 7 days)
+pub const TWEETS_COUNTS_RECENT: &str = "/2/tweets/counts/recent";
+
+/// Full-archive tweet counts
+pub const TWEETS_COUNTS_ALL: &str = "/2/tweets/counts/all";
+
+/// Users collection
+pub const USERS: &str = "/2/users";
+
+/// Authenticated user lookup
+pub const USERS_ME: &str = "/2/users/me";
+
+/// Direct message conversations
+pub const DM_CONVERSATIONS: &str = "/2/dm_conversations";
+
+/// Direct message events
+pub const DM_EVENTS: &str = "/2/dm_events";
+
+/// Lists collection
+pub const LISTS: &str = "/2/lists";
+
+/// Spaces collection
+pub const SPACES: &str = "/2/spaces";
+
+/// Space search
+pub const SPACES_SEARCH: &str = "/2/spaces/search";
+
+/// Batch compliance jobs
+pub const COMPLIANCE_JOBS: &str = "/2/compliance/jobs";
+
+/// Project tweet usage against the monthly cap
+pub const USAGE_TWEETS: &str = "/2/usage/tweets";
+
+/// Legacy v1.1 API root, for form-encoded user-context endpoints such as
+/// `/1.1/statuses/update.json`
+pub const V1_1: &str = "/1.1";
+
+/// OpenAPI specification document
+pub const OPENAPI_SPEC: &str = "/2/openapi.json";
+
+/// OAuth 1.0a request token (first step of the three-legged flow)
+pub const OAUTH_REQUEST_TOKEN: &str = "/oauth/request_token";
+
+/// OAuth 1.0a user authorization page (second step)
+pub const OAUTH_AUTHORIZE: &str = "/oauth/authorize";
+
+/// OAuth 1.0a access token exchange (third step)
+pub const OAUTH_ACCESS_TOKEN: &str = "/oauth/access_token";
+
+/// OAuth 2.0 app-only token endpoint (client credentials grant)
+pub const OAUTH2_TOKEN: &str = "/oauth2/token";
+
+/// OAuth 2.0 app-only token invalidation
+pub const OAUTH2_INVALIDATE_TOKEN: &str = "/oauth2/invalidate_token";
+
+/// OAuth 2.0 user token revocation
+pub const OAUTH2_REVOKE: &str = "/2/oauth2/revoke";
+
+/// Single tweet (`/2/tweets/:id`)
+pub fn tweet(id: &str) -> String {
+    format!("{}/{}", TWEETS, id)
+}
+
+/// Hidden state of a reply (`/2/tweets/:id/hidden`)
+pub fn tweet_hidden(id: &str) -> String {
+    format!("{}/{}/hidden", TWEETS, id)
+}
+
+/// Single user (`/2/users/:id`)
+pub fn user(id: &str) -> String {
+    format!("{}/{}", USERS, id)
+}
+
+/// User lookup by username (`/2/users/by/username/:username`)
+pub fn user_by_username(username: &str) -> String {
+    format!("{}/by/username/{}", USERS, username)
+}
+
+/// Tweets liked by a user (`/2/users/:id/likes`)
+pub fn user_likes(id: &str) -> String {
+    format!("{}/{}/likes", USERS, id)
+}
+
+/// Single direct message event (`/2/dm_events/:id`)
+pub fn dm_event(id: &str) -> String {
+    format!("{}/{}", DM_EVENTS, id)
+}
+
+/// Lists owned by a user (`/2/users/:id/owned_lists`)
+pub fn user_owned_lists(id: &str) -> String {
+    format!("{}/{}/owned_lists", USERS, id)
+}
+
+/// Lists followed by a user (`/2/users/:id/followed_lists`)
+pub fn user_followed_lists(id: &str) -> String {
+    format!("{}/{}/followed_lists", USERS, id)
+}
+
+/// Tweets of a List (`/2/lists/:id/tweets`)
+pub fn list_tweets(id: &str) -> String {
+    format!("{}/{}/tweets", LISTS, id)
+}
+
+/// Single Space (`/2/spaces/:id`)
+pub fn space(id: &str) -> String {
+    format!("{}/{}", SPACES, id)
+}
+
+/// Check whether `endpoint` is a resource nested under `root`
+///
+/// Returns `true` for `/2/users/123` under `/2/users`, but `false` for
+/// `/2/users` itself and for sibling paths such as `/2/users_extra`.
+pub fn is_nested_under(endpoint: &str, root: &str) -> bool {
+    endpoint
+        .strip_prefix(root)
+        .is_some_and(|rest| rest.starts_with('/'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_static_paths() {
+        assert_eq!(TWEETS, "/2/tweets");
+        assert_eq!(TWEETS_SEARCH_RECENT, "/2/tweets/search/recent");
+        assert_eq!(TWEETS_SEARCH_STREAM_RULES, "/2/tweets/search/stream/rules");
+        assert_eq!(USERS_ME, "/2/users/me");
+        assert_eq!(USERS_COMPLIANCE_STREAM, "/2/users/compliance/stream");
+    }
+
+    #[test]
+    fn test_parameterized_paths() {
+        assert_eq!(tweet("123"), "/2/tweets/123");
+        assert_eq!(tweet_hidden("123"), "/2/tweets/123/hidden");
+        assert_eq!(user("456"), "/2/users/456");
+        assert_eq!(user_by_username("jack"), "/2/users/by/username/jack");
+        assert_eq!(user_likes("456"), "/2/users/456/likes");
+        assert_eq!(dm_event("789"), "/2/dm_events/789");
+        assert_eq!(user_owned_lists("456"), "/2/users/456/owned_lists");
+        assert_eq!(user_followed_lists("456"), "/2/users/456/followed_lists");
+        assert_eq!(list_tweets("84"), "/2/lists/84/tweets");
+        assert_eq!(space("1DXxyRYNejbKM"), "/2/spaces/1DXxyRYNejbKM");
+    }
+
+    #[test]
+    fn test_is_nested_under() {
+        assert!(is_nested_under("/2/users/123", USERS));
+        assert!(is_nested_under("/2/users/123/likes", USERS));
+        assert!(!is_nested_under("/2/users", USERS));
+        assert!(!is_nested_under("/2/users_extra", USERS));
+        assert!(!is_nested_under("/2/tweets/123", USERS));
+    }
+}
@@ -0,0 +1,589 @@
+//! Tweet search and counts
+//!
+//! Recent search covers the last 7 days; full-archive search and counts
+//! cover every public tweet but have much lower rate limits, so their paged
+//! variants pace requests (see [`FULL_ARCHIVE_PAGE_INTERVAL`]).
+
+use crate::builder::query::{CountOptions, SearchOptions, SearchScope};
+use crate::client::{Client, HttpClient};
+use crate::endpoints::paths;
+use crate::error::Result;
+use crate::models::common::ApiResponse;
+use crate::models::tweet::{Tweet, TweetCount};
+use crate::pagination::cursor::{PageQuery, PaginationSpec};
+use crate::util::lock_unpoisoned;
+use reqwest::Method;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio_stream::Stream;
+
+/// Minimum delay between consecutive full-archive requests
+///
+/// Full-archive search and counts allow one request per second per app, on
+/// top of their per-window limits.
+pub const FULL_ARCHIVE_PAGE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Tweets from a full-archive search, fetched page by page
+///
+/// Returned by [`Client::search_all_stream`]. Dropping the stream stops the
+/// walk; [`resume_token`](Self::resume_token) tells where to pick it up.
+pub struct SearchAllStream<'a> {
+    tweets: Pin<Box<dyn Stream<Item = Result<Tweet>> + 'a>>,
+    resume_token: Arc<Mutex<Option<String>>>,
+}
+
+impl SearchAllStream<'_> {
+    /// Token of the page holding the next tweet not yet yielded
+    ///
+    /// Resuming from it may repeat tweets of a partially consumed page, but
+    /// never skips any. `None` until the first page is done when starting
+    /// from the beginning, and after the last page.
+    pub fn resume_token(&self) -> Option<String> {
+        lock_unpoisoned(&self.resume_token).clone()
+    }
+}
+
+impl Stream for SearchAllStream<'_> {
+    type Item = Result<Tweet>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.tweets.as_mut().poll_next(cx)
+    }
+}
+
+/// Tweets and time-bucketed counts for the same recent search
+///
+/// Returned by [`Client::search_recent_counts_combined`].
+#[derive(Debug, Clone)]
+pub struct RecentSearchWithCounts {
+    /// Page of matching tweets
+    pub tweets: ApiResponse<Vec<Tweet>>,
+
+    /// Matching tweet counts per bucket
+    pub counts: ApiResponse<Vec<TweetCount>>,
+}
+
+impl<H: HttpClient + Clone> Client<H> {
+    /// Search tweets from the last 7 days
+    ///
+    /// Returns the first page of results, or the page at
+    /// [`SearchOptions::next_token`] if set.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidRequest`](crate::Error::InvalidRequest) without sending anything if the time
+    /// bounds are invalid for recent search (see [`SearchOptions::validate`]).
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use chrono::{Duration, Utc};
+    /// use x_api_client::builder::query::SearchOptions;
+    ///
+    /// let opts = SearchOptions::new().start_time(Utc::now() - Duration::hours(6));
+    /// let page = client.search_recent("from:XDevelopers", &opts).await?;
+    /// ```
+    pub async fn search_recent(
+        &self,
+        query: &str,
+        opts: &SearchOptions,
+    ) -> Result<ApiResponse<Vec<Tweet>>> {
+        opts.validate(SearchScope::Recent, self.clock().now())?;
+        self.send(
+            Method::GET,
+            paths::TWEETS_SEARCH_RECENT,
+            &opts.to_query(query),
+            None,
+            None,
+        )
+        .await
+    }
+
+    /// Search the full tweet archive
+    ///
+    /// Returns the first page of results (or the page at
+    /// [`SearchOptions::next_token`]); use
+    /// [`search_all_paged`](Self::search_all_paged) to follow `next_token`.
+    /// Requires app-only authentication.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidRequest`](crate::Error::InvalidRequest) without sending anything if the time
+    /// bounds are invalid (see [`SearchOptions::validate`]).
+    pub async fn search_all(
+        &self,
+        query: &str,
+        opts: &SearchOptions,
+    ) -> Result<ApiResponse<Vec<Tweet>>> {
+        opts.validate(SearchScope::All, self.clock().now())?;
+        self.send(
+            Method::GET,
+            paths::TWEETS_SEARCH_ALL,
+            &opts.to_query(query),
+            None,
+            None,
+        )
+        .await
+    }
+
+    /// Stream every page of a full-archive search
+    ///
+    /// Pages are fetched as the stream is polled, at most one per
+    /// [`FULL_ARCHIVE_PAGE_INTERVAL`]. Invalid options are reported as the
+    /// first item.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use tokio_stream::StreamExt;
+    /// use x_api_client::builder::query::SearchOptions;
+    ///
+    /// let pages = client.search_all_paged("from:XDevelopers", &SearchOptions::new());
+    /// tokio::pin!(pages);
+    /// while let Some(page) = pages.next().await {
+    ///     for tweet in page?.data.unwrap_or_default() {
+    ///         println!("{}", tweet.text);
+    ///     }
+    /// }
+    /// ```
+    pub fn search_all_paged<'a>(
+        &'a self,
+        query: &str,
+        opts: &SearchOptions,
+    ) -> impl Stream<Item = Result<ApiResponse<Vec<Tweet>>>> + 'a {
+        let valid = opts.validate(SearchScope::All, self.clock().now());
+        self.full_archive_pages(
+            valid,
+            paths::TWEETS_SEARCH_ALL,
+            opts.page_query(query),
+            opts.get_next_token().map(str::to_string),
+        )
+    }
+
+    /// Stream every tweet matching a full-archive search
+    ///
+    /// Walks the pages like [`search_all_paged`](Self::search_all_paged),
+    /// yielding tweets one at a time. Rate limits are waited out by the
+    /// client (see [`RateLimitConfig`](crate::rate_limit::RateLimitConfig)).
+    /// To resume an interrupted walk, pass
+    /// [`SearchAllStream::resume_token`] to [`SearchOptions::next_token`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use tokio_stream::StreamExt;
+    /// use x_api_client::builder::query::SearchOptions;
+    ///
+    /// let mut tweets = client.search_all_stream("from:XDevelopers", &SearchOptions::new());
+    /// while let Some(tweet) = tweets.next().await {
+    ///     match tweet {
+    ///         Ok(tweet) => println!("{}", tweet.text),
+    ///         Err(e) => {
+    ///             eprintln!("stopped ({}), resume from {:?}", e, tweets.resume_token());
+    ///             break;
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn search_all_stream<'a>(
+        &'a self,
+        query: &str,
+        opts: &SearchOptions,
+    ) -> SearchAllStream<'a> {
+        let resume_token = Arc::new(Mutex::new(opts.get_next_token().map(str::to_string)));
+        let cursor = Arc::clone(&resume_token);
+        let pages = self.search_all_paged(query, opts);
+        let tweets = async_stream::try_stream! {
+            for await page in pages {
+                let page = page?;
+                let next = PaginationSpec::NEXT_TOKEN.next_token(page.meta.as_ref());
+                for tweet in page.data.unwrap_or_default() {
+                    yield tweet;
+                }
+                *lock_unpoisoned(&cursor) = next;
+            }
+        };
+
+        SearchAllStream {
+            tweets: Box::pin(tweets),
+            resume_token,
+        }
+    }
+
+    /// Count tweets from the last 7 days matching a query
+    ///
+    /// The total over the window is in
+    /// `meta.additional_fields["total_tweet_count"]`. Requires app-only
+    /// authentication.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidRequest`](crate::Error::InvalidRequest) without sending anything if the time
+    /// bounds are invalid for recent counts (see [`CountOptions::validate`]).
+    pub async fn count_recent(
+        &self,
+        query: &str,
+        opts: &CountOptions,
+    ) -> Result<ApiResponse<Vec<TweetCount>>> {
+        opts.validate(SearchScope::Recent, self.clock().now())?;
+        self.send(
+            Method::GET,
+            paths::TWEETS_COUNTS_RECENT,
+            &opts.to_query(query),
+            None,
+            None,
+        )
+        .await
+    }
+
+    /// Search recent tweets and count them in one call
+    ///
+    /// Sends [`search_recent`](Self::search_recent) and
+    /// [`count_recent`](Self::count_recent) concurrently; each goes through
+    /// the client's rate limiting like a standalone call. Fails with the
+    /// first error if either request fails. Requires app-only authentication.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use x_api_client::builder::query::{CountOptions, Granularity, SearchOptions};
+    ///
+    /// let combined = client
+    ///     .search_recent_counts_combined(
+    ///         "#rustlang",
+    ///         &SearchOptions::new(),
+    ///         &CountOptions::new().granularity(Granularity::Hour),
+    ///     )
+    ///     .await?;
+    /// println!("{} buckets", combined.counts.data.unwrap_or_default().len());
+    /// ```
+    pub async fn search_recent_counts_combined(
+        &self,
+        query: &str,
+        search: &SearchOptions,
+        counts: &CountOptions,
+    ) -> Result<RecentSearchWithCounts> {
+        let (tweets, counts) = tokio::try_join!(
+            self.search_recent(query, search),
+            self.count_recent(query, counts)
+        )?;
+        Ok(RecentSearchWithCounts { tweets, counts })
+    }
+
+    /// Count tweets matching a query over the full archive
+    ///
+    /// Returns the first page of buckets; use
+    /// [`count_all_paged`](Self::count_all_paged) to follow `next_token`.
+    /// The total over the page is in `meta.additional_fields["total_tweet_count"]`.
+    /// Requires app-only authentication.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidRequest`](crate::Error::InvalidRequest) without sending anything if the time
+    /// bounds are invalid (see [`CountOptions::validate`]).
+    pub async fn count_all(
+        &self,
+        query: &str,
+        opts: &CountOptions,
+    ) -> Result<ApiResponse<Vec<TweetCount>>> {
+        opts.validate(SearchScope::All, self.clock().now())?;
+        self.send(
+            Method::GET,
+            paths::TWEETS_COUNTS_ALL,
+            &opts.to_query(query),
+            None,
+            None,
+        )
+        .await
+    }
+
+    /// Stream every page of full-archive tweet counts
+    ///
+    /// Pages are fetched as the stream is polled, at most one per
+    /// [`FULL_ARCHIVE_PAGE_INTERVAL`]. Invalid options are reported as the
+    /// first item.
+    pub fn count_all_paged<'a>(
+        &'a self,
+        query: &str,
+        opts: &CountOptions,
+    ) -> impl Stream<Item = Result<ApiResponse<Vec<TweetCount>>>> + 'a {
+        let valid = opts.validate(SearchScope::All, self.clock().now());
+        self.full_archive_pages(
+            valid,
+            paths::TWEETS_COUNTS_ALL,
+            opts.page_query(query),
+            None,
+        )
+    }
+
+    /// Page through a full-archive endpoint once the options are validated
+    fn full_archive_pages<'a, T>(
+        &'a self,
+        valid: Result<()>,
+        path: &'static str,
+        query: PageQuery,
+        first_token: Option<String>,
+    ) -> impl Stream<Item = Result<ApiResponse<Vec<T>>>> + 'a
+    where
+        T: serde::de::DeserializeOwned + 'a,
+    {
+        async_stream::try_stream! {
+            valid?;
+            for await page in self.stream_pages(
+                path,
+                PaginationSpec::NEXT_TOKEN,
+                query,
+                first_token,
+                FULL_ARCHIVE_PAGE_INTERVAL,
+            ) {
+                yield page?;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::bearer::BearerTokenProvider;
+    use crate::error::Error;
+    use crate::util::clock::MockClock;
+    use std::sync::Arc;
+    use tokio_stream::StreamExt;
+    use wiremock::matchers::{method, path, query_param, query_param_is_missing};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn client(server: &MockServer, clock: &MockClock) -> Client {
+        Client::builder()
+            .auth(Arc::new(BearerTokenProvider::new("test")))
+            .base_url(server.uri())
+            .clock(Arc::new(clock.clone()))
+            .build()
+            .unwrap()
+    }
+
+    fn bucket(start: &str, end: &str, count: u64) -> serde_json::Value {
+        serde_json::json!({"start": start, "end": end, "tweet_count": count})
+    }
+
+    #[tokio::test]
+    async fn test_search_recent_rejects_old_start_time_before_sending() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let opts = SearchOptions::new().start_time(chrono::Utc::now() - chrono::Duration::days(8));
+        let err = client(&server, &MockClock::new(chrono::Utc::now()))
+            .search_recent("rust", &opts)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidRequest(_)));
+    }
+
+    #[tokio::test]
+    async fn test_count_all_paged_follows_next_token() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/tweets/counts/all"))
+            .and(query_param("query", "rust"))
+            .and(query_param("granularity", "day"))
+            .and(query_param_is_missing("next_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [bucket("2024-03-02T00:00:00.000Z", "2024-03-03T00:00:00.000Z", 7)],
+                "meta": {"total_tweet_count": 7, "next_token": "page2"}
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/2/tweets/counts/all"))
+            .and(query_param("next_token", "page2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [bucket("2024-03-01T00:00:00.000Z", "2024-03-02T00:00:00.000Z", 3)],
+                "meta": {"total_tweet_count": 3}
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let clock = MockClock::new(chrono::Utc::now());
+        let client = client(&server, &clock);
+        let opts = CountOptions::new().granularity(crate::builder::query::Granularity::Day);
+        let pages: Vec<_> = client
+            .count_all_paged("rust", &opts)
+            .collect::<Result<Vec<_>>>()
+            .await
+            .unwrap();
+
+        let counts: Vec<u64> = pages
+            .into_iter()
+            .flat_map(|page| page.data.unwrap_or_default())
+            .map(|bucket| bucket.tweet_count)
+            .collect();
+        assert_eq!(counts, vec![7, 3]);
+        // Paced once, between the two pages
+        assert_eq!(clock.sleeps(), vec![FULL_ARCHIVE_PAGE_INTERVAL]);
+    }
+
+    #[tokio::test]
+    async fn test_search_recent_counts_combined_sends_both_requests() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/tweets/search/recent"))
+            .and(query_param("query", "rust"))
+            .respond_with(tweets_page(&["1", "2"], None))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/2/tweets/counts/recent"))
+            .and(query_param("query", "rust"))
+            .and(query_param("granularity", "hour"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [bucket("2024-03-02T00:00:00.000Z", "2024-03-02T01:00:00.000Z", 2)],
+                "meta": {"total_tweet_count": 2}
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = client(&server, &MockClock::new(chrono::Utc::now()));
+        let counts = CountOptions::new().granularity(crate::builder::query::Granularity::Hour);
+        let combined = client
+            .search_recent_counts_combined("rust", &SearchOptions::new(), &counts)
+            .await
+            .unwrap();
+
+        let ids: Vec<_> = combined
+            .tweets
+            .data
+            .unwrap()
+            .into_iter()
+            .map(|tweet| tweet.id)
+            .collect();
+        assert_eq!(ids, vec!["1", "2"]);
+        assert_eq!(combined.counts.data.unwrap()[0].tweet_count, 2);
+    }
+
+    #[tokio::test]
+    async fn test_search_all_paged_reports_invalid_options_first() {
+        let server = MockServer::start().await;
+        let clock = MockClock::new(chrono::Utc::now());
+        let client = client(&server, &clock);
+        let now = chrono::Utc::now();
+        let opts = SearchOptions::new().start_time(now).end_time(now);
+
+        let pages: Vec<_> = client.search_all_paged("rust", &opts).collect().await;
+        assert_eq!(pages.len(), 1);
+        assert!(matches!(pages[0], Err(Error::InvalidRequest(_))));
+    }
+
+    fn tweets_page(ids: &[&str], next_token: Option<&str>) -> ResponseTemplate {
+        let data: Vec<_> = ids
+            .iter()
+            .map(|id| serde_json::json!({"id": id, "text": format!("tweet {}", id)}))
+            .collect();
+        let mut meta = serde_json::json!({"result_count": ids.len()});
+        if let Some(token) = next_token {
+            meta["next_token"] = serde_json::json!(token);
+        }
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({"data": data, "meta": meta}))
+    }
+
+    #[tokio::test]
+    async fn test_search_all_stream_waits_out_rate_limit_and_tracks_resume_token() {
+        let start = chrono::DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let reset = start + chrono::Duration::seconds(30);
+        let clock = MockClock::new(start);
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/tweets/search/all"))
+            .and(query_param_is_missing("next_token"))
+            .respond_with(tweets_page(&["1", "2"], Some("p2")))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/2/tweets/search/all"))
+            .and(query_param("next_token", "p2"))
+            .respond_with(
+                ResponseTemplate::new(429)
+                    .insert_header("x-rate-limit-limit", "300")
+                    .insert_header("x-rate-limit-remaining", "0")
+                    .insert_header("x-rate-limit-reset", reset.timestamp().to_string()),
+            )
+            .up_to_n_times(1)
+            .with_priority(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/2/tweets/search/all"))
+            .and(query_param("next_token", "p2"))
+            .respond_with(tweets_page(&["3", "4"], Some("p3")))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/2/tweets/search/all"))
+            .and(query_param("next_token", "p3"))
+            .respond_with(tweets_page(&["5"], None))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = client(&server, &clock);
+        let mut tweets = client.search_all_stream("rust", &SearchOptions::new());
+
+        let mut ids = Vec::new();
+        for _ in 0..3 {
+            ids.push(tweets.next().await.unwrap().unwrap().id);
+        }
+        // Interrupted in the middle of the second page
+        assert_eq!(tweets.resume_token().as_deref(), Some("p2"));
+
+        while let Some(tweet) = tweets.next().await {
+            ids.push(tweet.unwrap().id);
+        }
+        assert_eq!(ids, vec!["1", "2", "3", "4", "5"]);
+        assert_eq!(tweets.resume_token(), None);
+
+        // Paced after page 1, waited for the reset, then paced after page 2
+        assert_eq!(
+            clock.sleeps(),
+            vec![
+                FULL_ARCHIVE_PAGE_INTERVAL,
+                Duration::from_secs(29),
+                FULL_ARCHIVE_PAGE_INTERVAL
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_search_all_stream_resumes_from_token() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/tweets/search/all"))
+            .and(query_param("next_token", "p3"))
+            .respond_with(tweets_page(&["5"], None))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let clock = MockClock::new(chrono::Utc::now());
+        let client = client(&server, &clock);
+        let opts = SearchOptions::new().next_token("p3");
+        let tweets: Vec<Tweet> = client
+            .search_all_stream("rust", &opts)
+            .collect::<Result<_>>()
+            .await
+            .unwrap();
+        assert_eq!(tweets.len(), 1);
+        assert_eq!(tweets[0].id, "5");
+    }
+}
@@ -1 +1,367 @@
 //! Spaces operations
+
+use crate::builder::query::{Fields, FieldsBuilder, IDS_PARAM, MAX_LOOKUP_IDS, SpaceSearchOptions};
+use crate::client::{Client, HttpClient};
+use crate::endpoints::paths;
+use crate::error::{Error, Result};
+use crate::models::common::{ApiResponse, Includes, SpaceId};
+use crate::models::space::{Space, SpaceState};
+use reqwest::Method;
+
+/// Space fields requested by [`Client::live_spaces`]
+const LIVE_SPACE_FIELDS: [&str; 2] = ["participant_count", "title"];
+
+impl<H: HttpClient + Clone> Client<H> {
+    /// Get a Space by ID
+    ///
+    /// Only `id` and `state` are returned by default; request other
+    /// attributes with `space.fields`. Users referenced through the
+    /// `creator_id`, `host_ids`, `speaker_ids` and `invited_user_ids`
+    /// expansions are returned in `includes` (see
+    /// [`Space::hosts_in`] and friends).
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use x_api_client::builder::query::FieldsBuilder;
+    ///
+    /// let fields = FieldsBuilder::new()
+    ///     .space_fields(["host_ids", "participant_count", "title"])
+    ///     .expansions(["host_ids"])
+    ///     .user_fields(["username"])
+    ///     .build()?;
+    /// let response = client.get_space("1DXxyRYNejbKM", Some(&fields)).await?;
+    /// ```
+    pub async fn get_space(
+        &self,
+        space_id: impl Into<SpaceId>,
+        fields: Option<&Fields>,
+    ) -> Result<ApiResponse<Space>> {
+        let id = space_id.into();
+        let query = fields.map(Fields::to_query).unwrap_or_default();
+        self.send(Method::GET, &paths::space(&id), &query, None, Some(&id))
+            .await
+    }
+
+    /// Get Spaces by ID, in batches of up to 100 IDs
+    ///
+    /// Duplicate IDs are looked up once, and IDs are split into one request
+    /// per [`MAX_LOOKUP_IDS`] distinct IDs. The Spaces of every batch are
+    /// returned together, in the order of `ids`, along with the combined
+    /// `includes`. Spaces end and disappear quickly, so IDs the API cannot
+    /// find are reported in `errors` rather than failing the lookup.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidRequest`] if no IDs are given, or the error of
+    /// the first batch request that fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let response = client.get_spaces_chunked(space_ids, None).await?;
+    /// for error in response.errors.iter().flatten() {
+    ///     println!("gone: {:?}", error.resource_id);
+    /// }
+    /// ```
+    pub async fn get_spaces_chunked<I, S>(
+        &self,
+        ids: I,
+        fields: Option<&Fields>,
+    ) -> Result<ApiResponse<Vec<Space>>>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<SpaceId>,
+    {
+        let mut unique: Vec<SpaceId> = Vec::new();
+        for id in ids.into_iter().map(Into::into) {
+            if !unique.contains(&id) {
+                unique.push(id);
+            }
+        }
+        if unique.is_empty() {
+            return Err(Error::InvalidRequest(
+                "At least one ID is required for a lookup".to_string(),
+            ));
+        }
+
+        let mut spaces: Vec<Space> = Vec::with_capacity(unique.len());
+        let mut includes: Option<Includes> = None;
+        let mut errors = Vec::new();
+        for batch in unique.chunks(MAX_LOOKUP_IDS) {
+            let mut query = vec![(IDS_PARAM.to_string(), batch.join(","))];
+            query.extend(fields.map(Fields::to_query).unwrap_or_default());
+            let response = self
+                .send::<Vec<Space>>(Method::GET, paths::SPACES, &query, None, None)
+                .await?;
+            spaces.extend(response.data.unwrap_or_default());
+            errors.extend(response.errors.unwrap_or_default());
+            if let Some(batch_includes) = response.includes {
+                match &mut includes {
+                    Some(includes) => merge_includes(includes, batch_includes),
+                    None => includes = Some(batch_includes),
+                }
+            }
+        }
+
+        spaces.sort_by_key(|space| unique.iter().position(|id| *id == space.id));
+        Ok(ApiResponse {
+            data: Some(spaces),
+            includes,
+            meta: None,
+            errors: (!errors.is_empty()).then_some(errors),
+        })
+    }
+
+    /// Search Spaces by title
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use x_api_client::builder::query::SpaceSearchOptions;
+    /// use x_api_client::models::space::SpaceState;
+    ///
+    /// let spaces = client
+    ///     .search_spaces("rust", &SpaceSearchOptions::new().state(SpaceState::Scheduled))
+    ///     .await?;
+    /// ```
+    pub async fn search_spaces(
+        &self,
+        query: &str,
+        opts: &SpaceSearchOptions,
+    ) -> Result<Vec<Space>> {
+        opts.validate()?;
+        let response = self
+            .send::<Vec<Space>>(
+                Method::GET,
+                paths::SPACES_SEARCH,
+                &opts.to_query(query),
+                None,
+                None,
+            )
+            .await?;
+        Ok(response.data.unwrap_or_default())
+    }
+
+    /// Search live Spaces with at least `min_participants` participants
+    ///
+    /// Requests `participant_count` and drops Spaces below the threshold (or
+    /// without a reported count) client-side.
+    pub async fn live_spaces(&self, query: &str, min_participants: u32) -> Result<Vec<Space>> {
+        let opts = SpaceSearchOptions::new().state(SpaceState::Live).fields(
+            FieldsBuilder::new()
+                .space_fields(LIVE_SPACE_FIELDS)
+                .build()?,
+        );
+        let spaces = self.search_spaces(query, &opts).await?;
+        Ok(spaces
+            .into_iter()
+            .filter(|space| {
+                space.state == SpaceState::Live
+                    && space
+                        .participant_count
+                        .is_some_and(|count| count >= min_participants)
+            })
+            .collect())
+    }
+}
+
+/// Append the objects of `other` to `target`
+///
+/// Space topics have no typed field and arrive in `additional_fields`.
+fn merge_includes(target: &mut Includes, other: Includes) {
+    extend_included(&mut target.users, other.users);
+    extend_included(&mut target.tweets, other.tweets);
+    extend_included(&mut target.media, other.media);
+    extend_included(&mut target.places, other.places);
+    extend_included(&mut target.polls, other.polls);
+    for (key, value) in other.additional_fields {
+        match (target.additional_fields.get_mut(&key), value) {
+            (Some(serde_json::Value::Array(existing)), serde_json::Value::Array(more)) => {
+                existing.extend(more)
+            }
+            (_, value) => {
+                target.additional_fields.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Append one typed list of included objects to another
+fn extend_included<T>(target: &mut Option<Vec<T>>, other: Option<Vec<T>>) {
+    if let Some(other) = other {
+        target.get_or_insert_with(Vec::new).extend(other);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::bearer::BearerTokenProvider;
+    use std::sync::Arc;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_get_spaces_chunked_splits_batches_and_keeps_errors() {
+        let server = MockServer::start().await;
+        let ids: Vec<String> = (1..=120).map(|id| format!("space{}", id)).collect();
+        for batch in ids.chunks(MAX_LOOKUP_IDS) {
+            // The first Space of each batch has ended; the rest come back reversed
+            let (ended, live) = batch.split_first().unwrap();
+            let data: Vec<_> = live
+                .iter()
+                .rev()
+                .map(|id| serde_json::json!({"id": id, "state": "live"}))
+                .collect();
+            Mock::given(method("GET"))
+                .and(path("/2/spaces"))
+                .and(query_param("ids", batch.join(",")))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "data": data,
+                    "errors": [{
+                        "resource_id": ended,
+                        "title": "Not Found Error",
+                        "detail": format!("Could not find space with ids: [{}].", ended)
+                    }]
+                })))
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+
+        let client = Client::builder()
+            .auth(Arc::new(BearerTokenProvider::new("test")))
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+        let response = client.get_spaces_chunked(ids.clone(), None).await.unwrap();
+
+        let returned: Vec<&str> = response
+            .data
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|s| s.id.as_str())
+            .collect();
+        let expected: Vec<&str> = ids
+            .iter()
+            .map(String::as_str)
+            .filter(|id| *id != "space1" && *id != "space101")
+            .collect();
+        assert_eq!(returned, expected);
+        let missing: Vec<_> = response
+            .errors
+            .iter()
+            .flatten()
+            .filter_map(|e| e.resource_id.as_deref())
+            .collect();
+        assert_eq!(missing, vec!["space1", "space101"]);
+    }
+
+    #[test]
+    fn test_merge_includes_keeps_every_field() {
+        let mut target: Includes = serde_json::from_value(serde_json::json!({
+            "users": [{"id": "1", "name": "Host", "username": "host"}],
+            "topics": [{"id": "10", "name": "Rust"}]
+        }))
+        .unwrap();
+        let other: Includes = serde_json::from_value(serde_json::json!({
+            "users": [{"id": "2", "name": "Speaker", "username": "speaker"}],
+            "tweets": [{"id": "20", "text": "pinned"}],
+            "places": [{"id": "30"}],
+            "polls": [{"id": "40", "options": []}],
+            "topics": [{"id": "11", "name": "Zig"}]
+        }))
+        .unwrap();
+
+        merge_includes(&mut target, other);
+
+        assert!(target.user("1").is_some() && target.user("2").is_some());
+        assert!(target.tweet("20").is_some());
+        assert_eq!(target.places.as_ref().map(Vec::len), Some(1));
+        assert_eq!(target.polls.as_ref().map(Vec::len), Some(1));
+        assert_eq!(
+            target.additional_fields["topics"].as_array().map(Vec::len),
+            Some(2)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_live_spaces_filters_state_and_participants() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/spaces/search"))
+            .and(query_param("query", "rust"))
+            .and(query_param("state", "live"))
+            .and(query_param("space.fields", "participant_count,title"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [
+                    {"id": "1", "state": "live", "participant_count": 120},
+                    {"id": "2", "state": "live", "participant_count": 3},
+                    {"id": "3", "state": "scheduled", "participant_count": 500},
+                    {"id": "4", "state": "live"},
+                    {"id": "5", "state": "live", "participant_count": 10}
+                ]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = Client::builder()
+            .auth(Arc::new(BearerTokenProvider::new("test")))
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+        let spaces = client.live_spaces("rust", 10).await.unwrap();
+
+        let ids: Vec<_> = spaces.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, vec!["1", "5"]);
+    }
+
+    #[tokio::test]
+    async fn test_get_space_requests_fields_and_includes_hosts() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/spaces/1DXxyRYNejbKM"))
+            .and(query_param("expansions", "host_ids"))
+            .and(query_param("space.fields", "host_ids,participant_count"))
+            .and(query_param("user.fields", "username"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {
+                    "id": "1DXxyRYNejbKM",
+                    "state": "live",
+                    "host_ids": ["2244994945"],
+                    "participant_count": 42
+                },
+                "includes": {
+                    "users": [{"id": "2244994945", "name": "X Dev", "username": "XDevelopers"}]
+                }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = Client::builder()
+            .auth(Arc::new(BearerTokenProvider::new("test")))
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+        let fields = FieldsBuilder::new()
+            .space_fields(["host_ids", "participant_count"])
+            .expansions(["host_ids"])
+            .user_fields(["username"])
+            .build()
+            .unwrap();
+        let response = client
+            .get_space("1DXxyRYNejbKM", Some(&fields))
+            .await
+            .unwrap();
+
+        let space = response.data.as_ref().unwrap();
+        assert_eq!(space.participant_count, Some(42));
+        let hosts = space.hosts_in(response.includes.as_ref().unwrap());
+        assert_eq!(hosts.len(), 1);
+        assert_eq!(hosts[0].username, "XDevelopers");
+    }
+}
@@ -1 +1,914 @@
 //! Tweet operations
+
+use crate::builder::query::{
+    Fields, FieldsBuilder, IDS_PARAM, LookupOptions, MAX_LOOKUP_IDS, SOURCE_FIELD,
+};
+use crate::builder::request::{Body, TweetRequest};
+use crate::client::{Client, HttpClient};
+use crate::endpoints::paths;
+use crate::error::{Error, Result};
+use crate::models::common::{ApiResponse, Poll, ReplySettings, TweetId};
+use crate::models::tweet::{HideReplyResponse, Tweet, TweetAnalytics};
+use reqwest::Method;
+use std::collections::HashSet;
+use tokio_stream::Stream;
+
+/// Expansion including a tweet's poll
+const POLL_EXPANSION: &str = "attachments.poll_ids";
+
+/// Poll fields requested by [`Client::get_poll`]
+const POLL_FIELDS: [&str; 3] = ["duration_minutes", "end_datetime", "voting_status"];
+
+/// Tweet fields requested by [`Client::my_tweet_analytics`]
+const ANALYTICS_TWEET_FIELDS: [&str; 3] =
+    ["public_metrics", "non_public_metrics", "organic_metrics"];
+
+impl<H: HttpClient + Clone> Client<H> {
+    /// Reply to a tweet
+    ///
+    /// Shorthand for posting a [`TweetRequest`] built with
+    /// [`reply_to`](crate::builder::request::TweetRequestBuilder::reply_to).
+    ///
+    /// # Arguments
+    ///
+    /// * `tweet_id` - The tweet being replied to
+    /// * `text` - The reply text
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let reply = client.reply_to_tweet("1234567890", "Thanks!").await?;
+    /// ```
+    pub async fn reply_to_tweet(
+        &self,
+        tweet_id: impl Into<TweetId>,
+        text: impl Into<String>,
+    ) -> Result<Tweet> {
+        let request = TweetRequest::builder()
+            .text(text)
+            .reply_to(tweet_id)
+            .build()?;
+        self.post_tweet(request).await
+    }
+
+    /// Reply to a tweet, restricting who can reply to the reply
+    ///
+    /// Like [`reply_to_tweet`](Self::reply_to_tweet), but also sets
+    /// `reply_settings` on the posted reply.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use x_api_client::models::common::ReplySettings;
+    ///
+    /// let reply = client
+    ///     .reply_in_conversation("1234567890", "Following up", ReplySettings::Following)
+    ///     .await?;
+    /// ```
+    pub async fn reply_in_conversation(
+        &self,
+        tweet_id: impl Into<TweetId>,
+        text: impl Into<String>,
+        reply_settings: ReplySettings,
+    ) -> Result<Tweet> {
+        let request = TweetRequest::builder()
+            .text(text)
+            .reply_to(tweet_id)
+            .reply_settings(reply_settings)
+            .build()?;
+        self.post_tweet(request).await
+    }
+
+    /// Get a tweet by ID together with its expansions
+    ///
+    /// Unlike [`get_tweet`](Self::get_tweet), this keeps the `includes` of
+    /// the response, so objects requested through `fields` (author, media,
+    /// referenced tweets) can be looked up with
+    /// [`ApiResponse::author`], [`ApiResponse::media`] and
+    /// [`ApiResponse::referenced_tweet`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use x_api_client::builder::query::FieldsBuilder;
+    ///
+    /// let fields = FieldsBuilder::new()
+    ///     .expansions(["author_id"])
+    ///     .user_fields(["username"])
+    ///     .build()?;
+    /// let response = client.get_tweet_with_includes("1234567890", Some(&fields)).await?;
+    /// if let Some(author) = response.author() {
+    ///     println!("by @{}", author.username);
+    /// }
+    /// ```
+    pub async fn get_tweet_with_includes(
+        &self,
+        tweet_id: impl Into<TweetId>,
+        fields: Option<&Fields>,
+    ) -> Result<ApiResponse<Tweet>> {
+        let id = tweet_id.into();
+        let query = fields.map(Fields::to_query).unwrap_or_default();
+        self.send(Method::GET, &paths::tweet(&id), &query, None, Some(&id))
+            .await
+    }
+
+    /// Get the current state of the poll attached to a tweet
+    ///
+    /// Returns `None` if the tweet has no poll. Vote counts are final once
+    /// [`Poll::is_closed`] is true.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// if let Some(poll) = client.get_poll("1199786642791452673").await? {
+    ///     if poll.is_closed() {
+    ///         println!("winner: {:?}", poll.winning_option().map(|o| &o.label));
+    ///     }
+    /// }
+    /// ```
+    pub async fn get_poll(&self, tweet_id: impl Into<TweetId>) -> Result<Option<Poll>> {
+        let fields = FieldsBuilder::new()
+            .expansions([POLL_EXPANSION])
+            .poll_fields(POLL_FIELDS)
+            .build()?;
+        let response = self
+            .get_tweet_with_includes(tweet_id, Some(&fields))
+            .await?;
+        Ok(response.poll().cloned())
+    }
+
+    /// Get the name of the app a tweet was posted with
+    ///
+    /// Requests `tweet.fields=source` and returns [`Tweet::posted_via`]. The
+    /// field is deprecated and often empty on newer tweets, which gives
+    /// `None`.
+    pub async fn get_tweet_source(&self, tweet_id: impl Into<TweetId>) -> Result<Option<String>> {
+        let fields = FieldsBuilder::new().tweet_fields([SOURCE_FIELD]).build()?;
+        let response = self
+            .get_tweet_with_includes(tweet_id, Some(&fields))
+            .await?;
+        Ok(response
+            .data
+            .as_ref()
+            .and_then(Tweet::posted_via)
+            .map(str::to_string))
+    }
+
+    /// Get up to 100 tweets by ID in one request
+    ///
+    /// Tweets that do not exist or are not visible are left out (or are
+    /// `None` with [`LookupOptions::preserve_input_order`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidRequest`] if no IDs or more than
+    /// [`MAX_LOOKUP_IDS`](crate::builder::query::MAX_LOOKUP_IDS) distinct IDs
+    /// are given.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use x_api_client::builder::query::LookupOptions;
+    ///
+    /// let tweets = client
+    ///     .get_tweets(["1", "1", "2"], &LookupOptions::new().preserve_input_order(true))
+    ///     .await?;
+    /// assert_eq!(tweets.len(), 3);
+    /// ```
+    pub async fn get_tweets<I, S>(&self, ids: I, opts: &LookupOptions) -> Result<Vec<Option<Tweet>>>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<TweetId>,
+    {
+        let ids: Vec<TweetId> = ids.into_iter().map(Into::into).collect();
+        self.get_by_ids(paths::TWEETS, &ids, opts, |tweet: &Tweet| &tweet.id)
+            .await
+    }
+
+    /// Hide or unhide a reply to one of my conversations
+    ///
+    /// Hidden replies stay public but are moved behind a "hidden replies"
+    /// link. Only the author of the conversation's root tweet can hide
+    /// replies to it.
+    ///
+    /// # Arguments
+    ///
+    /// * `tweet_id` - The reply to hide or unhide
+    /// * `hidden` - `true` to hide the reply, `false` to unhide it
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let response = client.hide_reply("1234567890", true).await?;
+    /// assert!(response.hidden);
+    /// ```
+    pub async fn hide_reply(
+        &self,
+        tweet_id: impl Into<TweetId>,
+        hidden: bool,
+    ) -> Result<HideReplyResponse> {
+        let id = tweet_id.into();
+        let body = Body::json(&serde_json::json!({ "hidden": hidden }))?;
+        self.send(
+            Method::PUT,
+            &paths::tweet_hidden(&id),
+            &[],
+            Some(&body),
+            Some(&id),
+        )
+        .await?
+        .into_data()
+    }
+
+    /// Stream the tweets with the given IDs, looked up in batches
+    ///
+    /// `ids` is consumed lazily in batches of up to
+    /// [`MAX_LOOKUP_IDS`](crate::builder::query::MAX_LOOKUP_IDS) distinct IDs,
+    /// one lookup request per batch. Tweets are yielded as each batch
+    /// returns, in the order the API lists them. Rate limits are waited out
+    /// like for any other request.
+    ///
+    /// Tweets the API reports as unavailable (deleted, suspended, protected)
+    /// are skipped; their partial errors are logged at `WARN` level.
+    ///
+    /// # Errors
+    ///
+    /// Yields an error and stops if a lookup fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use tokio_stream::StreamExt;
+    ///
+    /// let mut tweets = std::pin::pin!(client.hydrate_tweets(ids));
+    /// while let Some(tweet) = tweets.next().await {
+    ///     println!("{}", tweet?.text);
+    /// }
+    /// ```
+    pub fn hydrate_tweets<'a, I>(&'a self, ids: I) -> impl Stream<Item = Result<Tweet>> + 'a
+    where
+        I: IntoIterator<Item = String>,
+        I::IntoIter: 'a,
+    {
+        let mut ids = ids.into_iter();
+        async_stream::try_stream! {
+            loop {
+                let mut batch: Vec<String> = Vec::with_capacity(MAX_LOOKUP_IDS);
+                while batch.len() < MAX_LOOKUP_IDS {
+                    let Some(id) = ids.next() else { break };
+                    if !batch.contains(&id) {
+                        batch.push(id);
+                    }
+                }
+                if batch.is_empty() {
+                    break;
+                }
+
+                let query = [(IDS_PARAM.to_string(), batch.join(","))];
+                let response: ApiResponse<Vec<Tweet>> = self
+                    .send(Method::GET, paths::TWEETS, &query, None, None)
+                    .await?;
+                for error in response.errors.iter().flatten() {
+                    tracing::warn!(
+                        resource_id = error.resource_id.as_deref().or(error.value.as_deref()),
+                        "Skipping unavailable tweet: {}",
+                        error.message
+                    );
+                }
+                for tweet in response.data.unwrap_or_default() {
+                    yield tweet;
+                }
+            }
+        }
+    }
+
+    /// Get the public, non-public and organic metrics of one of my tweets
+    ///
+    /// Looks up the authenticated user with [`me`](Self::me) and checks that
+    /// they wrote `tweet_id` before requesting the private metrics, which the
+    /// API only serves to the author.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Authorization`] if the tweet was written by another
+    /// user, or an error if any of the lookups fail.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let analytics = client.my_tweet_analytics("1234567890").await?;
+    /// if let Some(metrics) = analytics.non_public_metrics {
+    ///     println!("{:?} impressions", metrics.impression_count);
+    /// }
+    /// ```
+    pub async fn my_tweet_analytics(&self, tweet_id: impl Into<TweetId>) -> Result<TweetAnalytics> {
+        let id = tweet_id.into();
+        let me = self.me().await?;
+
+        let author_fields = FieldsBuilder::new().tweet_fields(["author_id"]).build()?;
+        let tweet = self
+            .get_tweet_with_includes(id.clone(), Some(&author_fields))
+            .await?
+            .into_data()?;
+        match tweet.author_id.as_deref() {
+            Some(author_id) if author_id == me.id => {}
+            Some(author_id) => {
+                return Err(Error::Authorization(format!(
+                    "Tweet {} was written by user {}, not the authenticated user {}; \
+                     private metrics are only available to the author",
+                    id, author_id, me.id
+                )));
+            }
+            None => {
+                return Err(Error::InvalidResponse(format!(
+                    "Tweet {} lookup did not include author_id",
+                    id
+                )));
+            }
+        }
+
+        let metrics_fields = FieldsBuilder::new()
+            .tweet_fields(ANALYTICS_TWEET_FIELDS)
+            .build()?;
+        let tweet = self
+            .get_tweet_with_includes(id, Some(&metrics_fields))
+            .await?
+            .into_data()?;
+        Ok(TweetAnalytics {
+            tweet_id: tweet.id,
+            public_metrics: tweet.public_metrics,
+            non_public_metrics: tweet.non_public_metrics,
+            organic_metrics: tweet.organic_metrics,
+        })
+    }
+
+    /// Get every version of a tweet, oldest first
+    ///
+    /// Looks up `tweet_id` (any version of the tweet), then fetches all the
+    /// versions listed in its `edit_history_tweet_ids` in one request.
+    /// Versions the API reports as unavailable (e.g. deleted) are left out.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the lookups fail, or
+    /// [`Error::InvalidResponse`] if a version is neither returned nor
+    /// reported as unavailable.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let versions = client.edit_history("1234567890").await?;
+    /// for pair in versions.windows(2) {
+    ///     println!("{:?} -> {:?}", pair[0].text, pair[1].text);
+    /// }
+    /// ```
+    pub async fn edit_history(&self, tweet_id: impl Into<TweetId>) -> Result<Vec<Tweet>> {
+        let tweet = self.get_tweet(tweet_id).await?;
+        if tweet.edit_history_tweet_ids.len() <= 1 {
+            return Ok(vec![tweet]);
+        }
+
+        let ids = tweet.edit_history_tweet_ids;
+        let query = [(IDS_PARAM.to_string(), ids.join(","))];
+        let response: ApiResponse<Vec<Tweet>> = self
+            .send(Method::GET, paths::TWEETS, &query, None, None)
+            .await?;
+
+        let unavailable: HashSet<String> = response
+            .errors
+            .into_iter()
+            .flatten()
+            .filter_map(|error| error.resource_id.or(error.value))
+            .collect();
+        let mut found = response.data.unwrap_or_default();
+
+        let mut versions = Vec::with_capacity(ids.len());
+        for id in &ids {
+            if let Some(pos) = found.iter().position(|t| &t.id == id) {
+                versions.push(found.swap_remove(pos));
+            } else if !unavailable.contains(id) {
+                return Err(Error::InvalidResponse(format!(
+                    "Edit history version {} missing from lookup response",
+                    id
+                )));
+            }
+        }
+        Ok(versions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oauth1_request as oauth;
+    use tokio_stream::StreamExt;
+    use wiremock::matchers::{body_json, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn created(id: &str, text: &str) -> ResponseTemplate {
+        ResponseTemplate::new(201).set_body_json(serde_json::json!({
+            "data": {"id": id, "text": text, "edit_history_tweet_ids": [id]}
+        }))
+    }
+
+    fn client(server: &MockServer) -> Client {
+        Client::builder()
+            .oauth1("ck", "cs", "at", "ats")
+            .base_url(server.uri())
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_reply_to_tweet_sends_nested_reply() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/2/tweets"))
+            .and(body_json(serde_json::json!({
+                "text": "Thanks!",
+                "reply": {"in_reply_to_tweet_id": "1234567890"}
+            })))
+            .respond_with(created("1", "Thanks!"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let tweet = client(&server)
+            .reply_to_tweet("1234567890", "Thanks!")
+            .await
+            .unwrap();
+        assert_eq!(tweet.id, "1");
+    }
+
+    #[tokio::test]
+    async fn test_post_tweet_sends_nested_media() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/2/tweets"))
+            .and(body_json(serde_json::json!({
+                "text": "Look",
+                "media": {"media_ids": ["1455952740635586573"]}
+            })))
+            .respond_with(created("3", "Look"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let request = TweetRequest::builder()
+            .text("Look")
+            .media(vec!["1455952740635586573".to_string()])
+            .build()
+            .unwrap();
+        client(&server).post_tweet(request).await.unwrap();
+
+        let sent = &server.received_requests().await.unwrap()[0];
+        let body: serde_json::Value = serde_json::from_slice(&sent.body).unwrap();
+        assert!(body.get("media_ids").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_reply_in_conversation_sets_reply_settings() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/2/tweets"))
+            .and(body_json(serde_json::json!({
+                "text": "Following up",
+                "reply": {"in_reply_to_tweet_id": "1234567890"},
+                "reply_settings": "following"
+            })))
+            .respond_with(created("2", "Following up"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let tweet = client(&server)
+            .reply_in_conversation("1234567890", "Following up", ReplySettings::Following)
+            .await
+            .unwrap();
+        assert_eq!(tweet.id, "2");
+    }
+
+    #[tokio::test]
+    async fn test_get_tweet_with_includes_keeps_author() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/tweets/20"))
+            .and(query_param("expansions", "author_id"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {
+                    "id": "20",
+                    "text": "hello",
+                    "author_id": "12",
+                    "edit_history_tweet_ids": ["20"]
+                },
+                "includes": {
+                    "users": [{"id": "12", "name": "Jack", "username": "jack"}]
+                }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let fields = FieldsBuilder::new()
+            .expansions(["author_id"])
+            .build()
+            .unwrap();
+        let response = client(&server)
+            .get_tweet_with_includes("20", Some(&fields))
+            .await
+            .unwrap();
+
+        assert_eq!(response.data.as_ref().unwrap().text, "hello");
+        assert_eq!(response.author().unwrap().username, "jack");
+        assert!(response.media().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_get_tweets_preserves_input_order_with_duplicates() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/tweets"))
+            .and(query_param("ids", "1,2,3"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [
+                    {"id": "2", "text": "two", "edit_history_tweet_ids": ["2"]},
+                    {"id": "1", "text": "one", "edit_history_tweet_ids": ["1"]}
+                ]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let tweets = client(&server)
+            .get_tweets(
+                ["1", "1", "2", "3"],
+                &LookupOptions::new().preserve_input_order(true),
+            )
+            .await
+            .unwrap();
+
+        let texts: Vec<_> = tweets
+            .iter()
+            .map(|t| t.as_ref().map(|t| t.text.as_str()))
+            .collect();
+        assert_eq!(texts, vec![Some("one"), Some("one"), Some("two"), None]);
+    }
+
+    #[tokio::test]
+    async fn test_hide_reply_sends_hidden_state() {
+        let server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/2/tweets/42/hidden"))
+            .and(body_json(serde_json::json!({"hidden": true})))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"data": {"hidden": true}})),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let response = client(&server).hide_reply("42", true).await.unwrap();
+        assert!(response.hidden);
+    }
+
+    #[tokio::test]
+    async fn test_hydrate_tweets_batches_and_skips_missing() {
+        let server = MockServer::start().await;
+        let ids: Vec<String> = (1..=150).map(|id| id.to_string()).collect();
+        for batch in ids.chunks(MAX_LOOKUP_IDS) {
+            // The last ID of each batch was deleted
+            let (missing, found) = batch.split_last().unwrap();
+            let data: Vec<_> = found
+                .iter()
+                .map(|id| serde_json::json!({"id": id, "text": id, "edit_history_tweet_ids": [id]}))
+                .collect();
+            Mock::given(method("GET"))
+                .and(path("/2/tweets"))
+                .and(query_param("ids", batch.join(",")))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "data": data,
+                    "errors": [{
+                        "resource_id": missing,
+                        "title": "Not Found Error",
+                        "detail": format!("Could not find tweet with ids: [{}].", missing)
+                    }]
+                })))
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+
+        let client = client(&server);
+        let tweets: Vec<Tweet> = client
+            .hydrate_tweets(ids.clone())
+            .collect::<Result<_>>()
+            .await
+            .unwrap();
+
+        let streamed: Vec<&str> = tweets.iter().map(|t| t.id.as_str()).collect();
+        let expected: Vec<&str> = ids
+            .iter()
+            .map(String::as_str)
+            .filter(|id| *id != "100" && *id != "150")
+            .collect();
+        assert_eq!(streamed, expected);
+    }
+
+    /// OAuth 1.0a header for a GET of `url` with the nonce and timestamp of `header`
+    fn resign(header: &str, url: &reqwest::Url) -> String {
+        let param = |name: &str| {
+            let start = header.find(&format!("{}=\"", name)).unwrap() + name.len() + 2;
+            header[start..].split('"').next().unwrap().to_string()
+        };
+        let nonce = param("oauth_nonce");
+        let timestamp: u64 = param("oauth_timestamp").parse().unwrap();
+        let mut uri = url.clone();
+        uri.set_query(None);
+        let params: Vec<(String, String)> = url
+            .query_pairs()
+            .map(|(k, v)| (k.into_owned(), v.into_owned()))
+            .collect();
+
+        let token = oauth::Token::from_parts("ck", "cs", "at", "ats");
+        let mut signer = oauth::Builder::with_token(token.as_ref(), oauth::HMAC_SHA1);
+        signer
+            .version(true)
+            .nonce(nonce.as_str())
+            .timestamp(std::num::NonZeroU64::new(timestamp));
+        signer.authorize("GET", uri.as_str(), &oauth::ParameterList::new(params))
+    }
+
+    #[tokio::test]
+    async fn test_extra_query_sent_and_signed() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/tweets"))
+            .and(query_param("ids", "1"))
+            .and(query_param("x_experiment", "on"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [{"id": "1", "text": "one", "edit_history_tweet_ids": ["1"]}]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let opts = LookupOptions::new().extra_query("x_experiment", "on");
+        client(&server).get_tweets(["1"], &opts).await.unwrap();
+
+        let requests = server.received_requests().await.unwrap();
+        let header = requests[0]
+            .headers
+            .get(reqwest::header::AUTHORIZATION)
+            .unwrap()
+            .to_str()
+            .unwrap();
+        // The recorded URL does not keep the port the request was signed for
+        let mut signed = reqwest::Url::parse(&server.uri()).unwrap();
+        signed.set_path(requests[0].url.path());
+        signed.set_query(requests[0].url.query());
+        assert_eq!(header, resign(header, &signed));
+
+        let mut unsigned = signed.clone();
+        unsigned.set_query(Some("ids=1"));
+        assert_ne!(header, resign(header, &unsigned));
+    }
+
+    #[tokio::test]
+    async fn test_source_requested_and_exposed() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/tweets"))
+            .and(query_param("tweet.fields", "created_at,source"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [
+                    {"id": "1", "text": "old", "source": "Twitter for iPhone"},
+                    {"id": "2", "text": "new", "source": ""}
+                ]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/2/tweets/1"))
+            .and(query_param("tweet.fields", "source"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {"id": "1", "text": "old", "source": "Twitter for iPhone"}
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = client(&server);
+        let fields = FieldsBuilder::new()
+            .tweet_fields(["created_at"])
+            .build()
+            .unwrap();
+        let opts = LookupOptions::new().fields(fields).with_source();
+        let tweets = client.get_tweets(["1", "2"], &opts).await.unwrap();
+        let sources: Vec<_> = tweets
+            .iter()
+            .map(|t| t.as_ref().and_then(Tweet::posted_via))
+            .collect();
+        assert_eq!(sources, vec![Some("Twitter for iPhone"), None]);
+
+        assert_eq!(
+            client.get_tweet_source("1").await.unwrap().as_deref(),
+            Some("Twitter for iPhone")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_tweets_rejects_too_many_ids() {
+        let ids: Vec<String> = (0..=crate::builder::query::MAX_LOOKUP_IDS)
+            .map(|i| i.to_string())
+            .collect();
+        let client = Client::builder()
+            .oauth1("ck", "cs", "at", "ats")
+            .build()
+            .unwrap();
+
+        let err = client
+            .get_tweets(ids, &LookupOptions::new())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidRequest(_)));
+    }
+
+    #[tokio::test]
+    async fn test_edit_history_skips_deleted_version() {
+        let server = MockServer::start().await;
+        let history = ["100", "101", "102"];
+        Mock::given(method("GET"))
+            .and(path("/2/tweets/102"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {"id": "102", "text": "v3", "edit_history_tweet_ids": history}
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/2/tweets"))
+            .and(query_param("ids", "100,101,102"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [
+                    {"id": "102", "text": "v3", "edit_history_tweet_ids": history},
+                    {"id": "100", "text": "v1", "edit_history_tweet_ids": history}
+                ],
+                "errors": [{
+                    "value": "101",
+                    "detail": "Could not find tweet with ids: [101].",
+                    "title": "Not Found Error",
+                    "resource_type": "tweet",
+                    "parameter": "ids",
+                    "resource_id": "101",
+                    "type": "https://api.twitter.com/2/problems/resource-not-found"
+                }]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let versions = client(&server).edit_history("102").await.unwrap();
+        let texts: Vec<_> = versions.iter().map(|t| t.text.as_str()).collect();
+        assert_eq!(texts, vec!["v1", "v3"]);
+    }
+
+    #[tokio::test]
+    async fn test_edit_history_of_unedited_tweet() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/tweets/5"))
+            .respond_with(created("5", "only"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let versions = client(&server).edit_history("5").await.unwrap();
+        assert_eq!(versions.len(), 1);
+        assert_eq!(versions[0].id, "5");
+    }
+
+    async fn mount_me(server: &MockServer, user_id: &str) {
+        Mock::given(method("GET"))
+            .and(path("/2/users/me"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {"id": user_id, "name": "Me", "username": "me"}
+            })))
+            .mount(server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_my_tweet_analytics_rejects_other_users_tweet() {
+        let server = MockServer::start().await;
+        mount_me(&server, "1").await;
+        Mock::given(method("GET"))
+            .and(path("/2/tweets/30"))
+            .and(query_param("tweet.fields", "author_id"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {"id": "30", "text": "not mine", "author_id": "2", "edit_history_tweet_ids": ["30"]}
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/2/tweets/30"))
+            .and(query_param(
+                "tweet.fields",
+                "public_metrics,non_public_metrics,organic_metrics",
+            ))
+            .respond_with(ResponseTemplate::new(403))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let err = client(&server).my_tweet_analytics("30").await.unwrap_err();
+        match err {
+            Error::Authorization(msg) => {
+                assert!(msg.contains("Tweet 30"), "{}", msg);
+                assert!(msg.contains("user 2"), "{}", msg);
+            }
+            other => panic!("expected Authorization, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_my_tweet_analytics_combines_metrics() {
+        let server = MockServer::start().await;
+        mount_me(&server, "1").await;
+        Mock::given(method("GET"))
+            .and(path("/2/tweets/31"))
+            .and(query_param("tweet.fields", "author_id"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {"id": "31", "text": "mine", "author_id": "1", "edit_history_tweet_ids": ["31"]}
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/2/tweets/31"))
+            .and(query_param(
+                "tweet.fields",
+                "public_metrics,non_public_metrics,organic_metrics",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {
+                    "id": "31",
+                    "text": "mine",
+                    "edit_history_tweet_ids": ["31"],
+                    "public_metrics": {"like_count": 3},
+                    "non_public_metrics": {"impression_count": 120, "url_link_clicks": 4},
+                    "organic_metrics": {"impression_count": 100}
+                }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let analytics = client(&server).my_tweet_analytics("31").await.unwrap();
+        assert_eq!(analytics.tweet_id, "31");
+        assert_eq!(analytics.public_metrics.unwrap().like_count, Some(3));
+        let non_public = analytics.non_public_metrics.unwrap();
+        assert_eq!(non_public.impression_count, Some(120));
+        assert_eq!(non_public.additional_fields["url_link_clicks"], 4);
+        assert_eq!(
+            analytics.organic_metrics.unwrap().impression_count,
+            Some(100)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_poll_reads_expanded_poll() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/tweets/40"))
+            .and(query_param("expansions", "attachments.poll_ids"))
+            .and(query_param(
+                "poll.fields",
+                "duration_minutes,end_datetime,voting_status",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {
+                    "id": "40",
+                    "text": "Which?",
+                    "edit_history_tweet_ids": ["40"],
+                    "attachments": {"poll_ids": ["7"]}
+                },
+                "includes": {"polls": [{
+                    "id": "7",
+                    "voting_status": "closed",
+                    "options": [
+                        {"position": 1, "label": "tabs", "votes": 3},
+                        {"position": 2, "label": "spaces", "votes": 5}
+                    ]
+                }]}
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let poll = client(&server).get_poll("40").await.unwrap().unwrap();
+        assert!(poll.is_closed());
+        assert_eq!(poll.winning_option().unwrap().label, "spaces");
+    }
+}
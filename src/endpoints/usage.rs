@@ -0,0 +1,304 @@
+//! Project usage and the monthly cap guard
+//!
+//! Tweets consumed through the API count against a project's monthly cap;
+//! going over it is billed as overage. A [`UsageGuard`] installed with
+//! [`Client::usage_guard`] checks the usage reported by X and refuses
+//! writes once consumption reaches a threshold, while reads keep working.
+
+use crate::auth::AuthProvider;
+use crate::client::{Client, HttpClient};
+use crate::endpoints::paths;
+use crate::error::{Error, Result};
+use crate::models::common::ApiResponse;
+use crate::models::usage::TweetUsage;
+use crate::util::lock_unpoisoned;
+use chrono::{DateTime, Utc};
+use reqwest::Method;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Default share of the cap above which writes are refused
+const DEFAULT_THRESHOLD: f64 = 0.95;
+
+/// Default time a usage reading is trusted before checking again
+const DEFAULT_CHECK_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// Monthly cap guard configuration
+///
+/// Writes are refused with [`Error::UsageCapReached`] once the project's
+/// usage reaches `threshold` times its cap. Usage is checked again when a
+/// write is attempted more than `check_interval` after the last check.
+///
+/// # Example
+///
+/// ```rust
+/// use x_api_client::endpoints::usage::UsageGuard;
+/// use std::time::Duration;
+///
+/// let guard = UsageGuard::custom()
+///     .threshold(0.9)
+///     .check_interval(Duration::from_secs(60))
+///     .build()?;
+/// assert_eq!(guard.threshold(), 0.9);
+/// # Ok::<(), x_api_client::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct UsageGuard {
+    /// Share of the cap above which writes are refused (default: 0.95)
+    threshold: f64,
+
+    /// Time a usage reading is trusted (default: 5 minutes)
+    check_interval: Duration,
+}
+
+impl Default for UsageGuard {
+    fn default() -> Self {
+        Self {
+            threshold: DEFAULT_THRESHOLD,
+            check_interval: DEFAULT_CHECK_INTERVAL,
+        }
+    }
+}
+
+impl UsageGuard {
+    /// Create a guard with default values
+    ///
+    /// Defaults:
+    /// - threshold: 0.95 (95% of the cap)
+    /// - check_interval: 5 minutes
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a custom guard with builder pattern
+    pub fn custom() -> UsageGuardBuilder {
+        UsageGuardBuilder::default()
+    }
+
+    /// Get the share of the cap above which writes are refused
+    pub fn threshold(&self) -> f64 {
+        self.threshold
+    }
+
+    /// Get the time a usage reading is trusted
+    pub fn check_interval(&self) -> Duration {
+        self.check_interval
+    }
+}
+
+/// Builder for creating custom usage guards with validation
+#[derive(Debug, Default)]
+pub struct UsageGuardBuilder {
+    guard: UsageGuard,
+}
+
+impl UsageGuardBuilder {
+    /// Set the share of the cap above which writes are refused
+    pub fn threshold(mut self, threshold: f64) -> Self {
+        self.guard.threshold = threshold;
+        self
+    }
+
+    /// Set the time a usage reading is trusted before checking again
+    pub fn check_interval(mut self, interval: Duration) -> Self {
+        self.guard.check_interval = interval;
+        self
+    }
+
+    /// Build the usage guard
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - threshold is not in (0, 1]
+    /// - check_interval is zero
+    pub fn build(self) -> Result<UsageGuard> {
+        if !(self.guard.threshold > 0.0 && self.guard.threshold <= 1.0) {
+            return Err(Error::Config(
+                "Usage guard threshold must be greater than 0 and at most 1".to_string(),
+            ));
+        }
+
+        if self.guard.check_interval.is_zero() {
+            return Err(Error::Config(
+                "Usage guard check interval must be non-zero".to_string(),
+            ));
+        }
+
+        Ok(self.guard)
+    }
+}
+
+/// Last usage reported by X
+#[derive(Debug, Clone, Copy)]
+struct UsageReading {
+    checked_at: DateTime<Utc>,
+    usage: u64,
+    cap: u64,
+}
+
+/// A [`UsageGuard`] with its last reading, shared between client clones
+pub(crate) struct UsageGuardState {
+    guard: UsageGuard,
+
+    /// Provider used to fetch usage, which requires app-only authentication
+    auth: Arc<dyn AuthProvider>,
+
+    last: Mutex<Option<UsageReading>>,
+}
+
+impl UsageGuardState {
+    pub(crate) fn new(guard: UsageGuard, auth: Arc<dyn AuthProvider>) -> Self {
+        Self {
+            guard,
+            auth,
+            last: Mutex::new(None),
+        }
+    }
+
+    /// Fail with [`Error::UsageCapReached`] if a write would risk overage
+    ///
+    /// Fetches usage through `client` when the last reading is older than
+    /// the check interval. Failing to fetch usage fails the write.
+    pub(crate) async fn check<H: HttpClient + Clone>(&self, client: &Client<H>) -> Result<()> {
+        let now = client.clock().now();
+        let fresh = (*lock_unpoisoned(&self.last)).filter(|reading| {
+            (now - reading.checked_at)
+                .to_std()
+                .is_ok_and(|age| age < self.guard.check_interval)
+        });
+        let reading = match fresh {
+            Some(reading) => reading,
+            None => {
+                let usage = Box::pin(client.with_auth(self.auth.clone()).tweet_usage())
+                    .await?
+                    .data
+                    .ok_or_else(|| {
+                        Error::InvalidResponse("Usage response has no data".to_string())
+                    })?;
+                let reading = UsageReading {
+                    checked_at: now,
+                    usage: usage.project_usage,
+                    cap: usage.project_cap,
+                };
+                *lock_unpoisoned(&self.last) = Some(reading);
+                reading
+            }
+        };
+
+        if reading.usage as f64 >= reading.cap as f64 * self.guard.threshold {
+            tracing::warn!(
+                usage = reading.usage,
+                cap = reading.cap,
+                "Monthly tweet cap nearly reached, refusing write"
+            );
+            return Err(Error::UsageCapReached {
+                usage: reading.usage,
+                cap: reading.cap,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for UsageGuardState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UsageGuardState")
+            .field("guard", &self.guard)
+            .field("last", &*lock_unpoisoned(&self.last))
+            .finish()
+    }
+}
+
+impl<H: HttpClient + Clone> Client<H> {
+    /// Get the project's tweet usage for the current billing cycle
+    ///
+    /// Requires app-only authentication.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let usage = client.tweet_usage().await?.data.unwrap();
+    /// println!("{:.0}% of the monthly cap used", usage.usage_ratio() * 100.0);
+    /// ```
+    pub async fn tweet_usage(&self) -> Result<ApiResponse<TweetUsage>> {
+        self.send(Method::GET, paths::USAGE_TWEETS, &[], None, None)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::bearer::BearerTokenProvider;
+    use crate::builder::request::TweetRequest;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[test]
+    fn test_usage_guard_builder_validates() {
+        assert!(UsageGuard::custom().threshold(0.0).build().is_err());
+        assert!(UsageGuard::custom().threshold(1.5).build().is_err());
+        assert!(
+            UsageGuard::custom()
+                .check_interval(Duration::ZERO)
+                .build()
+                .is_err()
+        );
+        assert_eq!(UsageGuard::new().threshold(), DEFAULT_THRESHOLD);
+    }
+
+    #[tokio::test]
+    async fn test_writes_refused_above_threshold_reads_allowed() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/usage/tweets"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {"project_cap": "1000000", "project_usage": "960000", "cap_reset_day": 5}
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/2/tweets"))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(0)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/2/tweets/20"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"data": {"id": "20", "text": "hi"}})),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = Client::builder()
+            .oauth1("ck", "cs", "at", "ats")
+            .base_url(server.uri())
+            .build()
+            .unwrap()
+            .usage_guard(
+                UsageGuard::new(),
+                Arc::new(BearerTokenProvider::new("token")),
+            );
+
+        for _ in 0..2 {
+            let err = client
+                .post_tweet(TweetRequest::new("Hello"))
+                .await
+                .unwrap_err();
+            assert!(matches!(
+                err,
+                Error::UsageCapReached {
+                    usage: 960_000,
+                    cap: 1_000_000
+                }
+            ));
+        }
+        assert_eq!(client.get_tweet("20").await.unwrap().id, "20");
+    }
+}
@@ -1 +1,300 @@
 //! User operations
+
+use crate::builder::query::{Fields, LookupOptions};
+use crate::client::{Client, HttpClient};
+use crate::endpoints::paths;
+use crate::error::{Error, Result};
+use crate::models::common::{ApiResponse, UserId};
+use crate::models::user::User;
+use reqwest::Method;
+
+/// Public account looked up by [`Client::verify_credentials`] with app-only auth
+const APP_ONLY_PROBE_USERNAME: &str = "XDevelopers";
+
+/// HTTP status for rejected credentials
+const STATUS_UNAUTHORIZED: u16 = 401;
+
+/// HTTP status for credentials lacking access
+const STATUS_FORBIDDEN: u16 = 403;
+
+impl<H: HttpClient + Clone> Client<H> {
+    /// Get the authenticated user
+    ///
+    /// Requires user-context authentication.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let me = client.me().await?;
+    /// println!("Signed in as @{}", me.username);
+    /// ```
+    pub async fn me(&self) -> Result<User> {
+        self.send(Method::GET, paths::USERS_ME, &[], None, None)
+            .await?
+            .into_data()
+    }
+
+    /// Check that the configured credentials are accepted by the API
+    ///
+    /// A fast preflight before long-running jobs. With user-context auth this
+    /// returns the authenticated user (see [`me`](Self::me)). Providers that
+    /// do not support `/2/users/me` are treated as app-only and checked with
+    /// a lookup of the public `@XDevelopers` account, which is returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Authentication`] if the credentials are rejected
+    /// (HTTP 401), [`Error::Authorization`] if they lack access (HTTP 403),
+    /// or any other error from the request.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let user = client.verify_credentials().await?;
+    /// println!("Credentials OK for @{}", user.username);
+    /// ```
+    pub async fn verify_credentials(&self) -> Result<User> {
+        let result = if self.auth_supports(paths::USERS_ME) {
+            self.me().await
+        } else {
+            self.get_user_by_username(APP_ONLY_PROBE_USERNAME).await
+        };
+        result.map_err(credential_error)
+    }
+
+    /// Get up to 100 users by ID in one request
+    ///
+    /// Users that do not exist or are suspended are left out (or are `None`
+    /// with [`LookupOptions::preserve_input_order`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidRequest`](crate::Error::InvalidRequest) if no
+    /// IDs or more than
+    /// [`MAX_LOOKUP_IDS`](crate::builder::query::MAX_LOOKUP_IDS) distinct IDs
+    /// are given.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use x_api_client::builder::query::LookupOptions;
+    ///
+    /// let users = client.get_users(["12", "783214"], &LookupOptions::new()).await?;
+    /// ```
+    pub async fn get_users<I, S>(&self, ids: I, opts: &LookupOptions) -> Result<Vec<Option<User>>>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<UserId>,
+    {
+        let ids: Vec<UserId> = ids.into_iter().map(Into::into).collect();
+        self.get_by_ids(paths::USERS, &ids, opts, |user: &User| &user.id)
+            .await
+    }
+
+    /// Get a user by username, along with the objects requested by `fields`
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use x_api_client::builder::query::FieldsBuilder;
+    ///
+    /// let fields = FieldsBuilder::new()
+    ///     .expansions(["pinned_tweet_id"])
+    ///     .tweet_fields(["created_at"])
+    ///     .build()?;
+    /// let response = client.get_user_by_username_with_includes("jack", Some(&fields)).await?;
+    /// let user = response.data.as_ref().unwrap();
+    /// if let Some(pinned) = response.pinned_tweet_of(user) {
+    ///     println!("pinned: {}", pinned.text);
+    /// }
+    /// ```
+    pub async fn get_user_by_username_with_includes(
+        &self,
+        username: &str,
+        fields: Option<&Fields>,
+    ) -> Result<ApiResponse<User>> {
+        let query = fields.map(Fields::to_query).unwrap_or_default();
+        self.send(
+            Method::GET,
+            &paths::user_by_username(username),
+            &query,
+            None,
+            Some(username),
+        )
+        .await
+    }
+
+    /// Find the ID of the user with `username`
+    ///
+    /// A leading `@` is ignored, and usernames are matched case-insensitively.
+    /// With a [`username_cache`](crate::client::ClientBuilder::username_cache)
+    /// configured, IDs found within its TTL are returned without a request.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let id = client.resolve_username("@XDevelopers").await?;
+    /// ```
+    pub async fn resolve_username(&self, username: &str) -> Result<UserId> {
+        let username = username.strip_prefix('@').unwrap_or(username);
+        let key = username.to_lowercase();
+        if let Some(id) = self
+            .username_cache()
+            .and_then(|cache| cache.get(&key, self.clock().now()))
+        {
+            return Ok(id);
+        }
+
+        let id = self.get_user_by_username(username).await?.id;
+        if let Some(cache) = self.username_cache() {
+            cache.insert(key, id.clone(), self.clock().now());
+        }
+        Ok(id)
+    }
+}
+
+/// Map credential rejections to the matching auth error
+fn credential_error(error: Error) -> Error {
+    match &error {
+        Error::Api(detail) => match detail.status() {
+            Some(STATUS_UNAUTHORIZED) => {
+                Error::Authentication(format!("Credentials rejected: {}", detail.message()))
+            }
+            Some(STATUS_FORBIDDEN) => {
+                Error::Authorization(format!("Credentials lack access: {}", detail.message()))
+            }
+            _ => error,
+        },
+        _ => error,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn client(server: &MockServer) -> Client {
+        Client::builder()
+            .oauth1("ck", "cs", "at", "ats")
+            .base_url(server.uri())
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_verify_credentials_user_context() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/users/me"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {"id": "12", "name": "Jack", "username": "jack"}
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let user = client(&server).verify_credentials().await.unwrap();
+        assert_eq!(user.id, "12");
+    }
+
+    #[tokio::test]
+    async fn test_verify_credentials_maps_401_to_authentication() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/users/me"))
+            .respond_with(ResponseTemplate::new(401).set_body_json(serde_json::json!({
+                "title": "Unauthorized",
+                "type": "about:blank",
+                "status": 401,
+                "detail": "Unauthorized"
+            })))
+            .mount(&server)
+            .await;
+
+        let err = client(&server).verify_credentials().await.unwrap_err();
+        assert!(matches!(err, Error::Authentication(_)), "{:?}", err);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_username_cached_within_ttl() {
+        use crate::util::cache::CacheConfig;
+        use crate::util::clock::MockClock;
+        use std::sync::Arc;
+        use std::time::Duration;
+
+        const TTL: Duration = Duration::from_secs(600);
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/users/by/username/XDevelopers"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {"id": "2244994945", "name": "Developers", "username": "XDevelopers"}
+            })))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let clock = MockClock::new(chrono::Utc::now());
+        let client = Client::builder()
+            .oauth1("ck", "cs", "at", "ats")
+            .base_url(server.uri())
+            .clock(Arc::new(clock.clone()))
+            .username_cache(CacheConfig::custom().ttl(TTL).build().unwrap())
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            client.resolve_username("XDevelopers").await.unwrap(),
+            "2244994945"
+        );
+        // Served from the cache, whatever the spelling
+        assert_eq!(
+            client.resolve_username("@xdevelopers").await.unwrap(),
+            "2244994945"
+        );
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+
+        clock.advance(TTL);
+        client.resolve_username("XDevelopers").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_pinned_tweet_expansion() {
+        use crate::builder::query::FieldsBuilder;
+        use wiremock::matchers::query_param;
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/users/by/username/XDevelopers"))
+            .and(query_param("expansions", "pinned_tweet_id"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {
+                    "id": "2244994945",
+                    "name": "Developers",
+                    "username": "XDevelopers",
+                    "pinned_tweet_id": "1430984356139470849"
+                },
+                "includes": {"tweets": [
+                    {"id": "1430984356139470849", "text": "Pinned announcement"}
+                ]}
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let fields = FieldsBuilder::new()
+            .expansions(["pinned_tweet_id"])
+            .tweet_fields(["created_at"])
+            .build()
+            .unwrap();
+        let response = client(&server)
+            .get_user_by_username_with_includes("XDevelopers", Some(&fields))
+            .await
+            .unwrap();
+        let user = response.data.as_ref().unwrap();
+        let pinned = response.pinned_tweet_of(user).unwrap();
+        assert_eq!(pinned.text, "Pinned announcement");
+    }
+}
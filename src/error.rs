@@ -6,6 +6,8 @@
 //! - Retry classification helpers
 //! - Error conversion implementations
 
+use crate::streaming::notice::StreamDisconnectNotice;
+use crate::streaming::rules::RuleValidationError;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
@@ -39,6 +41,28 @@ pub enum Error {
     #[error("Authorization failed: {0}")]
     Authorization(String),
 
+    /// The OAuth 2.0 token lacks scopes the endpoint requires
+    ///
+    /// Either list is empty when the API response does not name the scopes.
+    #[error(
+        "Insufficient OAuth 2.0 scope: requires [{}], token has [{}]",
+        required.join(", "),
+        present.join(", ")
+    )]
+    InsufficientScope {
+        /// Scopes the endpoint requires
+        required: Vec<String>,
+        /// Scopes granted to the token
+        present: Vec<String>,
+    },
+
+    /// The account is locked until someone completes a challenge on X
+    ///
+    /// Retrying cannot succeed; a human has to log in and unlock the
+    /// account first.
+    #[error("Account locked: {0}")]
+    AccountLocked(String),
+
     /// Rate limit exceeded
     #[error("Rate limit exceeded for endpoint '{endpoint}', resets at {reset_at}")]
     RateLimitExceeded {
@@ -52,10 +76,26 @@ pub enum Error {
         limit: u32,
     },
 
+    /// A write was refused because the project's monthly tweet cap is
+    /// nearly consumed (see [`UsageGuard`](crate::endpoints::usage::UsageGuard))
+    #[error("Monthly tweet cap nearly reached: {usage} of {cap} consumed")]
+    UsageCapReached {
+        /// Tweets consumed in the current billing cycle
+        usage: u64,
+        /// Tweets allowed per billing cycle
+        cap: u64,
+    },
+
     /// Invalid request parameters
     #[error("Invalid request: {0}")]
     InvalidRequest(String),
 
+    /// Filtered stream rules rejected by the API, with per-rule diagnostics
+    ///
+    /// Boxed to keep Error size reasonable
+    #[error("{0}")]
+    RuleValidation(Box<RuleValidationError>),
+
     /// Resource not found (404)
     #[error("Resource not found: {0}")]
     NotFound(String),
@@ -68,6 +108,24 @@ pub enum Error {
     #[error("Stream disconnected: {0}")]
     StreamDisconnected(String),
 
+    /// The stream announced an operational disconnect
+    ///
+    /// Boxed to keep Error size reasonable
+    #[error("Stream disconnected by X: {0}")]
+    StreamDisconnectNotice(Box<StreamDisconnectNotice>),
+
+    /// A resumed stream could not backfill all of the downtime
+    ///
+    /// Messages posted between `last_seen` and `backfilled_from` may be
+    /// missing. Yielded once, before the first message; the stream continues.
+    #[error("Stream gap: messages between {last_seen} and {backfilled_from} may be missing")]
+    StreamGap {
+        /// Time of the last message seen before the restart
+        last_seen: DateTime<Utc>,
+        /// Earliest time covered by backfill
+        backfilled_from: DateTime<Utc>,
+    },
+
     /// Invalid response format from API
     #[error("Invalid response format: {0}")]
     InvalidResponse(String),
@@ -87,6 +145,13 @@ pub enum Error {
     /// Configuration error
     #[error("Configuration error: {0}")]
     Config(String),
+
+    /// A `reqwest-middleware` layer failed the request
+    ///
+    /// Only produced with the `reqwest-middleware` feature, but always
+    /// present so matches on [`Error`] do not depend on enabled features.
+    #[error("Middleware error: {0}")]
+    Middleware(#[source] Box<dyn std::error::Error + Send + Sync>),
 }
 
 /// Detailed error information from the X API
@@ -145,7 +210,7 @@ impl Error {
             Error::RateLimitExceeded { .. } => true,
 
             // Stream disconnections are retryable
-            Error::StreamDisconnected(_) => true,
+            Error::StreamDisconnected(_) | Error::StreamDisconnectNotice(_) => true,
 
             // Timeouts are retryable
             Error::Timeout(_) => true,
@@ -167,13 +232,21 @@ impl Error {
             // These are permanent errors
             Error::Authentication(_)
             | Error::Authorization(_)
+            | Error::InsufficientScope { .. }
+            | Error::AccountLocked(_)
+            | Error::UsageCapReached { .. }
             | Error::InvalidRequest(_)
+            | Error::RuleValidation(_)
             | Error::NotFound(_)
             | Error::OAuth(_)
             | Error::Config(_)
             | Error::Serialization(_)
             | Error::StreamConnection(_)
+            | Error::StreamGap { .. }
             | Error::Io(_) => false,
+
+            // Middleware failures are final; retrying is the middleware's call
+            Error::Middleware(_) => false,
         }
     }
 
@@ -185,9 +258,16 @@ impl Error {
     ///
     /// Returns `None` for errors without specific retry timing (use exponential backoff)
     pub fn retry_after(&self) -> Option<std::time::Duration> {
+        self.retry_after_at(Utc::now())
+    }
+
+    /// Get the duration to wait before retrying, relative to `now`
+    ///
+    /// Like [`retry_after`](Self::retry_after), but with an explicit current
+    /// time (see [`Clock`](crate::util::clock::Clock)).
+    pub fn retry_after_at(&self, now: DateTime<Utc>) -> Option<std::time::Duration> {
         match self {
             Error::RateLimitExceeded { reset_at, .. } => {
-                let now = Utc::now();
                 if *reset_at > now {
                     (*reset_at - now).to_std().ok()
                 } else {
@@ -200,6 +280,34 @@ impl Error {
         }
     }
 
+    /// Get the rate limit reset time as epoch seconds
+    ///
+    /// This is the value of the `x-rate-limit-reset` header, untranslated,
+    /// which helps when debugging clock skew between the client and the API.
+    /// Returns `None` for errors other than [`Error::RateLimitExceeded`].
+    pub fn reset_at_epoch(&self) -> Option<i64> {
+        match self {
+            // reset_at is built from whole header seconds, so this is lossless
+            Error::RateLimitExceeded { reset_at, .. } => Some(reset_at.timestamp()),
+            _ => None,
+        }
+    }
+
+    /// Check if this is a duplicate content error
+    ///
+    /// Returned when posting a tweet whose text is identical to a recent
+    /// tweet by the same account. Bots can treat this as "already posted".
+    pub fn is_duplicate(&self) -> bool {
+        matches!(self, Error::Api(detail) if detail.code == DUPLICATE_CONTENT_CODE)
+    }
+
+    /// Check if the account is locked pending a challenge
+    ///
+    /// Automation should alert a human rather than retry.
+    pub fn is_account_locked(&self) -> bool {
+        matches!(self, Error::AccountLocked(_))
+    }
+
     /// Check if this is a rate limit error
     pub fn is_rate_limit(&self) -> bool {
         matches!(self, Error::RateLimitExceeded { .. })
@@ -209,7 +317,11 @@ impl Error {
     pub fn is_auth_error(&self) -> bool {
         matches!(
             self,
-            Error::Authentication(_) | Error::Authorization(_) | Error::OAuth(_)
+            Error::Authentication(_)
+                | Error::Authorization(_)
+                | Error::InsufficientScope { .. }
+                | Error::AccountLocked(_)
+                | Error::OAuth(_)
         )
     }
 
@@ -316,6 +428,279 @@ impl ApiErrorDetail {
     pub fn status(&self) -> Option<u16> {
         self.status
     }
+
+    /// Get the problem category named by the type URI
+    ///
+    /// Returns `None` if there is no type URI or it names a category this
+    /// crate does not know about.
+    pub fn problem_type(&self) -> Option<ProblemType> {
+        self.type_uri.as_deref().and_then(ProblemType::from_uri)
+    }
+}
+
+/// Path segment preceding the problem name in X problem type URIs
+const PROBLEMS_SEGMENT: &str = "/problems/";
+
+/// Category of an X API problem, from the problem type URI
+///
+/// X identifies problem categories with URIs such as
+/// `https://api.twitter.com/2/problems/resource-not-found`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ProblemType {
+    /// `invalid-request`: malformed or invalid parameters
+    InvalidRequest,
+    /// `resource-not-found`: the requested resource does not exist
+    ResourceNotFound,
+    /// `resource-unavailable`: the resource exists but cannot be served
+    /// (e.g. a suspended account)
+    ResourceUnavailable,
+    /// `not-authorized-for-resource`: the resource is private to the caller
+    NotAuthorizedForResource,
+    /// `not-authorized-for-field`: a requested field is private to the caller
+    NotAuthorizedForField,
+    /// `client-forbidden`: the app is not allowed to use the endpoint
+    ClientForbidden,
+    /// `disallowed-resource`: the resource is blocked for the app
+    DisallowedResource,
+    /// `unsupported-authentication`: wrong authentication type for the endpoint
+    UnsupportedAuthentication,
+    /// `usage-capped`: the app's monthly usage cap is exhausted
+    UsageCapped,
+    /// `conflict`: the request conflicts with the resource's state
+    Conflict,
+    /// `duplicate-rules`: a stream rule already exists
+    DuplicateRules,
+    /// `rule-cap`: the stream rule cap is reached
+    RuleCap,
+    /// `invalid-rules`: a stream rule is malformed
+    InvalidRules,
+    /// `connection-exception`: the stream connection limit is reached
+    ConnectionException,
+    /// `client-disconnected`: the stream client fell too far behind
+    ClientDisconnected,
+    /// `operational-disconnect`: X closed the stream for operational reasons
+    OperationalDisconnect,
+}
+
+impl ProblemType {
+    /// Parse a problem type URI
+    ///
+    /// Only the name after `/problems/` is considered, so both
+    /// `api.twitter.com` and `api.x.com` URIs are recognized.
+    pub fn from_uri(uri: &str) -> Option<Self> {
+        let (_, name) = uri.rsplit_once(PROBLEMS_SEGMENT)?;
+        let problem = match name {
+            "invalid-request" => ProblemType::InvalidRequest,
+            "resource-not-found" => ProblemType::ResourceNotFound,
+            "resource-unavailable" => ProblemType::ResourceUnavailable,
+            "not-authorized-for-resource" => ProblemType::NotAuthorizedForResource,
+            "not-authorized-for-field" => ProblemType::NotAuthorizedForField,
+            "client-forbidden" => ProblemType::ClientForbidden,
+            "disallowed-resource" => ProblemType::DisallowedResource,
+            "unsupported-authentication" => ProblemType::UnsupportedAuthentication,
+            "usage-capped" => ProblemType::UsageCapped,
+            "conflict" => ProblemType::Conflict,
+            "duplicate-rules" => ProblemType::DuplicateRules,
+            "rule-cap" => ProblemType::RuleCap,
+            "invalid-rules" => ProblemType::InvalidRules,
+            "connection-exception" => ProblemType::ConnectionException,
+            "client-disconnected" => ProblemType::ClientDisconnected,
+            "operational-disconnect" => ProblemType::OperationalDisconnect,
+            _ => return None,
+        };
+        Some(problem)
+    }
+}
+
+/// Normalized error code for duplicate content errors
+///
+/// This is the code X has long used for "Status is a duplicate"; v2 problem
+/// documents describing duplicate content are mapped to it as well.
+pub const DUPLICATE_CONTENT_CODE: &str = "187";
+
+/// Phrase identifying duplicate content in v2 problem document details
+const DUPLICATE_CONTENT_PHRASE: &str = "duplicate content";
+
+/// Phrases marking an OAuth 2.0 insufficient-scope failure (lowercase)
+const INSUFFICIENT_SCOPE_MARKERS: &[&str] = &[
+    "insufficient_scope",
+    "insufficient-scope",
+    "insufficient scope",
+    "required scope",
+    "missing scope",
+];
+
+/// Labels introducing the scopes an endpoint requires (lowercase)
+const REQUIRED_SCOPE_LABELS: &[&str] = &["required scopes:", "required scope:"];
+
+/// Labels introducing the scopes a token was granted (lowercase)
+const PRESENT_SCOPE_LABELS: &[&str] = &[
+    "token scopes:",
+    "token scope:",
+    "present scopes:",
+    "granted scopes:",
+];
+
+/// Error code X returns for accounts locked pending a challenge
+pub const ACCOUNT_LOCKED_CODE: &str = "326";
+
+/// Phrases marking a locked account in 403 responses (lowercase)
+const ACCOUNT_LOCKED_MARKERS: &[&str] = &["temporarily locked", "account is locked"];
+
+/// HTTP status of insufficient-scope and account-lock failures
+const STATUS_FORBIDDEN: u16 = 403;
+
+/// Error response body as returned by the X API
+///
+/// Covers v2 problem documents (`title`, `detail`, `type`, `status`), the
+/// `errors` array, and flat `{code, message}` objects.
+#[derive(Debug, Deserialize)]
+struct ErrorBody {
+    title: Option<String>,
+    detail: Option<String>,
+    #[serde(rename = "type")]
+    type_uri: Option<String>,
+    code: Option<serde_json::Value>,
+    message: Option<String>,
+    #[serde(default)]
+    errors: Vec<ErrorEntry>,
+}
+
+/// Single entry of an `errors` array
+#[derive(Debug, Deserialize)]
+struct ErrorEntry {
+    title: Option<String>,
+    detail: Option<String>,
+    #[serde(rename = "type")]
+    type_uri: Option<String>,
+    code: Option<serde_json::Value>,
+    message: Option<String>,
+    parameter: Option<String>,
+    value: Option<serde_json::Value>,
+}
+
+/// Parse an error response body into an [`Error`]
+///
+/// Top-level problem fields take precedence over the first entry of the
+/// `errors` array. Known error conditions are normalized (see
+/// [`DUPLICATE_CONTENT_CODE`]). Bodies that are not a JSON error object are
+/// kept verbatim as the message.
+pub(crate) fn parse_api_error(status: u16, body: &str) -> Error {
+    let Ok(parsed) = serde_json::from_str::<ErrorBody>(body) else {
+        return ApiErrorDetail::new(status.to_string(), body)
+            .with_status(status)
+            .into();
+    };
+    let first = parsed.errors.into_iter().next();
+
+    let code = parsed
+        .code
+        .or_else(|| first.as_ref().and_then(|e| e.code.clone()))
+        .map(|code| json_to_string(&code))
+        .or_else(|| parsed.title.clone())
+        .or_else(|| first.as_ref().and_then(|e| e.title.clone()))
+        .unwrap_or_else(|| status.to_string());
+    let message = parsed
+        .detail
+        .or(parsed.message)
+        .or_else(|| first.as_ref().and_then(|e| e.message.clone()))
+        .or_else(|| first.as_ref().and_then(|e| e.detail.clone()))
+        .or(parsed.title)
+        .unwrap_or_else(|| body.to_string());
+
+    let is_duplicate =
+        code == DUPLICATE_CONTENT_CODE || message.to_lowercase().contains(DUPLICATE_CONTENT_PHRASE);
+    let code = if is_duplicate {
+        DUPLICATE_CONTENT_CODE.to_string()
+    } else {
+        code
+    };
+
+    if is_account_locked(status, &code, &message) {
+        return Error::AccountLocked(message);
+    }
+
+    let type_uri = parsed
+        .type_uri
+        .or_else(|| first.as_ref().and_then(|e| e.type_uri.clone()));
+    if status == STATUS_FORBIDDEN {
+        let described = format!("{} {} {}", code, message, type_uri.as_deref().unwrap_or(""));
+        if let Some(error) = insufficient_scope(&described) {
+            return error;
+        }
+    }
+
+    let mut detail = ApiErrorDetail::new(code, message).with_status(status);
+    if let Some(type_uri) = type_uri {
+        detail = detail.with_type_uri(type_uri);
+    }
+    if let Some(first) = first {
+        if let Some(parameter) = first.parameter {
+            detail = detail.with_parameter(parameter);
+        }
+        if let Some(value) = first.value {
+            detail = detail.with_value(json_to_string(&value));
+        }
+    }
+    detail.into()
+}
+
+/// Whether a response reports a locked account, by code or by a 403 message
+fn is_account_locked(status: u16, code: &str, message: &str) -> bool {
+    if code == ACCOUNT_LOCKED_CODE {
+        return true;
+    }
+    let lower = message.to_lowercase();
+    status == STATUS_FORBIDDEN
+        && ACCOUNT_LOCKED_MARKERS
+            .iter()
+            .any(|marker| lower.contains(marker))
+}
+
+/// Build an [`Error::InsufficientScope`] if `text` describes a missing scope
+///
+/// Scopes are read from lists such as `Missing required scopes: tweet.write,
+/// users.read` and `Token scopes: tweet.read`.
+fn insufficient_scope(text: &str) -> Option<Error> {
+    let lower = text.to_lowercase();
+    if !INSUFFICIENT_SCOPE_MARKERS
+        .iter()
+        .any(|marker| lower.contains(marker))
+    {
+        return None;
+    }
+    Some(Error::InsufficientScope {
+        required: scopes_after(text, &lower, REQUIRED_SCOPE_LABELS),
+        present: scopes_after(text, &lower, PRESENT_SCOPE_LABELS),
+    })
+}
+
+/// Scopes listed after the first of `labels` found in `lower` (the lowercase
+/// `text`), up to the end of the sentence
+fn scopes_after(text: &str, lower: &str, labels: &[&str]) -> Vec<String> {
+    let Some(start) = labels
+        .iter()
+        .find_map(|label| lower.find(label).map(|at| at + label.len()))
+    else {
+        return Vec::new();
+    };
+    let list = &text[start..];
+    // Scope names contain dots, so a sentence ends at ". " or the end
+    let list = list.split(". ").next().unwrap_or(list);
+    list.split([',', ' ', '[', ']', ';'])
+        .map(|scope| scope.trim_end_matches('.'))
+        .filter(|scope| !scope.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Render a JSON scalar without quotes (codes and values may be numbers or strings)
+fn json_to_string(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
 }
 
 /// Result type alias for this crate
@@ -326,6 +711,127 @@ mod tests {
     use super::*;
     use chrono::Duration;
 
+    #[test]
+    fn test_parse_duplicate_content_problem() {
+        let body = r#"{
+            "detail": "You are not allowed to create a Tweet with duplicate content.",
+            "type": "about:blank",
+            "title": "Forbidden",
+            "status": 403
+        }"#;
+
+        let err = parse_api_error(403, body);
+        assert!(err.is_duplicate());
+        assert!(err.is_client_error());
+        match err {
+            Error::Api(detail) => {
+                assert_eq!(detail.code(), DUPLICATE_CONTENT_CODE);
+                assert_eq!(
+                    detail.message(),
+                    "You are not allowed to create a Tweet with duplicate content."
+                );
+                assert_eq!(detail.type_uri(), Some("about:blank"));
+                assert_eq!(detail.status(), Some(403));
+            }
+            other => panic!("Expected Api error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_legacy_duplicate_code() {
+        let body = r#"{"errors": [{"code": 187, "message": "Status is a duplicate."}]}"#;
+        assert!(parse_api_error(403, body).is_duplicate());
+    }
+
+    #[test]
+    fn test_parse_errors_array() {
+        let body = r#"{
+            "errors": [{
+                "parameters": {"ids": ["abc"]},
+                "parameter": "ids",
+                "value": "abc",
+                "message": "The `ids` query parameter value [abc] is not valid"
+            }],
+            "title": "Invalid Request",
+            "detail": "One or more parameters to your request was invalid.",
+            "type": "https://api.twitter.com/2/problems/invalid-request"
+        }"#;
+
+        let err = parse_api_error(400, body);
+        assert!(!err.is_duplicate());
+        match err {
+            Error::Api(detail) => {
+                assert_eq!(detail.code(), "Invalid Request");
+                assert_eq!(
+                    detail.message(),
+                    "One or more parameters to your request was invalid."
+                );
+                assert_eq!(detail.parameter(), Some("ids"));
+                assert_eq!(detail.value(), Some("abc"));
+                assert_eq!(
+                    detail.type_uri(),
+                    Some("https://api.twitter.com/2/problems/invalid-request")
+                );
+            }
+            other => panic!("Expected Api error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_insufficient_scope_problem() {
+        let body = r#"{
+            "title": "Forbidden",
+            "detail": "Missing required scopes: tweet.write, users.read. Token scopes: tweet.read, offline.access.",
+            "type": "about:blank",
+            "status": 403
+        }"#;
+
+        let err = parse_api_error(403, body);
+        assert!(err.is_auth_error());
+        assert!(!err.is_retryable());
+        match err {
+            Error::InsufficientScope { required, present } => {
+                assert_eq!(required, vec!["tweet.write", "users.read"]);
+                assert_eq!(present, vec!["tweet.read", "offline.access"]);
+            }
+            other => panic!("Expected InsufficientScope, got {:?}", other),
+        }
+
+        // Other 403s stay API errors
+        let forbidden = r#"{"title": "Forbidden", "detail": "Forbidden", "status": 403}"#;
+        assert!(matches!(parse_api_error(403, forbidden), Error::Api(_)));
+    }
+
+    #[test]
+    fn test_parse_account_locked() {
+        let body = r#"{"errors": [{
+            "code": 326,
+            "message": "To protect our users from spam and other malicious activity, this account is temporarily locked. Please log in to https://x.com to unlock your account."
+        }]}"#;
+
+        let err = parse_api_error(403, body);
+        assert!(err.is_account_locked());
+        assert!(!err.is_retryable());
+
+        let problem = r#"{"title": "Forbidden", "detail": "This account is temporarily locked.", "status": 403}"#;
+        assert!(parse_api_error(403, problem).is_account_locked());
+
+        let forbidden = r#"{"title": "Forbidden", "detail": "Forbidden", "status": 403}"#;
+        assert!(!parse_api_error(403, forbidden).is_account_locked());
+    }
+
+    #[test]
+    fn test_parse_non_json_error_body() {
+        match parse_api_error(502, "<html>Bad Gateway</html>") {
+            Error::Api(detail) => {
+                assert_eq!(detail.code(), "502");
+                assert_eq!(detail.message(), "<html>Bad Gateway</html>");
+                assert_eq!(detail.status(), Some(502));
+            }
+            other => panic!("Expected Api error, got {:?}", other),
+        }
+    }
+
     #[test]
     fn test_error_io_is_retryable() {
         let err = Error::Io(std::io::Error::new(
@@ -494,4 +1000,36 @@ mod tests {
         let err = Error::InvalidResponse("Malformed JSON".to_string());
         assert!(!err.is_retryable());
     }
+
+    #[test]
+    fn test_problem_type_from_uri() {
+        let cases = [
+            (
+                "https://api.twitter.com/2/problems/invalid-request",
+                ProblemType::InvalidRequest,
+            ),
+            (
+                "https://api.twitter.com/2/problems/resource-not-found",
+                ProblemType::ResourceNotFound,
+            ),
+            (
+                "https://api.x.com/2/problems/usage-capped",
+                ProblemType::UsageCapped,
+            ),
+            (
+                "https://api.twitter.com/2/problems/not-authorized-for-resource",
+                ProblemType::NotAuthorizedForResource,
+            ),
+        ];
+        for (uri, expected) in cases {
+            let detail = ApiErrorDetail::new("400", "problem").with_type_uri(uri);
+            assert_eq!(detail.problem_type(), Some(expected), "{}", uri);
+        }
+
+        let unknown = ApiErrorDetail::new("400", "problem")
+            .with_type_uri("https://api.twitter.com/2/problems/brand-new-problem");
+        assert_eq!(unknown.problem_type(), None);
+        assert_eq!(ProblemType::from_uri("about:blank"), None);
+        assert_eq!(ApiErrorDetail::new("400", "problem").problem_type(), None);
+    }
 }
@@ -25,9 +25,12 @@
 
 pub mod auth;
 pub mod builder;
+pub mod cassette;
 pub mod client;
 pub mod endpoints;
 pub mod error;
+#[cfg(feature = "reqwest-middleware")]
+pub mod middleware;
 pub mod models;
 pub mod pagination;
 pub mod rate_limit;
@@ -0,0 +1,135 @@
+//! `reqwest-middleware` integration
+//!
+//! Available with the `reqwest-middleware` feature. [`MiddlewareClient`]
+//! wraps a [`ClientWithMiddleware`] so that middleware layered on it (tracing,
+//! caching, custom retries) sees every request the client sends, including
+//! the already-authenticated headers.
+
+use crate::Client;
+use crate::client::{ClientBuilder, HttpClient};
+use crate::error::{Error, Result};
+use reqwest_middleware::ClientWithMiddleware;
+
+/// HTTP client that routes requests through a `reqwest-middleware` stack
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use x_api_client::client::ClientBuilder;
+/// use x_api_client::middleware::MiddlewareClient;
+///
+/// let http = reqwest_middleware::ClientBuilder::new(reqwest::Client::new())
+///     .with(my_tracing_middleware)
+///     .build();
+///
+/// let client = ClientBuilder::with_http(MiddlewareClient::new(http))
+///     .oauth1("ck", "cs", "at", "ats")
+///     .build()?;
+/// ```
+#[derive(Clone)]
+pub struct MiddlewareClient {
+    client: ClientWithMiddleware,
+}
+
+impl MiddlewareClient {
+    /// Wrap a client with its middleware stack
+    pub fn new(client: ClientWithMiddleware) -> Self {
+        Self { client }
+    }
+
+    /// Get a reference to the underlying middleware client
+    pub fn inner(&self) -> &ClientWithMiddleware {
+        &self.client
+    }
+}
+
+impl HttpClient for MiddlewareClient {
+    async fn execute(&self, request: reqwest::Request) -> Result<reqwest::Response> {
+        self.client.execute(request).await.map_err(Error::from)
+    }
+}
+
+impl From<reqwest_middleware::Error> for Error {
+    fn from(error: reqwest_middleware::Error) -> Self {
+        match error {
+            reqwest_middleware::Error::Reqwest(e) => Error::Network(e),
+            reqwest_middleware::Error::Middleware(e) => Error::Middleware(e.into()),
+        }
+    }
+}
+
+impl ClientBuilder<MiddlewareClient> {
+    /// Build the Client
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no authentication provider or HTTP client is
+    /// configured. Timeouts are configured on the wrapped `reqwest::Client`.
+    pub fn build(self) -> Result<Client<MiddlewareClient>> {
+        self.build_with_custom_http()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest_middleware::{Middleware, Next};
+    use std::sync::{Arc, Mutex};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    /// Records the method, path, and auth header of every request it sees
+    #[derive(Clone, Default)]
+    struct Recorder {
+        seen: Arc<Mutex<Vec<(String, String, bool)>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl Middleware for Recorder {
+        async fn handle(
+            &self,
+            req: reqwest::Request,
+            extensions: &mut http::Extensions,
+            next: Next<'_>,
+        ) -> reqwest_middleware::Result<reqwest::Response> {
+            self.seen.lock().unwrap().push((
+                req.method().to_string(),
+                req.url().path().to_string(),
+                req.headers().contains_key(reqwest::header::AUTHORIZATION),
+            ));
+            next.run(req, extensions).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_middleware_observes_request() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/tweets/123"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": {"id": "123", "text": "hello", "edit_history_tweet_ids": ["123"]}
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let recorder = Recorder::default();
+        let http = reqwest_middleware::ClientBuilder::new(reqwest::Client::new())
+            .with(recorder.clone())
+            .build();
+        let client = ClientBuilder::with_http(MiddlewareClient::new(http))
+            .oauth1("ck", "cs", "at", "ats")
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+
+        let tweet = client.get_tweet("123").await.unwrap();
+        assert_eq!(tweet.id, "123");
+
+        let seen = recorder.seen.lock().unwrap();
+        assert_eq!(
+            *seen,
+            vec![("GET".to_string(), "/2/tweets/123".to_string(), true)]
+        );
+    }
+}
@@ -16,6 +16,7 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fmt;
 
 /// Unique identifier for a Tweet (64-bit integer represented as string)
 ///
@@ -37,6 +38,9 @@ pub type ListId = String;
 /// Unique identifier for a filtered stream Rule (alphanumeric string)
 pub type RuleId = String;
 
+/// Unique identifier for a direct message event (64-bit integer represented as string)
+pub type DmEventId = String;
+
 /// Unique identifier for a conversation thread (64-bit integer represented as string)
 pub type ConversationId = String;
 
@@ -60,6 +64,19 @@ pub struct ApiResponse<T> {
     pub errors: Option<Vec<ApiError>>,
 }
 
+impl<T> ApiResponse<T> {
+    /// Extract the primary data, failing if the response carried none
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidResponse` if `data` is missing.
+    pub fn into_data(self) -> crate::error::Result<T> {
+        self.data.ok_or_else(|| {
+            crate::error::Error::InvalidResponse("No data in API response".to_string())
+        })
+    }
+}
+
 /// Expanded objects included in responses
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(default)]
@@ -84,6 +101,36 @@ pub struct Includes {
     pub additional_fields: HashMap<String, serde_json::Value>,
 }
 
+impl Includes {
+    /// Find an included user by ID
+    pub fn user(&self, id: &str) -> Option<&crate::models::user::User> {
+        self.users.as_deref()?.iter().find(|u| u.id == id)
+    }
+
+    /// Find an included tweet by ID
+    pub fn tweet(&self, id: &str) -> Option<&crate::models::tweet::Tweet> {
+        self.tweets.as_deref()?.iter().find(|t| t.id == id)
+    }
+
+    /// Find included media by media key
+    pub fn media(&self, media_key: &str) -> Option<&crate::models::media::Media> {
+        self.media
+            .as_deref()?
+            .iter()
+            .find(|m| m.media_key == media_key)
+    }
+
+    /// Find an included place by ID
+    pub fn place(&self, id: &str) -> Option<&Place> {
+        self.places.as_deref()?.iter().find(|p| p.id == id)
+    }
+
+    /// Find an included poll by ID
+    pub fn poll(&self, id: &str) -> Option<&Poll> {
+        self.polls.as_deref()?.iter().find(|p| p.id == id)
+    }
+}
+
 /// Pagination and response metadata
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(default)]
@@ -110,14 +157,19 @@ pub struct ResponseMeta {
 
 /// API error detail
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "ApiErrorFields")]
 pub struct ApiError {
     /// Error code (CAPS_CASE)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub code: Option<String>,
 
-    /// Human-readable message
+    /// Human-readable message, or `detail` if the error has no `message`
     pub message: String,
 
+    /// Problem detail, as sent in problem-style partial errors
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+
     /// Problematic parameter
     #[serde(skip_serializing_if = "Option::is_none")]
     pub parameter: Option<String>,
@@ -126,8 +178,12 @@ pub struct ApiError {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub value: Option<String>,
 
-    /// Problem type URI
+    /// ID of the resource the error concerns (e.g. a tweet that was deleted)
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource_id: Option<String>,
+
+    /// Problem type URI
+    #[serde(alias = "type", skip_serializing_if = "Option::is_none")]
     pub type_uri: Option<String>,
 
     /// Forward compatibility: capture unknown fields
@@ -135,6 +191,39 @@ pub struct ApiError {
     pub additional_fields: HashMap<String, serde_json::Value>,
 }
 
+/// [`ApiError`] as sent, with `message` and `detail` both optional
+#[derive(Deserialize)]
+struct ApiErrorFields {
+    code: Option<String>,
+    message: Option<String>,
+    detail: Option<String>,
+    parameter: Option<String>,
+    value: Option<String>,
+    resource_id: Option<String>,
+    #[serde(alias = "type")]
+    type_uri: Option<String>,
+    #[serde(flatten)]
+    additional_fields: HashMap<String, serde_json::Value>,
+}
+
+impl From<ApiErrorFields> for ApiError {
+    fn from(fields: ApiErrorFields) -> Self {
+        Self {
+            code: fields.code,
+            message: fields
+                .message
+                .or_else(|| fields.detail.clone())
+                .unwrap_or_default(),
+            detail: fields.detail,
+            parameter: fields.parameter,
+            value: fields.value,
+            resource_id: fields.resource_id,
+            type_uri: fields.type_uri,
+            additional_fields: fields.additional_fields,
+        }
+    }
+}
+
 /// Geographic place information
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(default)]
@@ -156,8 +245,65 @@ pub struct Place {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub place_type: Option<String>,
 
+    /// GeoJSON feature describing the place's area
+    ///
+    /// Requested with `geo` in [`place_fields`](crate::builder::query::FieldsBuilder::place_fields).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub geo: Option<PlaceGeo>,
+
+    /// Forward compatibility: capture unknown fields
+    #[serde(
+        flatten,
+        deserialize_with = "crate::util::serde_helpers::deserialize_unknown_fields"
+    )]
+    pub additional_fields: HashMap<String, serde_json::Value>,
+}
+
+impl Place {
+    /// Bounding box of the place as `[west, south, east, north]`
+    ///
+    /// Longitudes and latitudes are in degrees, in GeoJSON order. Returns
+    /// `None` if `geo` was not requested or carries no four-value bbox.
+    pub fn bounding_box(&self) -> Option<[f64; 4]> {
+        self.geo.as_ref()?.bbox.as_deref()?.try_into().ok()
+    }
+
+    /// Center of the bounding box as `[longitude, latitude]`
+    ///
+    /// Boxes crossing the antimeridian (west greater than east) are centered
+    /// across it rather than around the globe.
+    pub fn centroid(&self) -> Option<[f64; 2]> {
+        let [west, south, east, north] = self.bounding_box()?;
+        let east = if west > east {
+            east + FULL_TURN_DEGREES
+        } else {
+            east
+        };
+        let mut longitude = (west + east) / 2.0;
+        if longitude > HALF_TURN_DEGREES {
+            longitude -= FULL_TURN_DEGREES;
+        }
+        Some([longitude, (south + north) / 2.0])
+    }
+}
+
+/// Degrees of longitude around the globe
+const FULL_TURN_DEGREES: f64 = 360.0;
+
+/// Largest longitude, at the antimeridian
+const HALF_TURN_DEGREES: f64 = 180.0;
+
+/// GeoJSON feature of a [`Place`]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct PlaceGeo {
+    /// GeoJSON object type, `Feature` for places
+    #[serde(rename = "type")]
+    pub geo_type: String,
+
+    /// `[west, south, east, north]` bounds in degrees
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub geo: Option<GeoCoordinates>,
+    pub bbox: Option<Vec<f64>>,
 
     /// Forward compatibility: capture unknown fields
     #[serde(flatten)]
@@ -177,6 +323,9 @@ pub struct GeoCoordinates {
     pub additional_fields: HashMap<String, serde_json::Value>,
 }
 
+/// `voting_status` of a poll that no longer accepts votes
+pub const POLL_CLOSED: &str = "closed";
+
 /// Poll information
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(default)]
@@ -195,10 +344,48 @@ pub struct Poll {
     pub voting_status: Option<String>,
 
     /// Forward compatibility: capture unknown fields
-    #[serde(flatten)]
+    #[serde(
+        flatten,
+        deserialize_with = "crate::util::serde_helpers::deserialize_unknown_fields"
+    )]
     pub additional_fields: HashMap<String, serde_json::Value>,
 }
 
+impl Poll {
+    /// Whether the poll is reported as closed (requires `poll.fields=voting_status`)
+    pub fn is_closed(&self) -> bool {
+        self.voting_status.as_deref() == Some(POLL_CLOSED)
+    }
+
+    /// Whether the poll is closed at `now`
+    ///
+    /// Also true once `end_datetime` has passed, even if the poll was
+    /// fetched while still open.
+    pub fn is_closed_at(&self, now: chrono::DateTime<chrono::Utc>) -> bool {
+        self.is_closed() || self.end_datetime.is_some_and(|end| end <= now)
+    }
+
+    /// Total number of votes across all options
+    pub fn total_votes(&self) -> u64 {
+        self.options
+            .iter()
+            .map(|option| u64::from(option.votes))
+            .sum()
+    }
+
+    /// The option with the most votes
+    ///
+    /// `None` if no votes were cast or several options tie for the lead.
+    pub fn winning_option(&self) -> Option<&PollOption> {
+        let most = self.options.iter().map(|option| option.votes).max()?;
+        let mut leaders = self.options.iter().filter(|option| option.votes == most);
+        match (leaders.next(), leaders.next()) {
+            (Some(winner), None) if most > 0 => Some(winner),
+            _ => None,
+        }
+    }
+}
+
 /// Poll option
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PollOption {
@@ -208,13 +395,143 @@ pub struct PollOption {
 }
 
 /// Reply settings for tweets
-#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
+///
+/// Deserialization accepts the API's spellings (e.g. `mentionedUsers`) and
+/// keeps values this crate does not know in [`Unknown`](Self::Unknown).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
 #[non_exhaustive]
 pub enum ReplySettings {
     Everyone,
     MentionedUsers,
     Following,
+    /// Only subscribers (formerly super followers) can reply
+    Subscribers,
+    /// A value returned by the API that this crate does not recognize
+    Unknown(String),
+}
+
+impl ReplySettings {
+    /// Serialized name of this value
+    pub fn as_str(&self) -> &str {
+        match self {
+            ReplySettings::Everyone => "everyone",
+            ReplySettings::MentionedUsers => "mentioned_users",
+            ReplySettings::Following => "following",
+            ReplySettings::Subscribers => "subscribers",
+            ReplySettings::Unknown(value) => value,
+        }
+    }
+
+    /// Check whether this value can be set when creating a tweet
+    pub fn is_settable(&self) -> bool {
+        !matches!(self, ReplySettings::Unknown(_))
+    }
+
+    /// Value sent in a create-tweet request
+    ///
+    /// `None` for [`Everyone`](Self::Everyone), the default, which is sent by
+    /// omitting the field, and for values that cannot be set.
+    pub(crate) fn request_value(&self) -> Option<&'static str> {
+        match self {
+            ReplySettings::MentionedUsers => Some("mentionedUsers"),
+            ReplySettings::Following => Some("following"),
+            ReplySettings::Subscribers => Some("subscribers"),
+            ReplySettings::Everyone | ReplySettings::Unknown(_) => None,
+        }
+    }
+}
+
+impl From<String> for ReplySettings {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "everyone" => ReplySettings::Everyone,
+            "mentioned_users" | "mentionedUsers" => ReplySettings::MentionedUsers,
+            "following" => ReplySettings::Following,
+            "subscribers" => ReplySettings::Subscribers,
+            _ => ReplySettings::Unknown(value),
+        }
+    }
+}
+
+impl From<ReplySettings> for String {
+    fn from(value: ReplySettings) -> Self {
+        value.as_str().to_string()
+    }
+}
+
+/// Primary language subtag of English
+const ENGLISH: &str = "en";
+
+/// Length bounds of a BCP47 primary language subtag
+const PRIMARY_SUBTAG_LEN: std::ops::RangeInclusive<usize> = 2..=8;
+
+/// Length bounds of the BCP47 subtags after the primary one
+const SUBTAG_LEN: std::ops::RangeInclusive<usize> = 1..=8;
+
+/// BCP47 language tag (e.g. `en`, `pt-BR`)
+///
+/// Serialized as the plain tag. Deserialization accepts any string, since
+/// the API also returns non-standard tags such as `qme` (media only);
+/// [`Lang::new`] checks the shape of tags built by hand.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Lang(String);
+
+impl Lang {
+    /// Create a language tag, checking its BCP47 shape
+    ///
+    /// The primary subtag must be 2 to 8 ASCII letters, and any further
+    /// `-`-separated subtags 1 to 8 ASCII letters or digits.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidRequest` if `tag` is not shaped like a BCP47 tag.
+    pub fn new(tag: impl Into<String>) -> crate::error::Result<Self> {
+        let tag = tag.into();
+        let mut subtags = tag.split('-');
+        let primary_ok = subtags.next().is_some_and(|primary| {
+            PRIMARY_SUBTAG_LEN.contains(&primary.len())
+                && primary.chars().all(|c| c.is_ascii_alphabetic())
+        });
+        let rest_ok = subtags.all(|subtag| {
+            SUBTAG_LEN.contains(&subtag.len()) && subtag.chars().all(|c| c.is_ascii_alphanumeric())
+        });
+        if !(primary_ok && rest_ok) {
+            return Err(crate::error::Error::InvalidRequest(format!(
+                "'{}' is not a BCP47 language tag",
+                tag
+            )));
+        }
+        Ok(Self(tag))
+    }
+
+    /// The tag as sent by the API
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Primary language subtag (e.g. `pt` for `pt-BR`)
+    pub fn primary(&self) -> &str {
+        self.0.split('-').next().unwrap_or_default()
+    }
+
+    /// Check whether the primary language is English, in any region
+    pub fn is_english(&self) -> bool {
+        self.primary().eq_ignore_ascii_case(ENGLISH)
+    }
+}
+
+impl fmt::Display for Lang {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl AsRef<str> for Lang {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
 }
 
 /// Visibility/withheld information
@@ -239,6 +556,108 @@ pub struct Withheld {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_lang_shape_and_english() {
+        for tag in ["en", "en-GB", "pt-BR", "zh-Hant-TW", "und", "es-419"] {
+            assert!(Lang::new(tag).is_ok(), "{} should be valid", tag);
+        }
+        for tag in ["", "e", "en-", "en_US", "1a", "en-toolongsubtag", "-en"] {
+            assert!(Lang::new(tag).is_err(), "{} should be invalid", tag);
+        }
+
+        assert!(Lang::new("en").unwrap().is_english());
+        assert!(Lang::new("EN-us").unwrap().is_english());
+        assert!(!Lang::new("eng").unwrap().is_english());
+        assert_eq!(Lang::new("pt-BR").unwrap().primary(), "pt");
+
+        // Deserialization keeps values that fail validation
+        let lang: Lang = serde_json::from_str("\"qme\"").unwrap();
+        assert_eq!(lang.as_str(), "qme");
+        let odd: Lang = serde_json::from_str("\"not a tag\"").unwrap();
+        assert!(!odd.is_english());
+        assert_eq!(serde_json::to_string(&lang).unwrap(), "\"qme\"");
+    }
+
+    fn poll(votes: &[u32]) -> Poll {
+        Poll {
+            id: "1199786642468413448".to_string(),
+            options: votes
+                .iter()
+                .enumerate()
+                .map(|(i, &votes)| PollOption {
+                    position: i as u32 + 1,
+                    label: format!("option {}", i + 1),
+                    votes,
+                })
+                .collect(),
+            voting_status: Some(POLL_CLOSED.to_string()),
+            ..Poll::default()
+        }
+    }
+
+    #[test]
+    fn test_place_bounding_box_and_centroid() {
+        let place: Place = serde_json::from_value(serde_json::json!({
+            "id": "01a9a39529b27f36",
+            "full_name": "Manhattan, NY",
+            "place_type": "city",
+            "geo": {
+                "type": "Feature",
+                "bbox": [-74.026675, 40.683935, -73.910408, 40.877483],
+                "properties": {}
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(
+            place.bounding_box(),
+            Some([-74.026675, 40.683935, -73.910408, 40.877483])
+        );
+        let [longitude, latitude] = place.centroid().unwrap();
+        assert!((longitude - -73.9685415).abs() < 1e-9);
+        assert!((latitude - 40.780709).abs() < 1e-9);
+
+        // Fiji straddles the antimeridian
+        let fiji = Place {
+            geo: Some(PlaceGeo {
+                bbox: Some(vec![177.0, -19.0, -179.0, -16.0]),
+                ..PlaceGeo::default()
+            }),
+            ..Place::default()
+        };
+        assert_eq!(fiji.centroid(), Some([179.0, -17.5]));
+        assert_eq!(Place::default().centroid(), None);
+    }
+
+    #[test]
+    fn test_closed_poll_winner_and_total() {
+        let poll = poll(&[795, 800, 12]);
+        assert!(poll.is_closed());
+        assert_eq!(poll.total_votes(), 1607);
+        assert_eq!(poll.winning_option().unwrap().position, 2);
+    }
+
+    #[test]
+    fn test_poll_tie_has_no_winner() {
+        let poll = poll(&[40, 12, 40]);
+        assert_eq!(poll.total_votes(), 92);
+        assert!(poll.winning_option().is_none());
+        assert!(self::poll(&[0, 0]).winning_option().is_none());
+    }
+
+    #[test]
+    fn test_poll_closed_after_end_datetime() {
+        let end = chrono::DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let poll = Poll {
+            end_datetime: Some(end),
+            voting_status: Some("open".to_string()),
+            ..poll(&[1, 2])
+        };
+        assert!(!poll.is_closed());
+        assert!(!poll.is_closed_at(end - chrono::Duration::seconds(1)));
+        assert!(poll.is_closed_at(end));
+    }
+
     #[test]
     fn test_api_response_roundtrip() {
         let json = r#"{"data":{"id":"123"},"meta":{"result_count":1}}"#;
@@ -307,6 +726,45 @@ mod tests {
         assert_eq!(error.message, roundtrip.message);
     }
 
+    #[test]
+    fn test_partial_error_problem_shape() {
+        let json = r#"{
+            "value": "123",
+            "detail": "Could not find tweet with ids: [123].",
+            "title": "Not Found Error",
+            "resource_type": "tweet",
+            "parameter": "ids",
+            "resource_id": "123",
+            "type": "https://api.twitter.com/2/problems/resource-not-found"
+        }"#;
+
+        let error: ApiError = serde_json::from_str(json).unwrap();
+        assert_eq!(error.message, "Could not find tweet with ids: [123].");
+        assert_eq!(error.resource_id.as_deref(), Some("123"));
+        assert_eq!(
+            error.type_uri.as_deref(),
+            Some("https://api.twitter.com/2/problems/resource-not-found")
+        );
+    }
+
+    #[test]
+    fn test_partial_error_with_message_and_detail() {
+        let json = r#"{
+            "message": "Tweet not found",
+            "detail": "Could not find tweet with ids: [123].",
+            "title": "Not Found Error",
+            "type": "https://api.twitter.com/2/problems/resource-not-found"
+        }"#;
+
+        let error: ApiError = serde_json::from_str(json).unwrap();
+        assert_eq!(error.message, "Tweet not found");
+        assert_eq!(
+            error.detail.as_deref(),
+            Some("Could not find tweet with ids: [123].")
+        );
+        assert!(error.additional_fields.contains_key("title"));
+    }
+
     #[test]
     fn test_place_unknown_fields_captured() {
         let json = r#"{
@@ -368,6 +826,20 @@ mod tests {
         assert!(matches!(settings, ReplySettings::Following));
     }
 
+    #[test]
+    fn test_reply_settings_api_values() {
+        let settings: ReplySettings = serde_json::from_str(r#""subscribers""#).unwrap();
+        assert_eq!(settings, ReplySettings::Subscribers);
+
+        let settings: ReplySettings = serde_json::from_str(r#""mentionedUsers""#).unwrap();
+        assert_eq!(settings, ReplySettings::MentionedUsers);
+
+        let settings: ReplySettings = serde_json::from_str(r#""verified""#).unwrap();
+        assert_eq!(settings, ReplySettings::Unknown("verified".to_string()));
+        assert_eq!(serde_json::to_string(&settings).unwrap(), r#""verified""#);
+        assert!(!settings.is_settable());
+    }
+
     #[test]
     fn test_withheld_default_fields() {
         let json = r#"{}"#;
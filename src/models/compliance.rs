@@ -0,0 +1,165 @@
+//! Compliance events
+//!
+//! Delivered by the compliance streams (see
+//! [`Client::compliance_stream`](crate::Client::compliance_stream)) whenever
+//! a tweet or user changes in a way that stored copies must reflect: a tweet
+//! is deleted or withheld, a user is suspended or protected, and so on.
+//!
+//! # Visibility Strategy
+//!
+//! Struct fields are intentionally `pub` to support Serde serialization/deserialization.
+//! See module documentation in `models/common.rs` for rationale.
+
+use crate::models::common::{TweetId, UserId};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A message of a compliance stream
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ComplianceMessage {
+    /// The compliance event
+    pub data: ComplianceEvent,
+}
+
+/// A compliance event, keyed by its type
+///
+/// Tweet events arrive on the tweets compliance stream and user events on
+/// the users compliance stream. An event type not listed here fails to
+/// parse; the stream yields that error and continues.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum ComplianceEvent {
+    /// A tweet was deleted
+    Delete(TweetComplianceEvent),
+    /// A tweet was edited
+    TweetEdit(TweetEditEvent),
+    /// A tweet was withheld in some countries
+    Withheld(TweetComplianceEvent),
+    /// A tweet was made unavailable
+    Drop(TweetComplianceEvent),
+    /// A dropped tweet was made available again
+    Undrop(TweetComplianceEvent),
+    /// A user removed the location from their tweets
+    ScrubGeo(ScrubGeoEvent),
+    /// A user account was deleted
+    UserDelete(UserComplianceEvent),
+    /// A deleted user account was restored
+    UserUndelete(UserComplianceEvent),
+    /// A user account was suspended
+    UserSuspend(UserComplianceEvent),
+    /// A suspended user account was reinstated
+    UserUnsuspend(UserComplianceEvent),
+    /// A user account was withheld in some countries
+    UserWithheld(UserComplianceEvent),
+    /// A user protected their tweets
+    UserProtect(UserComplianceEvent),
+    /// A user made their tweets public again
+    UserUnprotect(UserComplianceEvent),
+    /// A user changed their profile
+    UserProfileModification(UserComplianceEvent),
+}
+
+impl ComplianceEvent {
+    /// Time the event happened
+    pub fn event_at(&self) -> DateTime<Utc> {
+        match self {
+            ComplianceEvent::Delete(event)
+            | ComplianceEvent::Withheld(event)
+            | ComplianceEvent::Drop(event)
+            | ComplianceEvent::Undrop(event) => event.event_at,
+            ComplianceEvent::TweetEdit(event) => event.event_at,
+            ComplianceEvent::ScrubGeo(event) => event.event_at,
+            ComplianceEvent::UserDelete(event)
+            | ComplianceEvent::UserUndelete(event)
+            | ComplianceEvent::UserSuspend(event)
+            | ComplianceEvent::UserUnsuspend(event)
+            | ComplianceEvent::UserWithheld(event)
+            | ComplianceEvent::UserProtect(event)
+            | ComplianceEvent::UserUnprotect(event)
+            | ComplianceEvent::UserProfileModification(event) => event.event_at,
+        }
+    }
+}
+
+/// Tweet an event concerns
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ComplianceTweet {
+    pub id: TweetId,
+
+    pub author_id: UserId,
+}
+
+/// User an event concerns
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ComplianceUser {
+    pub id: UserId,
+}
+
+/// Event about a single tweet
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TweetComplianceEvent {
+    pub tweet: ComplianceTweet,
+
+    pub event_at: DateTime<Utc>,
+
+    /// Countries the tweet is withheld in (withheld events)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub withheld_in_countries: Vec<String>,
+
+    /// Forward compatibility: capture unknown fields
+    #[serde(flatten)]
+    pub additional_fields: HashMap<String, serde_json::Value>,
+}
+
+/// Event about an edited tweet
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TweetEditEvent {
+    /// The new version of the tweet
+    pub tweet: ComplianceTweet,
+
+    pub event_at: DateTime<Utc>,
+
+    /// ID of the original tweet of the edit chain
+    pub initial_tweet_id: TweetId,
+
+    /// IDs of every version of the tweet, oldest first
+    #[serde(default)]
+    pub edit_tweet_ids: Vec<TweetId>,
+
+    /// Forward compatibility: capture unknown fields
+    #[serde(flatten)]
+    pub additional_fields: HashMap<String, serde_json::Value>,
+}
+
+/// Event removing the location from a user's tweets
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScrubGeoEvent {
+    pub user: ComplianceUser,
+
+    /// Newest tweet the location was removed from
+    pub up_to_tweet_id: TweetId,
+
+    pub event_at: DateTime<Utc>,
+
+    /// Forward compatibility: capture unknown fields
+    #[serde(flatten)]
+    pub additional_fields: HashMap<String, serde_json::Value>,
+}
+
+/// Event about a user account
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UserComplianceEvent {
+    pub user: ComplianceUser,
+
+    pub event_at: DateTime<Utc>,
+
+    /// Countries the user is withheld in (withheld events)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub withheld_in_countries: Vec<String>,
+
+    /// Forward compatibility: capture unknown fields
+    #[serde(flatten)]
+    pub additional_fields: HashMap<String, serde_json::Value>,
+}
@@ -0,0 +1,121 @@
+//! Direct message objects
+//!
+//! # Visibility Strategy
+//!
+//! Struct fields are intentionally `pub` to support Serde serialization/deserialization.
+//! See module documentation in `models/common.rs` for rationale.
+
+use crate::models::common::{ConversationId, DmEventId, MediaId, TweetId, UserId};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A direct message event (a message, or a participant joining or leaving)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DirectMessageEvent {
+    /// Unique identifier of this event
+    pub id: DmEventId,
+
+    /// Kind of event
+    pub event_type: DmEventType,
+
+    // Optional fields
+    /// Message text (MessageCreate events only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+
+    /// User ID of the sender
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sender_id: Option<UserId>,
+
+    /// Conversation the event belongs to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dm_conversation_id: Option<ConversationId>,
+
+    /// Creation time of this event
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<DateTime<Utc>>,
+
+    /// Attached media and cards
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attachments: Option<DmAttachments>,
+
+    /// Tweets shared in the message
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub referenced_tweets: Option<Vec<DmReferencedTweet>>,
+
+    /// Users who joined or left (ParticipantsJoin/ParticipantsLeave events only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub participant_ids: Option<Vec<UserId>>,
+
+    /// Forward compatibility: capture unknown fields
+    #[serde(
+        flatten,
+        deserialize_with = "crate::util::serde_helpers::deserialize_unknown_fields"
+    )]
+    pub additional_fields: HashMap<String, serde_json::Value>,
+}
+
+/// Kind of direct message event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub enum DmEventType {
+    MessageCreate,
+    ParticipantsJoin,
+    ParticipantsLeave,
+}
+
+/// Attachments of a direct message
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct DmAttachments {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub media_keys: Option<Vec<MediaId>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub card_ids: Option<Vec<String>>,
+
+    /// Forward compatibility: capture unknown fields
+    #[serde(flatten)]
+    pub additional_fields: HashMap<String, serde_json::Value>,
+}
+
+/// Tweet shared in a direct message
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DmReferencedTweet {
+    pub id: TweetId,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dm_event_roundtrip() {
+        let json = r#"{
+            "id": "1580705921830768647",
+            "event_type": "MessageCreate",
+            "text": "Hello there",
+            "sender_id": "906948460078698496",
+            "dm_conversation_id": "1512210732774948865-906948460078698496",
+            "created_at": "2022-10-14T00:00:00.000Z",
+            "attachments": {"media_keys": ["3_1"]},
+            "referenced_tweets": [{"id": "20"}]
+        }"#;
+
+        let event: DirectMessageEvent = serde_json::from_str(json).unwrap();
+        assert_eq!(event.event_type, DmEventType::MessageCreate);
+        assert_eq!(event.sender_id.as_deref(), Some("906948460078698496"));
+        assert_eq!(
+            event.attachments.unwrap().media_keys,
+            Some(vec!["3_1".to_string()])
+        );
+
+        let join: DirectMessageEvent = serde_json::from_str(
+            r#"{"id": "2", "event_type": "ParticipantsJoin", "participant_ids": ["7"]}"#,
+        )
+        .unwrap();
+        assert_eq!(join.event_type, DmEventType::ParticipantsJoin);
+        assert!(join.text.is_none());
+    }
+}
@@ -45,7 +45,10 @@ pub struct List {
     pub private: Option<bool>,
 
     /// Forward compatibility: capture unknown fields
-    #[serde(flatten)]
+    #[serde(
+        flatten,
+        deserialize_with = "crate::util::serde_helpers::deserialize_unknown_fields"
+    )]
     pub additional_fields: HashMap<String, serde_json::Value>,
 }
 
@@ -6,8 +6,12 @@
 //! See module documentation in `models/common.rs` for rationale.
 
 use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
 use std::collections::HashMap;
 
+/// Content type of progressive MP4 video variants
+pub const MP4_CONTENT_TYPE: &str = "video/mp4";
+
 /// A Media object
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Media {
@@ -64,18 +68,82 @@ pub struct Media {
     pub variants: Option<Vec<MediaVariant>>,
 
     /// Forward compatibility: capture unknown fields
-    #[serde(flatten)]
+    #[serde(
+        flatten,
+        deserialize_with = "crate::util::serde_helpers::deserialize_unknown_fields"
+    )]
     pub additional_fields: HashMap<String, serde_json::Value>,
 }
 
+impl Media {
+    /// Alternative text description (requires `media.fields=alt_text`)
+    pub fn alt_text(&self) -> Option<&str> {
+        self.alt_text.as_deref()
+    }
+
+    /// Highest-bitrate variant of the given content type
+    ///
+    /// Variants without a bitrate (such as HLS manifests) rank lowest.
+    pub fn best_variant(&self, content_type: &str) -> Option<&MediaVariant> {
+        self.variants
+            .as_deref()?
+            .iter()
+            .filter(|v| v.content_type == content_type)
+            .max_by_key(|v| v.bit_rate)
+    }
+
+    /// MP4 variants, highest bitrate first
+    pub fn mp4_variants(&self) -> Vec<&MediaVariant> {
+        let mut variants: Vec<_> = self
+            .variants
+            .iter()
+            .flatten()
+            .filter(|v| v.content_type == MP4_CONTENT_TYPE)
+            .collect();
+        variants.sort_by_key(|v| Reverse(v.bit_rate));
+        variants
+    }
+}
+
 /// Type of media
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
 #[non_exhaustive]
 pub enum MediaType {
     Photo,
     Video,
     AnimatedGif,
+    /// A type this version does not know about
+    Unknown(String),
+}
+
+impl MediaType {
+    /// Value sent in `type`
+    pub fn as_str(&self) -> &str {
+        match self {
+            MediaType::Photo => "photo",
+            MediaType::Video => "video",
+            MediaType::AnimatedGif => "animated_gif",
+            MediaType::Unknown(value) => value,
+        }
+    }
+}
+
+impl From<String> for MediaType {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "photo" => MediaType::Photo,
+            "video" => MediaType::Video,
+            "animated_gif" => MediaType::AnimatedGif,
+            _ => MediaType::Unknown(value),
+        }
+    }
+}
+
+impl From<MediaType> for String {
+    fn from(value: MediaType) -> Self {
+        value.as_str().to_string()
+    }
 }
 
 /// Media engagement metrics
@@ -147,4 +215,47 @@ mod tests {
         let mtype: MediaType = serde_json::from_str(json).unwrap();
         assert!(matches!(mtype, MediaType::AnimatedGif));
     }
+
+    #[test]
+    fn test_unknown_media_type_roundtrip() {
+        let json = r#"{"media_key": "9_123", "type": "hologram"}"#;
+        let media: Media = serde_json::from_str(json).unwrap();
+        assert_eq!(media.media_type, MediaType::Unknown("hologram".to_string()));
+
+        let serialized = serde_json::to_value(&media).unwrap();
+        assert_eq!(serialized["type"], "hologram");
+    }
+
+    #[test]
+    fn test_variants_by_bitrate() {
+        let json = r#"{
+            "media_key": "7_123",
+            "type": "video",
+            "variants": [
+                {"bit_rate": 832000, "content_type": "video/mp4", "url": "https://video.twimg.com/mid.mp4"},
+                {"content_type": "application/x-mpegURL", "url": "https://video.twimg.com/pl.m3u8"},
+                {"bit_rate": 2176000, "content_type": "video/mp4", "url": "https://video.twimg.com/high.mp4"},
+                {"bit_rate": 256000, "content_type": "video/mp4", "url": "https://video.twimg.com/low.mp4"}
+            ]
+        }"#;
+        let media: Media = serde_json::from_str(json).unwrap();
+
+        let best = media.best_variant(MP4_CONTENT_TYPE).unwrap();
+        assert_eq!(best.url, "https://video.twimg.com/high.mp4");
+
+        let hls = media.best_variant("application/x-mpegURL").unwrap();
+        assert_eq!(hls.url, "https://video.twimg.com/pl.m3u8");
+        assert!(media.best_variant("video/webm").is_none());
+
+        let rates: Vec<_> = media.mp4_variants().iter().map(|v| v.bit_rate).collect();
+        assert_eq!(rates, vec![Some(2176000), Some(832000), Some(256000)]);
+    }
+
+    #[test]
+    fn test_no_variants() {
+        let json = r#"{"media_key": "3_1", "type": "photo"}"#;
+        let media: Media = serde_json::from_str(json).unwrap();
+        assert!(media.best_variant(MP4_CONTENT_TYPE).is_none());
+        assert!(media.mp4_variants().is_empty());
+    }
 }
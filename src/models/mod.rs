@@ -2,10 +2,15 @@
 //!
 //! This module provides strongly-typed models for all API objects including:
 //! - Tweet, User, Space, List, Media, Poll, Place, DirectMessage, Community
+//! - Compliance events
+//! - Project usage
 
 pub mod common;
+pub mod compliance;
+pub mod direct_message;
 pub mod list;
 pub mod media;
 pub mod space;
 pub mod tweet;
+pub mod usage;
 pub mod user;
@@ -5,7 +5,8 @@
 //! Struct fields are intentionally `pub` to support Serde serialization/deserialization.
 //! See module documentation in `models/common.rs` for rationale.
 
-use crate::models::common::{SpaceId, UserId};
+use crate::models::common::{Includes, Lang, SpaceId, UserId};
+use crate::models::user::User;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -46,7 +47,7 @@ pub struct Space {
 
     /// Primary language of audio in the Space (BCP47)
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub lang: Option<String>,
+    pub lang: Option<Lang>,
 
     /// Number of participants
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -81,18 +82,92 @@ pub struct Space {
     pub updated_at: Option<DateTime<Utc>>,
 
     /// Forward compatibility: capture unknown fields
-    #[serde(flatten)]
+    #[serde(
+        flatten,
+        deserialize_with = "crate::util::serde_helpers::deserialize_unknown_fields"
+    )]
     pub additional_fields: HashMap<String, serde_json::Value>,
 }
 
+impl Space {
+    /// Find the creator in `includes` (requires `expansions=creator_id`)
+    pub fn creator_in<'a>(&self, includes: &'a Includes) -> Option<&'a User> {
+        includes.user(self.creator_id.as_deref()?)
+    }
+
+    /// Find the hosts in `includes` (requires `expansions=host_ids`)
+    ///
+    /// Hosts missing from `includes` are skipped.
+    pub fn hosts_in<'a>(&self, includes: &'a Includes) -> Vec<&'a User> {
+        users_in(self.host_ids.as_deref(), includes)
+    }
+
+    /// Find the speakers in `includes` (requires `expansions=speaker_ids`)
+    ///
+    /// Speakers missing from `includes` are skipped.
+    pub fn speakers_in<'a>(&self, includes: &'a Includes) -> Vec<&'a User> {
+        users_in(self.speaker_ids.as_deref(), includes)
+    }
+}
+
+fn users_in<'a>(ids: Option<&[UserId]>, includes: &'a Includes) -> Vec<&'a User> {
+    ids.unwrap_or_default()
+        .iter()
+        .filter_map(|id| includes.user(id))
+        .collect()
+}
+
 /// State of a Space
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
-#[serde(rename_all = "PascalCase")]
+///
+/// The API reports states in lowercase; both spellings are accepted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
 #[non_exhaustive]
 pub enum SpaceState {
     Live,
     Scheduled,
     Ended,
+    /// A state this version does not know about
+    Unknown(String),
+}
+
+impl SpaceState {
+    /// Serialized name of this state
+    pub fn as_str(&self) -> &str {
+        match self {
+            SpaceState::Live => "Live",
+            SpaceState::Scheduled => "Scheduled",
+            SpaceState::Ended => "Ended",
+            SpaceState::Unknown(value) => value,
+        }
+    }
+
+    /// Value of the `state` query parameter selecting this state
+    pub fn as_query_value(&self) -> &str {
+        match self {
+            SpaceState::Live => "live",
+            SpaceState::Scheduled => "scheduled",
+            SpaceState::Ended => "ended",
+            SpaceState::Unknown(value) => value,
+        }
+    }
+}
+
+impl From<String> for SpaceState {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "Live" | "live" => SpaceState::Live,
+            "Scheduled" | "scheduled" => SpaceState::Scheduled,
+            "Ended" | "ended" => SpaceState::Ended,
+            _ => SpaceState::Unknown(value),
+        }
+    }
+}
+
+impl From<SpaceState> for String {
+    fn from(value: SpaceState) -> Self {
+        value.as_str().to_string()
+    }
 }
 
 #[cfg(test)]
@@ -137,4 +212,11 @@ mod tests {
         let json = serde_json::to_string(&state).unwrap();
         assert_eq!(json, r#""Ended""#);
     }
+
+    #[test]
+    fn test_space_state_lowercase_from_api() {
+        let state: SpaceState = serde_json::from_str(r#""live""#).unwrap();
+        assert_eq!(state, SpaceState::Live);
+        assert_eq!(state.as_query_value(), "live");
+    }
 }
@@ -5,7 +5,11 @@
 //! Struct fields are intentionally `pub` to support Serde serialization/deserialization.
 //! See module documentation in `models/common.rs` for rationale.
 
-use crate::models::common::{ReplySettings, TweetId, UserId, Withheld};
+use crate::models::common::{
+    ApiResponse, Includes, Lang, Poll, ReplySettings, TweetId, UserId, Withheld,
+};
+use crate::models::media::Media;
+use crate::models::user::User;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -70,7 +74,7 @@ pub struct Tweet {
 
     /// Language of the Tweet (BCP47 language tag)
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub lang: Option<String>,
+    pub lang: Option<Lang>,
 
     /// Indicates if this Tweet contains URLs marked as possibly sensitive
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -84,7 +88,7 @@ pub struct Tweet {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub reply_settings: Option<ReplySettings>,
 
-    /// The name of the app used to post this Tweet
+    /// The name of the app used to post this Tweet (see [`posted_via`](Self::posted_via))
     #[serde(skip_serializing_if = "Option::is_none")]
     pub source: Option<String>,
 
@@ -100,11 +104,267 @@ pub struct Tweet {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub edit_controls: Option<EditControls>,
 
+    /// Full text of a long-form tweet (requires `tweet.fields=note_tweet`)
+    ///
+    /// `text` is truncated for long-form tweets; see
+    /// [`full_text`](Tweet::full_text).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note_tweet: Option<NoteTweet>,
+
     /// Forward compatibility: capture unknown fields
-    #[serde(flatten)]
+    #[serde(
+        flatten,
+        deserialize_with = "crate::util::serde_helpers::deserialize_unknown_fields"
+    )]
     pub additional_fields: HashMap<String, serde_json::Value>,
 }
 
+impl Tweet {
+    /// Find the referenced tweet of the given type
+    ///
+    /// The order of `referenced_tweets` is not guaranteed (a reply that also
+    /// quotes may list either first), so this scans by type rather than
+    /// relying on position.
+    pub fn referenced(&self, reference_type: ReferenceType) -> Option<&ReferencedTweet> {
+        self.referenced_tweets
+            .as_deref()?
+            .iter()
+            .find(|r| r.reference_type == reference_type)
+    }
+
+    /// The complete text of the tweet
+    ///
+    /// Returns the `note_tweet` text of long-form tweets, whose `text` is
+    /// truncated, and `text` otherwise.
+    pub fn full_text(&self) -> &str {
+        self.note_tweet
+            .as_ref()
+            .map_or(self.text.as_str(), |note| note.text.as_str())
+    }
+
+    /// Check if this tweet is a reply
+    pub fn is_reply(&self) -> bool {
+        self.referenced(ReferenceType::RepliedTo).is_some()
+    }
+
+    /// Check if this tweet quotes another tweet
+    pub fn is_quote(&self) -> bool {
+        self.referenced(ReferenceType::Quoted).is_some()
+    }
+
+    /// Check if this tweet is a retweet
+    pub fn is_retweet(&self) -> bool {
+        self.referenced(ReferenceType::Retweeted).is_some()
+    }
+
+    /// Check if this tweet is withheld in some countries or for copyright
+    pub fn is_withheld(&self) -> bool {
+        self.withheld.is_some()
+    }
+
+    /// Countries this tweet is withheld in (ISO 3166-1 alpha-2 codes)
+    pub fn withheld_countries(&self) -> &[String] {
+        self.withheld
+            .as_ref()
+            .and_then(|withheld| withheld.country_codes.as_deref())
+            .unwrap_or_default()
+    }
+
+    /// Check if this tweet's URLs are marked as possibly sensitive
+    ///
+    /// Requires `tweet.fields=possibly_sensitive`.
+    pub fn is_sensitive(&self) -> bool {
+        self.possibly_sensitive == Some(true)
+    }
+
+    /// Creation time decoded from the tweet's snowflake ID
+    ///
+    /// Available even when `created_at` was not requested, with millisecond
+    /// precision. Returns `None` for IDs that predate snowflakes.
+    pub fn created_at_from_id(&self) -> Option<DateTime<Utc>> {
+        crate::util::snowflake_to_datetime(&self.id)
+    }
+
+    /// Creation time from `created_at`, or decoded from the ID if the field
+    /// was not requested
+    ///
+    /// Returns `None` only for tweets without `created_at` whose IDs predate
+    /// snowflakes (see [`created_at_from_id`](Self::created_at_from_id)).
+    pub fn best_created_at(&self) -> Option<DateTime<Utc>> {
+        self.created_at.or_else(|| self.created_at_from_id())
+    }
+
+    /// Name of the app this tweet was posted with (e.g. `Twitter Web App`)
+    ///
+    /// Requires `tweet.fields=source` (see [`LookupOptions::with_source`](crate::builder::query::LookupOptions::with_source)).
+    /// The field is deprecated: it is mostly populated on older tweets and
+    /// is often absent or empty on newer ones, so `None` does not mean the
+    /// field was not requested. Empty values are returned as `None`.
+    pub fn posted_via(&self) -> Option<&str> {
+        self.source.as_deref().filter(|source| !source.is_empty())
+    }
+
+    /// Find this tweet's author in `includes` (requires `expansions=author_id`)
+    pub fn author_in<'a>(&self, includes: &'a Includes) -> Option<&'a User> {
+        includes.user(self.author_id.as_deref()?)
+    }
+
+    /// Find the user this tweet replies to in `includes`
+    /// (requires `expansions=in_reply_to_user_id`)
+    pub fn reply_to_user_in<'a>(&self, includes: &'a Includes) -> Option<&'a User> {
+        includes.user(self.in_reply_to_user_id.as_deref()?)
+    }
+
+    /// Find this tweet's attached media in `includes`
+    /// (requires `expansions=attachments.media_keys`)
+    ///
+    /// Media missing from `includes` are skipped.
+    pub fn media_in<'a>(&self, includes: &'a Includes) -> Vec<&'a Media> {
+        let keys = self
+            .attachments
+            .as_ref()
+            .and_then(|a| a.media_keys.as_deref())
+            .unwrap_or_default();
+        keys.iter().filter_map(|key| includes.media(key)).collect()
+    }
+
+    /// Find the media shown with this tweet in `includes`, including media
+    /// owned by source tweets
+    ///
+    /// Media of retweets, quotes and tweets reusing another tweet's media
+    /// belong to the source tweet. Returns this tweet's own media first,
+    /// then those of the tweets named by `attachments.media_source_tweet_id`
+    /// and of the quoted or retweeted tweet, without duplicates. Requires
+    /// `expansions=attachments.media_keys` plus
+    /// `attachments.media_source_tweet_id` or `referenced_tweets.id`, and
+    /// `referenced_tweets.id.attachments.media_keys` for quoted media.
+    pub fn media_for<'a>(&self, includes: &'a Includes) -> Vec<&'a Media> {
+        let source_ids = self
+            .attachments
+            .as_ref()
+            .and_then(|a| a.media_source_tweet_id.as_deref())
+            .unwrap_or_default()
+            .iter()
+            .map(String::as_str)
+            .chain(
+                [ReferenceType::Quoted, ReferenceType::Retweeted]
+                    .into_iter()
+                    .filter_map(|reference_type| self.referenced(reference_type))
+                    .map(|reference| reference.id.as_str()),
+            );
+        let mut media = self.media_in(includes);
+        for source in source_ids.filter_map(|id| includes.tweet(id)) {
+            for item in source.media_in(includes) {
+                if !media.iter().any(|m| m.media_key == item.media_key) {
+                    media.push(item);
+                }
+            }
+        }
+        media
+    }
+
+    /// Find this tweet's poll in `includes`
+    /// (requires `expansions=attachments.poll_ids`)
+    pub fn poll_in<'a>(&self, includes: &'a Includes) -> Option<&'a Poll> {
+        let poll_ids = self.attachments.as_ref()?.poll_ids.as_deref()?;
+        poll_ids.iter().find_map(|id| includes.poll(id))
+    }
+
+    /// Find the referenced tweet of the given type in `includes`
+    /// (requires `expansions=referenced_tweets.id`)
+    pub fn referenced_in<'a>(
+        &self,
+        reference_type: ReferenceType,
+        includes: &'a Includes,
+    ) -> Option<&'a Tweet> {
+        includes.tweet(&self.referenced(reference_type)?.id)
+    }
+
+    /// Find the author of the referenced tweet of the given type in `includes`
+    /// (requires `expansions=referenced_tweets.id,referenced_tweets.id.author_id`)
+    pub fn referenced_author_in<'a>(
+        &self,
+        reference_type: ReferenceType,
+        includes: &'a Includes,
+    ) -> Option<&'a User> {
+        self.referenced_in(reference_type, includes)?
+            .author_in(includes)
+    }
+
+    /// Public engagements per impression
+    ///
+    /// Divides [`TweetMetrics::engagement_total`] by the impression count of
+    /// `public_metrics`. Returns `None` if `public_metrics` or its
+    /// `impression_count` is absent, or if the tweet has no impressions.
+    pub fn engagement_rate(&self) -> Option<f64> {
+        let metrics = self.public_metrics.as_ref()?;
+        let impressions = metrics.impression_count.filter(|&count| count > 0)?;
+        Some(metrics.engagement_total() as f64 / impressions as f64)
+    }
+}
+
+impl<T> ApiResponse<T> {
+    /// The user `tweet` replies to, if expanded in this response
+    ///
+    /// Requires `expansions=in_reply_to_user_id`. Works for the tweets in
+    /// `data` as well as for tweets in `includes`.
+    pub fn reply_to_user_of(&self, tweet: &Tweet) -> Option<&User> {
+        tweet.reply_to_user_in(self.includes.as_ref()?)
+    }
+
+    /// Alt text of each media attached to `tweet`, in attachment order
+    ///
+    /// Requires `expansions=attachments.media_keys` and
+    /// `media.fields=alt_text`. Media without alt text, or missing from
+    /// `includes`, are `None`.
+    pub fn alt_texts_for(&self, tweet: &Tweet) -> Vec<Option<&str>> {
+        let keys = tweet
+            .attachments
+            .as_ref()
+            .and_then(|a| a.media_keys.as_deref())
+            .unwrap_or_default();
+        keys.iter()
+            .map(|key| self.includes.as_ref()?.media(key).and_then(Media::alt_text))
+            .collect()
+    }
+}
+
+impl ApiResponse<Tweet> {
+    /// The author of the returned tweet, if expanded
+    pub fn author(&self) -> Option<&User> {
+        self.data.as_ref()?.author_in(self.includes.as_ref()?)
+    }
+
+    /// The media shown with the returned tweet, if expanded
+    ///
+    /// Includes media owned by source tweets (see [`Tweet::media_for`]).
+    pub fn media(&self) -> Vec<&Media> {
+        match (&self.data, &self.includes) {
+            (Some(tweet), Some(includes)) => tweet.media_for(includes),
+            _ => Vec::new(),
+        }
+    }
+
+    /// The poll attached to the returned tweet, if expanded
+    pub fn poll(&self) -> Option<&Poll> {
+        self.data.as_ref()?.poll_in(self.includes.as_ref()?)
+    }
+
+    /// The referenced tweet of the given type, if expanded
+    pub fn referenced_tweet(&self, reference_type: ReferenceType) -> Option<&Tweet> {
+        self.data
+            .as_ref()?
+            .referenced_in(reference_type, self.includes.as_ref()?)
+    }
+
+    /// The author of the referenced tweet of the given type, if expanded
+    pub fn referenced_author(&self, reference_type: ReferenceType) -> Option<&User> {
+        self.data
+            .as_ref()?
+            .referenced_author_in(reference_type, self.includes.as_ref()?)
+    }
+}
+
 /// Engagement metrics for a Tweet
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(default)]
@@ -132,6 +392,23 @@ pub struct TweetMetrics {
     pub additional_fields: HashMap<String, serde_json::Value>,
 }
 
+impl TweetMetrics {
+    /// Sum of likes, retweets, replies and quotes
+    ///
+    /// Missing counts are treated as zero.
+    pub fn engagement_total(&self) -> u64 {
+        [
+            self.like_count,
+            self.retweet_count,
+            self.reply_count,
+            self.quote_count,
+        ]
+        .into_iter()
+        .flatten()
+        .fold(0, u64::saturating_add)
+    }
+}
+
 /// Tweet attachments (media, polls, etc.)
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(default)]
@@ -139,6 +416,10 @@ pub struct Attachments {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub media_keys: Option<Vec<String>>,
 
+    /// IDs of the tweets the attached media were originally posted with
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub media_source_tweet_id: Option<Vec<TweetId>>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub poll_ids: Option<Vec<String>>,
 
@@ -250,13 +531,44 @@ pub struct ReferencedTweet {
 }
 
 /// Type of tweet reference
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
 #[non_exhaustive]
 pub enum ReferenceType {
     Retweeted,
     Quoted,
     RepliedTo,
+    /// A type this version does not know about
+    Unknown(String),
+}
+
+impl ReferenceType {
+    /// Value sent in `type`
+    pub fn as_str(&self) -> &str {
+        match self {
+            ReferenceType::Retweeted => "retweeted",
+            ReferenceType::Quoted => "quoted",
+            ReferenceType::RepliedTo => "replied_to",
+            ReferenceType::Unknown(value) => value,
+        }
+    }
+}
+
+impl From<String> for ReferenceType {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "retweeted" => ReferenceType::Retweeted,
+            "quoted" => ReferenceType::Quoted,
+            "replied_to" => ReferenceType::RepliedTo,
+            _ => ReferenceType::Unknown(value),
+        }
+    }
+}
+
+impl From<ReferenceType> for String {
+    fn from(value: ReferenceType) -> Self {
+        value.as_str().to_string()
+    }
 }
 
 /// Context annotation
@@ -301,6 +613,61 @@ pub struct DeleteResponse {
     pub deleted: bool,
 }
 
+/// Response from hiding or unhiding a reply
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HideReplyResponse {
+    /// Whether the reply is now hidden
+    pub hidden: bool,
+}
+
+/// Full content of a long-form tweet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NoteTweet {
+    /// The untruncated text
+    pub text: String,
+
+    /// Entities parsed from the untruncated text
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entities: Option<Entities>,
+
+    /// Forward compatibility: capture unknown fields
+    #[serde(flatten)]
+    pub additional_fields: HashMap<String, serde_json::Value>,
+}
+
+/// Number of tweets in one time bucket of a counts response
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TweetCount {
+    /// Start of the bucket (inclusive)
+    pub start: DateTime<Utc>,
+
+    /// End of the bucket (exclusive)
+    pub end: DateTime<Utc>,
+
+    /// Number of matching tweets in the bucket
+    pub tweet_count: u64,
+}
+
+/// Metrics for one of the authenticated user's own tweets
+///
+/// Returned by [`Client::my_tweet_analytics`](crate::Client::my_tweet_analytics).
+/// Non-public and organic metrics are only available to the tweet's author,
+/// for tweets from the last 30 days.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TweetAnalytics {
+    /// Tweet the metrics belong to
+    pub tweet_id: TweetId,
+
+    /// Publicly visible engagement counts
+    pub public_metrics: Option<TweetMetrics>,
+
+    /// Private counts such as impressions and link clicks
+    pub non_public_metrics: Option<TweetMetrics>,
+
+    /// Counts from organic (non-promoted) distribution
+    pub organic_metrics: Option<TweetMetrics>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -405,4 +772,299 @@ mod tests {
         assert!(entities.hashtags.is_some());
         assert_eq!(entities.hashtags.as_ref().unwrap()[0].tag, "rust");
     }
+
+    #[test]
+    fn test_referenced_independent_of_order() {
+        let orders = [
+            r#"[{"type":"replied_to","id":"1"},{"type":"quoted","id":"2"}]"#,
+            r#"[{"type":"quoted","id":"2"},{"type":"replied_to","id":"1"}]"#,
+        ];
+
+        for referenced in orders {
+            let json = format!(
+                r#"{{"id":"3","text":"reply and quote","referenced_tweets":{}}}"#,
+                referenced
+            );
+            let tweet: Tweet = serde_json::from_str(&json).unwrap();
+
+            assert_eq!(tweet.referenced(ReferenceType::RepliedTo).unwrap().id, "1");
+            assert_eq!(tweet.referenced(ReferenceType::Quoted).unwrap().id, "2");
+            assert!(tweet.referenced(ReferenceType::Retweeted).is_none());
+            assert!(tweet.is_reply());
+            assert!(tweet.is_quote());
+            assert!(!tweet.is_retweet());
+        }
+
+        let original: Tweet = serde_json::from_str(r#"{"id":"4","text":"original"}"#).unwrap();
+        assert!(!original.is_reply());
+        assert!(!original.is_quote());
+    }
+
+    #[test]
+    fn test_media_for_resolves_source_tweet_media() {
+        let json = r#"{
+            "data": {
+                "id": "3",
+                "text": "look at this",
+                "referenced_tweets": [{"type": "quoted", "id": "2"}]
+            },
+            "includes": {
+                "media": [
+                    {"media_key": "3_1", "type": "photo"},
+                    {"media_key": "3_7", "type": "video"}
+                ],
+                "tweets": [
+                    {"id": "2", "text": "quoted", "attachments": {"media_keys": ["3_1"]}},
+                    {"id": "5", "text": "source", "attachments": {"media_keys": ["3_7"]}}
+                ]
+            }
+        }"#;
+        let response: ApiResponse<Tweet> = serde_json::from_str(json).unwrap();
+        let includes = response.includes.as_ref().unwrap();
+        let tweet = response.data.as_ref().unwrap();
+
+        assert!(tweet.media_in(includes).is_empty());
+        let media: Vec<_> = tweet
+            .media_for(includes)
+            .iter()
+            .map(|m| &m.media_key)
+            .collect();
+        assert_eq!(media, vec!["3_1"]);
+
+        let reusing: Tweet = serde_json::from_value(serde_json::json!({
+            "id": "6",
+            "text": "reposted video",
+            "attachments": {"media_keys": ["3_7"], "media_source_tweet_id": ["5"]}
+        }))
+        .unwrap();
+        let media: Vec<_> = reusing
+            .media_for(includes)
+            .iter()
+            .map(|m| &m.media_key)
+            .collect();
+        assert_eq!(media, vec!["3_7"]);
+    }
+
+    #[test]
+    fn test_expansions_resolved_from_includes() {
+        let json = r#"{
+            "data": {
+                "id": "3",
+                "text": "look",
+                "attachments": {"media_keys": ["3_1", "3_2"]},
+                "referenced_tweets": [{"type": "quoted", "id": "2"}]
+            },
+            "includes": {
+                "media": [{"media_key": "3_2", "type": "photo"}],
+                "tweets": [{"id": "2", "text": "quoted"}]
+            }
+        }"#;
+        let response: ApiResponse<Tweet> = serde_json::from_str(json).unwrap();
+
+        let media: Vec<_> = response.media().iter().map(|m| &m.media_key).collect();
+        assert_eq!(media, vec!["3_2"]);
+        assert_eq!(
+            response
+                .referenced_tweet(ReferenceType::Quoted)
+                .unwrap()
+                .text,
+            "quoted"
+        );
+        assert!(
+            response
+                .referenced_tweet(ReferenceType::RepliedTo)
+                .is_none()
+        );
+        assert!(response.author().is_none());
+    }
+
+    #[test]
+    fn test_quoted_tweet_author_resolved_from_includes() {
+        let json = r#"{
+            "data": {
+                "id": "3",
+                "text": "so true",
+                "author_id": "30",
+                "referenced_tweets": [{"type": "quoted", "id": "2"}]
+            },
+            "includes": {
+                "tweets": [{"id": "2", "text": "quoted", "author_id": "20"}],
+                "users": [
+                    {"id": "30", "name": "Quoter", "username": "quoter"},
+                    {"id": "20", "name": "Original", "username": "original"}
+                ]
+            }
+        }"#;
+        let response: ApiResponse<Tweet> = serde_json::from_str(json).unwrap();
+
+        let quoted = response.referenced_tweet(ReferenceType::Quoted).unwrap();
+        assert_eq!(quoted.text, "quoted");
+        assert_eq!(
+            response
+                .referenced_author(ReferenceType::Quoted)
+                .unwrap()
+                .username,
+            "original"
+        );
+        assert_eq!(response.author().unwrap().username, "quoter");
+        assert!(
+            response
+                .referenced_author(ReferenceType::Retweeted)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_alt_texts_for_attached_media() {
+        let json = r#"{
+            "data": [{
+                "id": "5",
+                "text": "two pictures",
+                "attachments": {"media_keys": ["3_1", "3_2"]}
+            }],
+            "includes": {
+                "media": [
+                    {"media_key": "3_2", "type": "photo"},
+                    {"media_key": "3_1", "type": "photo", "alt_text": "A cat asleep on a keyboard"}
+                ]
+            }
+        }"#;
+        let response: ApiResponse<Vec<Tweet>> = serde_json::from_str(json).unwrap();
+        let tweet = &response.data.as_ref().unwrap()[0];
+
+        assert_eq!(
+            response.alt_texts_for(tweet),
+            vec![Some("A cat asleep on a keyboard"), None]
+        );
+    }
+
+    #[test]
+    fn test_reply_to_user_resolved_from_includes() {
+        let json = r#"{
+            "data": [
+                {"id": "7", "text": "@jack agreed", "in_reply_to_user_id": "12"},
+                {"id": "8", "text": "not a reply"}
+            ],
+            "includes": {"users": [{"id": "12", "name": "Jack", "username": "jack"}]}
+        }"#;
+        let response: ApiResponse<Vec<Tweet>> = serde_json::from_str(json).unwrap();
+        let tweets = response.data.as_ref().unwrap();
+
+        let user = response.reply_to_user_of(&tweets[0]).unwrap();
+        assert_eq!(user.username, "jack");
+        assert!(response.reply_to_user_of(&tweets[1]).is_none());
+    }
+
+    #[test]
+    fn test_created_at_from_id() {
+        let tweet: Tweet =
+            serde_json::from_str(r#"{"id":"1445078208190291973","text":"hi"}"#).unwrap();
+        let created = tweet.created_at_from_id().unwrap();
+        assert_eq!(created.to_rfc3339(), "2021-10-04T17:27:47.744+00:00");
+    }
+
+    #[test]
+    fn test_withheld_and_sensitive_accessors() {
+        let withheld: Tweet = serde_json::from_value(serde_json::json!({
+            "id": "1",
+            "text": "hi",
+            "withheld": {"copyright": false, "country_codes": ["DE", "FR"]}
+        }))
+        .unwrap();
+        assert!(withheld.is_withheld());
+        assert_eq!(withheld.withheld_countries(), ["DE", "FR"]);
+        assert!(!withheld.is_sensitive());
+
+        let sensitive: Tweet = serde_json::from_value(serde_json::json!({
+            "id": "2",
+            "text": "hi",
+            "possibly_sensitive": true
+        }))
+        .unwrap();
+        assert!(sensitive.is_sensitive());
+        assert!(!sensitive.is_withheld());
+        assert!(sensitive.withheld_countries().is_empty());
+    }
+
+    #[test]
+    fn test_best_created_at_prefers_field() {
+        let requested: Tweet = serde_json::from_str(
+            r#"{"id":"1445078208190291973","text":"hi","created_at":"2021-10-04T17:27:47.000Z"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            requested.best_created_at().unwrap().to_rfc3339(),
+            "2021-10-04T17:27:47+00:00"
+        );
+
+        let decoded: Tweet =
+            serde_json::from_str(r#"{"id":"1445078208190291973","text":"hi"}"#).unwrap();
+        assert_eq!(
+            decoded.best_created_at().unwrap().to_rfc3339(),
+            "2021-10-04T17:27:47.744+00:00"
+        );
+
+        let legacy: Tweet = serde_json::from_str(r#"{"id":"20","text":"hi"}"#).unwrap();
+        assert_eq!(legacy.best_created_at(), None);
+    }
+
+    #[test]
+    fn test_engagement_rate() {
+        let tweet: Tweet = serde_json::from_value(serde_json::json!({
+            "id": "1",
+            "text": "hi",
+            "public_metrics": {
+                "like_count": 30,
+                "retweet_count": 10,
+                "reply_count": 5,
+                "quote_count": 5,
+                "bookmark_count": 7,
+                "impression_count": 1000
+            }
+        }))
+        .unwrap();
+        assert_eq!(
+            tweet.public_metrics.as_ref().unwrap().engagement_total(),
+            50
+        );
+        assert_eq!(tweet.engagement_rate(), Some(0.05));
+
+        let no_impressions: Tweet = serde_json::from_value(serde_json::json!({
+            "id": "2",
+            "text": "hi",
+            "public_metrics": {"like_count": 3, "retweet_count": 1}
+        }))
+        .unwrap();
+        assert_eq!(
+            no_impressions
+                .public_metrics
+                .as_ref()
+                .unwrap()
+                .engagement_total(),
+            4
+        );
+        assert_eq!(no_impressions.engagement_rate(), None);
+
+        let mut unseen = no_impressions;
+        unseen.public_metrics.as_mut().unwrap().impression_count = Some(0);
+        assert_eq!(unseen.engagement_rate(), None);
+    }
+
+    #[test]
+    fn test_full_text_prefers_note_tweet() {
+        let long = "word ".repeat(100);
+        let json = serde_json::json!({
+            "id": "1",
+            "text": format!("{}…", &long[..270]),
+            "note_tweet": {
+                "text": long,
+                "entities": {"hashtags": [{"start": 0, "end": 4, "tag": "word"}]}
+            }
+        });
+        let tweet: Tweet = serde_json::from_value(json).unwrap();
+        assert_eq!(tweet.full_text(), long);
+
+        let short: Tweet = serde_json::from_str(r#"{"id":"2","text":"short"}"#).unwrap();
+        assert_eq!(short.full_text(), "short");
+    }
 }
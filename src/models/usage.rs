@@ -0,0 +1,70 @@
+//! Project usage objects
+//!
+//! # Visibility Strategy
+//!
+//! Struct fields are intentionally `pub` to support Serde serialization/deserialization.
+//! See module documentation in `models/common.rs` for rationale.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Tweet consumption of the project against its monthly cap
+///
+/// Returned by [`Client::tweet_usage`](crate::Client::tweet_usage).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TweetUsage {
+    /// Tweets consumed by the project in the current billing cycle
+    #[serde(deserialize_with = "crate::util::serde_helpers::deserialize_flexible_number")]
+    pub project_usage: u64,
+
+    /// Tweets the project may consume per billing cycle
+    #[serde(deserialize_with = "crate::util::serde_helpers::deserialize_flexible_number")]
+    pub project_cap: u64,
+
+    /// Day of the month the usage resets
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cap_reset_day: Option<u32>,
+
+    /// Identifier of the project
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+
+    /// Forward compatibility: capture unknown fields
+    #[serde(
+        flatten,
+        deserialize_with = "crate::util::serde_helpers::deserialize_unknown_fields"
+    )]
+    pub additional_fields: HashMap<String, serde_json::Value>,
+}
+
+impl TweetUsage {
+    /// Share of the cap consumed, from 0.0 (can exceed 1.0)
+    ///
+    /// A zero cap counts as fully consumed.
+    pub fn usage_ratio(&self) -> f64 {
+        if self.project_cap == 0 {
+            return 1.0;
+        }
+        self.project_usage as f64 / self.project_cap as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_usage_parsed_from_strings() {
+        let json = r#"{
+            "cap_reset_day": 19,
+            "project_id": "1234567890",
+            "project_cap": "2000000",
+            "project_usage": "1500000",
+            "daily_project_usage": {"project_id": "1234567890", "usage": []}
+        }"#;
+        let usage: TweetUsage = serde_json::from_str(json).unwrap();
+        assert_eq!(usage.project_cap, 2_000_000);
+        assert_eq!(usage.usage_ratio(), 0.75);
+        assert!(usage.additional_fields.contains_key("daily_project_usage"));
+    }
+}
@@ -5,7 +5,8 @@
 //! Struct fields are intentionally `pub` to support Serde serialization/deserialization.
 //! See module documentation in `models/common.rs` for rationale.
 
-use crate::models::common::{TweetId, UserId, Withheld};
+use crate::models::common::{ApiResponse, Includes, TweetId, UserId, Withheld};
+use crate::models::tweet::Tweet;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -43,6 +44,10 @@ pub struct User {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub pinned_tweet_id: Option<TweetId>,
 
+    /// Unique identifier of the user's most recent Tweet
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub most_recent_tweet_id: Option<TweetId>,
+
     /// The URL to the profile image for this user (normal size)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub profile_image_url: Option<String>,
@@ -71,11 +76,102 @@ pub struct User {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub withheld: Option<Withheld>,
 
+    /// Relationship between the authenticated user and this user
+    /// (user-context lookups with `user.fields=connection_status`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub connection_status: Option<Vec<ConnectionStatus>>,
+
     /// Forward compatibility: capture unknown fields
-    #[serde(flatten)]
+    #[serde(
+        flatten,
+        deserialize_with = "crate::util::serde_helpers::deserialize_unknown_fields"
+    )]
     pub additional_fields: HashMap<String, serde_json::Value>,
 }
 
+impl User {
+    /// Whether `connection_status` includes `status`
+    ///
+    /// False when `connection_status` was not requested.
+    pub fn has_connection(&self, status: ConnectionStatus) -> bool {
+        self.connection_status
+            .as_deref()
+            .is_some_and(|statuses| statuses.contains(&status))
+    }
+
+    /// Whether the authenticated user follows this user
+    pub fn is_following(&self) -> bool {
+        self.has_connection(ConnectionStatus::Following)
+    }
+
+    /// Whether this user follows the authenticated user
+    pub fn is_followed_by(&self) -> bool {
+        self.has_connection(ConnectionStatus::FollowedBy)
+    }
+
+    /// Whether the authenticated user blocks this user
+    pub fn is_blocking(&self) -> bool {
+        self.has_connection(ConnectionStatus::Blocking)
+    }
+
+    /// Whether the authenticated user mutes this user
+    pub fn is_muting(&self) -> bool {
+        self.has_connection(ConnectionStatus::Muting)
+    }
+
+    /// Find this user's pinned tweet in `includes`
+    /// (requires `expansions=pinned_tweet_id`)
+    pub fn pinned_tweet_in<'a>(&self, includes: &'a Includes) -> Option<&'a Tweet> {
+        includes.tweet(self.pinned_tweet_id.as_deref()?)
+    }
+
+    /// Find this user's most recent tweet in `includes`
+    /// (requires `expansions=most_recent_tweet_id`)
+    pub fn most_recent_tweet_in<'a>(&self, includes: &'a Includes) -> Option<&'a Tweet> {
+        includes.tweet(self.most_recent_tweet_id.as_deref()?)
+    }
+}
+
+impl<T> ApiResponse<T> {
+    /// The pinned tweet of `user`, if expanded in this response
+    ///
+    /// Works for the user in `data` as well as for users in `includes`.
+    pub fn pinned_tweet_of(&self, user: &User) -> Option<&Tweet> {
+        user.pinned_tweet_in(self.includes.as_ref()?)
+    }
+
+    /// The most recent tweet of `user`, if expanded in this response
+    ///
+    /// Works for the user in `data` as well as for users in `includes`.
+    pub fn most_recent_tweet_of(&self, user: &User) -> Option<&Tweet> {
+        user.most_recent_tweet_in(self.includes.as_ref()?)
+    }
+}
+
+/// Relationship flag in a user's `connection_status`
+///
+/// Values this crate does not know map to [`Other`](Self::Other).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum ConnectionStatus {
+    /// The authenticated user follows this user
+    Following,
+    /// This user follows the authenticated user
+    FollowedBy,
+    /// The authenticated user asked to follow this (protected) user
+    FollowRequestSent,
+    /// This user asked to follow the authenticated user
+    FollowRequestReceived,
+    /// The authenticated user blocks this user
+    Blocking,
+    /// The authenticated user mutes this user
+    Muting,
+    /// A relationship this version does not know about
+    #[serde(other)]
+    Other,
+}
+
 /// User engagement metrics
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(default)]
@@ -143,8 +239,8 @@ pub struct UserDescriptionEntity {
 }
 
 /// Type of user verification
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
 #[non_exhaustive]
 pub enum VerifiedType {
     /// Blue checkmark (verified)
@@ -155,6 +251,39 @@ pub enum VerifiedType {
     Business,
     /// No verification
     None,
+    /// A type this version does not know about
+    Unknown(String),
+}
+
+impl VerifiedType {
+    /// Value sent in `verified_type`
+    pub fn as_str(&self) -> &str {
+        match self {
+            VerifiedType::Blue => "blue",
+            VerifiedType::Government => "government",
+            VerifiedType::Business => "business",
+            VerifiedType::None => "none",
+            VerifiedType::Unknown(value) => value,
+        }
+    }
+}
+
+impl From<String> for VerifiedType {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "blue" => VerifiedType::Blue,
+            "government" => VerifiedType::Government,
+            "business" => VerifiedType::Business,
+            "none" => VerifiedType::None,
+            _ => VerifiedType::Unknown(value),
+        }
+    }
+}
+
+impl From<VerifiedType> for String {
+    fn from(value: VerifiedType) -> Self {
+        value.as_str().to_string()
+    }
 }
 
 #[cfg(test)]
@@ -179,6 +308,26 @@ mod tests {
         assert_eq!(user.id, roundtrip.id);
     }
 
+    #[test]
+    fn test_most_recent_tweet_resolved_from_includes() {
+        let json = r#"{
+            "data": {
+                "id": "2244994945",
+                "name": "X Dev",
+                "username": "XDevelopers",
+                "most_recent_tweet_id": "1732815485935063346"
+            },
+            "includes": {
+                "tweets": [{"id": "1732815485935063346", "text": "latest"}]
+            }
+        }"#;
+        let response: ApiResponse<User> = serde_json::from_str(json).unwrap();
+        let user = response.data.as_ref().unwrap();
+
+        assert_eq!(response.most_recent_tweet_of(user).unwrap().text, "latest");
+        assert!(response.pinned_tweet_of(user).is_none());
+    }
+
     #[test]
     fn test_user_unknown_fields_captured() {
         let json = r#"{
@@ -223,4 +372,21 @@ mod tests {
         let json = serde_json::to_string(&vtype).unwrap();
         assert_eq!(json, r#""government""#);
     }
+
+    #[test]
+    fn test_connection_status_helpers() {
+        let json = r#"{
+            "id": "783214",
+            "name": "X",
+            "username": "X",
+            "connection_status": ["following", "followed_by", "something_new"]
+        }"#;
+
+        let user: User = serde_json::from_str(json).unwrap();
+        assert!(user.is_following());
+        assert!(user.is_followed_by());
+        assert!(!user.is_blocking());
+        assert!(!user.is_muting());
+        assert!(user.has_connection(ConnectionStatus::Other));
+    }
 }
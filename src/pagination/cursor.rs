@@ -1 +1,304 @@
 //! Cursor-based pagination
+
+use crate::client::{Client, HttpClient};
+use crate::error::{Error, Result};
+use crate::models::common::{ApiResponse, ResponseMeta};
+use reqwest::Method;
+use serde::de::DeserializeOwned;
+use std::time::Duration;
+use tokio_stream::Stream;
+
+/// Query parameter carrying the cursor of the page to fetch
+const PAGINATION_TOKEN_PARAM: &str = "pagination_token";
+
+/// Query parameter carrying the cursor of the page to fetch on search and
+/// counts endpoints
+const NEXT_TOKEN_PARAM: &str = "next_token";
+
+/// `meta` field carrying the cursor of the next page
+const NEXT_TOKEN_FIELD: &str = "next_token";
+
+/// Names of the cursor fields used by a paginated endpoint
+///
+/// Most endpoints take the cursor as `pagination_token` and return the next
+/// one in `meta.next_token`, but search and counts endpoints take
+/// `next_token`, and a few name the response field differently. Endpoints
+/// declare their spec instead of hard-coding the names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct PaginationSpec {
+    /// Query parameter carrying the cursor of the page to fetch
+    pub(crate) request_param: &'static str,
+
+    /// `meta` field carrying the cursor of the next page
+    pub(crate) response_field: &'static str,
+}
+
+impl PaginationSpec {
+    /// `pagination_token` in, `meta.next_token` out (timelines, lists, rules, DMs)
+    pub(crate) const PAGINATION_TOKEN: Self = Self {
+        request_param: PAGINATION_TOKEN_PARAM,
+        response_field: NEXT_TOKEN_FIELD,
+    };
+
+    /// `next_token` in, `meta.next_token` out (search and counts)
+    pub(crate) const NEXT_TOKEN: Self = Self {
+        request_param: NEXT_TOKEN_PARAM,
+        response_field: NEXT_TOKEN_FIELD,
+    };
+
+    /// Query parameter requesting the page at `token`
+    pub(crate) fn param(&self, token: &str) -> (String, String) {
+        (self.request_param.to_string(), token.to_string())
+    }
+
+    /// Cursor of the next page, if `meta` has one
+    pub(crate) fn next_token(&self, meta: Option<&ResponseMeta>) -> Option<String> {
+        let meta = meta?;
+        if self.response_field == NEXT_TOKEN_FIELD {
+            return meta.next_token.clone();
+        }
+        meta.additional_fields
+            .get(self.response_field)?
+            .as_str()
+            .map(str::to_string)
+    }
+}
+
+/// Query parameters of a paginated request
+///
+/// Parameters added with an options type's `extra_query` are kept apart so
+/// that they always come last, after the typed parameters and the page
+/// token.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct PageQuery {
+    typed: Vec<(String, String)>,
+    extra: Vec<(String, String)>,
+}
+
+impl PageQuery {
+    pub(crate) fn new(typed: Vec<(String, String)>, extra: &[(String, String)]) -> Self {
+        Self {
+            typed,
+            extra: extra.to_vec(),
+        }
+    }
+
+    /// Parameters requesting the page at `token`, named as `spec` says
+    pub(crate) fn page(&self, spec: PaginationSpec, token: Option<&str>) -> Vec<(String, String)> {
+        self.typed
+            .iter()
+            .cloned()
+            .chain(token.map(|token| spec.param(token)))
+            .chain(self.extra.iter().cloned())
+            .collect()
+    }
+}
+
+/// Fail if the API handed back the token of the page just fetched
+///
+/// Following it would loop forever.
+fn ensure_advanced(current: Option<&String>, next: &str) -> Result<()> {
+    if current.is_some_and(|current| current == next) {
+        return Err(Error::InvalidResponse(format!(
+            "Pagination did not advance past token {}",
+            next
+        )));
+    }
+    Ok(())
+}
+
+impl<H: HttpClient + Clone> Client<H> {
+    /// Fetch every page of a paginated GET endpoint
+    ///
+    /// Follows the cursor named by `spec` until a page without one, starting
+    /// from `first_token` if given. Pages without `data` contribute no items.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first request error, or [`Error::InvalidResponse`] if the
+    /// API hands back the token of the page just fetched (which would
+    /// otherwise loop forever).
+    pub(crate) async fn get_all_pages<T>(
+        &self,
+        path: &str,
+        spec: PaginationSpec,
+        query: PageQuery,
+        first_token: Option<String>,
+        resource_id: Option<&str>,
+    ) -> Result<Vec<T>>
+    where
+        T: DeserializeOwned,
+    {
+        let mut items = Vec::new();
+        let mut token = first_token;
+
+        loop {
+            let page_query = query.page(spec, token.as_deref());
+            let page: ApiResponse<Vec<T>> = self
+                .send(Method::GET, path, &page_query, None, resource_id)
+                .await?;
+            let next = spec.next_token(page.meta.as_ref());
+            items.extend(page.data.unwrap_or_default());
+
+            match next {
+                None => return Ok(items),
+                Some(next) => {
+                    ensure_advanced(token.as_ref(), &next)?;
+                    token = Some(next);
+                }
+            }
+        }
+    }
+
+    /// Stream the pages of a paginated GET endpoint
+    ///
+    /// Pages are requested lazily as the stream is polled, waiting
+    /// `page_interval` between requests on top of the per-endpoint rate
+    /// limiting applied to every request. Starts from `first_token` if
+    /// given. The stream ends after a page without the cursor named by
+    /// `spec`, or after the first error.
+    pub(crate) fn stream_pages<'a, T>(
+        &'a self,
+        path: &'a str,
+        spec: PaginationSpec,
+        query: PageQuery,
+        first_token: Option<String>,
+        page_interval: Duration,
+    ) -> impl Stream<Item = Result<ApiResponse<Vec<T>>>> + 'a
+    where
+        T: DeserializeOwned + 'a,
+    {
+        async_stream::try_stream! {
+            let mut token = first_token;
+            loop {
+                let page_query = query.page(spec, token.as_deref());
+                let page: ApiResponse<Vec<T>> =
+                    self.send(Method::GET, path, &page_query, None, None).await?;
+                let next = spec.next_token(page.meta.as_ref());
+                yield page;
+
+                match next {
+                    None => break,
+                    Some(next) => {
+                        ensure_advanced(token.as_ref(), &next)?;
+                        token = Some(next);
+                    }
+                }
+                self.clock().sleep(page_interval).await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio_stream::StreamExt;
+    use wiremock::matchers::{method, path, query_param, query_param_is_missing};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const PAGED_PATH: &str = "/2/users/12/owned_lists";
+
+    fn client(server: &MockServer) -> Client {
+        Client::builder()
+            .oauth1("ck", "cs", "at", "ats")
+            .base_url(server.uri())
+            .build()
+            .unwrap()
+    }
+
+    fn page(ids: &[&str], next_token: Option<&str>) -> ResponseTemplate {
+        let data: Vec<_> = ids.iter().map(|id| serde_json::json!({"id": id})).collect();
+        let mut meta = serde_json::json!({"result_count": ids.len()});
+        if let Some(token) = next_token {
+            meta["next_token"] = serde_json::json!(token);
+        }
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({"data": data, "meta": meta}))
+    }
+
+    fn ids(items: &[serde_json::Value]) -> Vec<&str> {
+        items
+            .iter()
+            .map(|item| item["id"].as_str().unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_pagination_token_request_next_token_response() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(PAGED_PATH))
+            .and(query_param_is_missing("pagination_token"))
+            .respond_with(page(&["1", "2"], Some("p2")))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(PAGED_PATH))
+            .and(query_param("pagination_token", "p2"))
+            .and(query_param_is_missing("next_token"))
+            .respond_with(page(&["3"], None))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let items: Vec<serde_json::Value> = client(&server)
+            .get_all_pages(
+                PAGED_PATH,
+                PaginationSpec::PAGINATION_TOKEN,
+                PageQuery::default(),
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(ids(&items), vec!["1", "2", "3"]);
+    }
+
+    #[tokio::test]
+    async fn test_next_token_request_next_token_response() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(PAGED_PATH))
+            .and(query_param_is_missing("next_token"))
+            .respond_with(page(&["1"], Some("n2")))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(PAGED_PATH))
+            .and(query_param("next_token", "n2"))
+            .and(query_param_is_missing("pagination_token"))
+            .respond_with(page(&["2"], None))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = client(&server);
+        let pages: Vec<ApiResponse<Vec<serde_json::Value>>> = client
+            .stream_pages(
+                PAGED_PATH,
+                PaginationSpec::NEXT_TOKEN,
+                PageQuery::default(),
+                None,
+                Duration::ZERO,
+            )
+            .collect::<Result<_>>()
+            .await
+            .unwrap();
+        let items: Vec<_> = pages.into_iter().flat_map(|p| p.data.unwrap()).collect();
+        assert_eq!(ids(&items), vec!["1", "2"]);
+    }
+
+    #[test]
+    fn test_custom_response_field() {
+        let spec = PaginationSpec {
+            request_param: PAGINATION_TOKEN_PARAM,
+            response_field: "pagination_token",
+        };
+        let meta: ResponseMeta =
+            serde_json::from_value(serde_json::json!({"pagination_token": "c2"})).unwrap();
+        assert_eq!(spec.next_token(Some(&meta)).as_deref(), Some("c2"));
+        assert_eq!(PaginationSpec::NEXT_TOKEN.next_token(Some(&meta)), None);
+    }
+}
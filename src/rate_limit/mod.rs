@@ -57,6 +57,14 @@ impl RateLimitConfig {
         self.auto_wait
     }
 
+    /// Copy of this configuration with auto-wait disabled
+    pub(crate) fn without_auto_wait(&self) -> Self {
+        Self {
+            auto_wait: false,
+            ..self.clone()
+        }
+    }
+
     /// Create a custom rate limit configuration with builder pattern
     pub fn custom() -> RateLimitConfigBuilder {
         RateLimitConfigBuilder::default()
@@ -1 +1,283 @@
 //! Rate limit state tracking
+//!
+//! Tracks the per-endpoint state reported by the X API in the
+//! `x-rate-limit-limit`, `x-rate-limit-remaining`, and `x-rate-limit-reset`
+//! response headers, so that requests to an exhausted endpoint can wait for
+//! the window to reset instead of being rejected with HTTP 429.
+
+use crate::error::{Error, Result};
+use crate::util::lock_unpoisoned;
+use chrono::{DateTime, Utc};
+use reqwest::header::HeaderMap;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Header carrying the request limit for the current window
+pub const LIMIT_HEADER: &str = "x-rate-limit-limit";
+
+/// Header carrying the number of requests left in the current window
+pub const REMAINING_HEADER: &str = "x-rate-limit-remaining";
+
+/// Header carrying the window reset time in epoch seconds
+pub const RESET_HEADER: &str = "x-rate-limit-reset";
+
+/// Index of the API version segment in a split path (`["", "2", ...]`)
+const VERSION_SEGMENT: usize = 1;
+
+/// Rate limit state of a single endpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitState {
+    /// Total requests allowed in the window
+    pub limit: u32,
+
+    /// Requests left in the window
+    pub remaining: u32,
+
+    /// When the window resets
+    pub reset_at: DateTime<Utc>,
+}
+
+impl RateLimitState {
+    /// Parse the rate limit headers of a response
+    ///
+    /// Returns `Ok(None)` when the response carries no rate limit headers.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidResponse`] if only some of the headers are
+    /// present or a header value is malformed.
+    pub fn from_headers(headers: &HeaderMap) -> Result<Option<Self>> {
+        let limit = header_value(headers, LIMIT_HEADER)?;
+        let remaining = header_value(headers, REMAINING_HEADER)?;
+        let reset = header_value(headers, RESET_HEADER)?;
+
+        match (limit, remaining, reset) {
+            (None, None, None) => Ok(None),
+            (Some(limit), Some(remaining), Some(reset)) => {
+                let reset_at = DateTime::from_timestamp(reset, 0).ok_or_else(|| {
+                    Error::InvalidResponse(format!("Invalid {} value: {}", RESET_HEADER, reset))
+                })?;
+                Ok(Some(Self {
+                    limit: to_u32(limit, LIMIT_HEADER)?,
+                    remaining: to_u32(remaining, REMAINING_HEADER)?,
+                    reset_at,
+                }))
+            }
+            _ => Err(Error::InvalidResponse(
+                "Incomplete x-rate-limit-* headers in response".to_string(),
+            )),
+        }
+    }
+
+    /// Time left until the window resets, if the endpoint is exhausted
+    pub fn wait_duration(&self, now: DateTime<Utc>) -> Option<Duration> {
+        if self.remaining > 0 || self.reset_at <= now {
+            return None;
+        }
+        (self.reset_at - now).to_std().ok()
+    }
+
+    /// Build the error reported when this endpoint is exhausted
+    pub fn exceeded_error(&self, endpoint: impl Into<String>) -> Error {
+        Error::RateLimitExceeded {
+            reset_at: self.reset_at,
+            endpoint: endpoint.into(),
+            remaining: self.remaining,
+            limit: self.limit,
+        }
+    }
+}
+
+/// Per-endpoint rate limit tracker
+///
+/// Endpoints are keyed by [`endpoint_key`], which folds numeric path segments
+/// into `:id` so that e.g. every `GET /2/tweets/:id` lookup shares one window,
+/// matching how the X API accounts for them.
+///
+/// The tracker is internally synchronized and meant to be shared via `Arc`
+/// between clones of a [`Client`](crate::Client).
+#[derive(Debug, Default)]
+pub struct RateLimitTracker {
+    states: Mutex<HashMap<String, RateLimitState>>,
+}
+
+impl RateLimitTracker {
+    /// Create an empty tracker
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the rate limit state reported in a response's headers
+    ///
+    /// Responses without rate limit headers leave the tracked state untouched.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the headers are malformed (see
+    /// [`RateLimitState::from_headers`]).
+    pub fn update_from_headers(
+        &self,
+        endpoint: &str,
+        headers: &HeaderMap,
+    ) -> Result<Option<RateLimitState>> {
+        let state = RateLimitState::from_headers(headers)?;
+        if let Some(state) = state {
+            lock_unpoisoned(&self.states).insert(endpoint.to_string(), state);
+        }
+        Ok(state)
+    }
+
+    /// Get the last known state of an endpoint
+    pub fn state(&self, endpoint: &str) -> Option<RateLimitState> {
+        lock_unpoisoned(&self.states).get(endpoint).copied()
+    }
+
+    /// Time to wait before calling an endpoint, if it is currently exhausted
+    pub fn wait_duration(&self, endpoint: &str, now: DateTime<Utc>) -> Option<Duration> {
+        self.state(endpoint)
+            .and_then(|state| state.wait_duration(now))
+    }
+
+    /// Forget all tracked state
+    pub fn clear(&self) {
+        lock_unpoisoned(&self.states).clear();
+    }
+}
+
+/// Rate limit key for a request: method plus path with numeric IDs folded
+///
+/// `GET /2/tweets/1234` becomes `GET /2/tweets/:id`. The leading API version
+/// segment is kept as is.
+pub fn endpoint_key(method: &reqwest::Method, path: &str) -> String {
+    let path = path
+        .split('/')
+        .enumerate()
+        .map(|(i, segment)| {
+            let is_id = !segment.is_empty() && segment.bytes().all(|b| b.is_ascii_digit());
+            if i > VERSION_SEGMENT && is_id {
+                ":id"
+            } else {
+                segment
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/");
+    format!("{} {}", method, path)
+}
+
+fn header_value(headers: &HeaderMap, name: &str) -> Result<Option<i64>> {
+    let Some(value) = headers.get(name) else {
+        return Ok(None);
+    };
+    value
+        .to_str()
+        .ok()
+        .and_then(|v| v.trim().parse::<i64>().ok())
+        .map(Some)
+        .ok_or_else(|| Error::InvalidResponse(format!("Invalid {} header: {:?}", name, value)))
+}
+
+fn to_u32(value: i64, name: &str) -> Result<u32> {
+    u32::try_from(value)
+        .map_err(|_| Error::InvalidResponse(format!("Invalid {} value: {}", name, value)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    fn headers(limit: &str, remaining: &str, reset: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(LIMIT_HEADER, HeaderValue::from_str(limit).unwrap());
+        headers.insert(REMAINING_HEADER, HeaderValue::from_str(remaining).unwrap());
+        headers.insert(RESET_HEADER, HeaderValue::from_str(reset).unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_parse_headers() {
+        let state = RateLimitState::from_headers(&headers("900", "899", "1700000000"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(state.limit, 900);
+        assert_eq!(state.remaining, 899);
+        assert_eq!(state.reset_at.timestamp(), 1_700_000_000);
+    }
+
+    #[test]
+    fn test_exceeded_error_keeps_header_epoch() {
+        let state = RateLimitState::from_headers(&headers("900", "0", "1700000000"))
+            .unwrap()
+            .unwrap();
+        let err = state.exceeded_error("GET /2/tweets/:id");
+
+        assert_eq!(err.reset_at_epoch(), Some(1_700_000_000));
+        match err {
+            Error::RateLimitExceeded { reset_at, .. } => {
+                assert_eq!(reset_at.to_rfc3339(), "2023-11-14T22:13:20+00:00");
+            }
+            other => panic!("Expected RateLimitExceeded, got {:?}", other),
+        }
+        assert_eq!(Error::NotFound("tweet".to_string()).reset_at_epoch(), None);
+    }
+
+    #[test]
+    fn test_missing_headers_are_not_an_error() {
+        assert_eq!(
+            RateLimitState::from_headers(&HeaderMap::new()).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_malformed_headers_are_rejected() {
+        assert!(RateLimitState::from_headers(&headers("900", "lots", "1700000000")).is_err());
+
+        let mut partial = HeaderMap::new();
+        partial.insert(LIMIT_HEADER, HeaderValue::from_static("900"));
+        assert!(RateLimitState::from_headers(&partial).is_err());
+    }
+
+    #[test]
+    fn test_wait_only_when_exhausted() {
+        let now = Utc::now();
+        let reset = (now + chrono::Duration::seconds(30))
+            .timestamp()
+            .to_string();
+        let tracker = RateLimitTracker::new();
+
+        tracker
+            .update_from_headers("GET /2/tweets/:id", &headers("900", "1", &reset))
+            .unwrap();
+        assert_eq!(tracker.wait_duration("GET /2/tweets/:id", now), None);
+
+        tracker
+            .update_from_headers("GET /2/tweets/:id", &headers("900", "0", &reset))
+            .unwrap();
+        let wait = tracker.wait_duration("GET /2/tweets/:id", now).unwrap();
+        assert!(wait <= Duration::from_secs(30));
+        assert!(wait > Duration::from_secs(28));
+
+        // Past the reset time the endpoint is usable again
+        let later = now + chrono::Duration::seconds(31);
+        assert_eq!(tracker.wait_duration("GET /2/tweets/:id", later), None);
+    }
+
+    #[test]
+    fn test_endpoint_key_folds_ids() {
+        assert_eq!(
+            endpoint_key(&reqwest::Method::GET, "/2/tweets/1234"),
+            "GET /2/tweets/:id"
+        );
+        assert_eq!(
+            endpoint_key(&reqwest::Method::POST, "/2/users/42/likes"),
+            "POST /2/users/:id/likes"
+        );
+        assert_eq!(
+            endpoint_key(&reqwest::Method::GET, "/2/tweets"),
+            "GET /2/tweets"
+        );
+    }
+}
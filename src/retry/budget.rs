@@ -0,0 +1,253 @@
+//! Client-wide retry budget
+//!
+//! [`RetryPolicy`](super::policy::RetryPolicy) bounds the retries of a single
+//! request. A [`RetryBudget`] bounds the retries of all requests together, so
+//! a burst of failures cannot multiply the load sent to an unhealthy API:
+//! each request deposits a fraction of a retry into the budget, each retry
+//! withdraws a whole one, and deposits expire after a time window.
+
+use crate::error::{Error, Result};
+use crate::util::lock_unpoisoned;
+use chrono::{DateTime, Utc};
+use std::collections::VecDeque;
+use std::sync::{Mutex, MutexGuard};
+use std::time::Duration;
+
+/// Default share of requests that may be retried
+const DEFAULT_RATIO: f64 = 0.1;
+
+/// Default window over which requests and retries are counted
+const DEFAULT_WINDOW: Duration = Duration::from_secs(60);
+
+/// Default number of retries allowed per window regardless of traffic
+const DEFAULT_MIN_RETRIES: u32 = 10;
+
+/// Retry budget configuration
+///
+/// Retries within any `window` are limited to `min_retries` plus `ratio`
+/// times the number of requests started in that window.
+///
+/// # Example
+///
+/// ```rust
+/// use x_api_client::retry::budget::RetryBudget;
+/// use std::time::Duration;
+///
+/// // Retry at most 20% of requests over the last 30 seconds
+/// let budget = RetryBudget::custom()
+///     .ratio(0.2)
+///     .window(Duration::from_secs(30))
+///     .min_retries(0)
+///     .build()?;
+/// assert_eq!(budget.ratio(), 0.2);
+/// # Ok::<(), x_api_client::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryBudget {
+    /// Retries allowed per request (default: 0.1)
+    ratio: f64,
+
+    /// Window over which requests and retries are counted (default: 60 seconds)
+    window: Duration,
+
+    /// Retries allowed per window regardless of traffic (default: 10)
+    min_retries: u32,
+}
+
+impl Default for RetryBudget {
+    fn default() -> Self {
+        Self {
+            ratio: DEFAULT_RATIO,
+            window: DEFAULT_WINDOW,
+            min_retries: DEFAULT_MIN_RETRIES,
+        }
+    }
+}
+
+impl RetryBudget {
+    /// Create a retry budget with default values
+    ///
+    /// Defaults:
+    /// - ratio: 0.1 (10% of requests)
+    /// - window: 60 seconds
+    /// - min_retries: 10
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a custom retry budget with builder pattern
+    pub fn custom() -> RetryBudgetBuilder {
+        RetryBudgetBuilder::default()
+    }
+
+    /// Get the number of retries allowed per request
+    pub fn ratio(&self) -> f64 {
+        self.ratio
+    }
+
+    /// Get the window over which requests and retries are counted
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    /// Get the number of retries allowed per window regardless of traffic
+    pub fn min_retries(&self) -> u32 {
+        self.min_retries
+    }
+}
+
+/// Builder for creating custom retry budgets with validation
+#[derive(Debug, Default)]
+pub struct RetryBudgetBuilder {
+    budget: RetryBudget,
+}
+
+impl RetryBudgetBuilder {
+    /// Set the number of retries allowed per request
+    pub fn ratio(mut self, ratio: f64) -> Self {
+        self.budget.ratio = ratio;
+        self
+    }
+
+    /// Set the window over which requests and retries are counted
+    pub fn window(mut self, window: Duration) -> Self {
+        self.budget.window = window;
+        self
+    }
+
+    /// Set the number of retries allowed per window regardless of traffic
+    pub fn min_retries(mut self, min: u32) -> Self {
+        self.budget.min_retries = min;
+        self
+    }
+
+    /// Build the retry budget
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - ratio is negative, NaN, or Infinity
+    /// - window is zero
+    pub fn build(self) -> Result<RetryBudget> {
+        if self.budget.ratio < 0.0 || !self.budget.ratio.is_finite() {
+            return Err(Error::Config(
+                "Retry budget ratio must be non-negative and finite".to_string(),
+            ));
+        }
+
+        if self.budget.window.is_zero() {
+            return Err(Error::Config(
+                "Retry budget window must be non-zero".to_string(),
+            ));
+        }
+
+        Ok(self.budget)
+    }
+}
+
+/// Requests and retries counted against a [`RetryBudget`], shared between
+/// client clones
+#[derive(Debug)]
+pub(crate) struct RetryBudgetTracker {
+    budget: RetryBudget,
+    ledger: Mutex<Ledger>,
+}
+
+/// Start times of the requests and retries in the current window
+#[derive(Debug, Default)]
+struct Ledger {
+    requests: VecDeque<DateTime<Utc>>,
+    retries: VecDeque<DateTime<Utc>>,
+}
+
+impl Ledger {
+    /// Forget events that happened before `cutoff`
+    fn expire(&mut self, cutoff: DateTime<Utc>) {
+        for events in [&mut self.requests, &mut self.retries] {
+            while events.front().is_some_and(|&at| at < cutoff) {
+                events.pop_front();
+            }
+        }
+    }
+}
+
+impl RetryBudgetTracker {
+    pub(crate) fn new(budget: RetryBudget) -> Self {
+        Self {
+            budget,
+            ledger: Mutex::new(Ledger::default()),
+        }
+    }
+
+    fn ledger(&self, now: DateTime<Utc>) -> MutexGuard<'_, Ledger> {
+        let mut ledger = lock_unpoisoned(&self.ledger);
+        let window =
+            chrono::Duration::from_std(self.budget.window).unwrap_or(chrono::TimeDelta::MAX);
+        ledger.expire(
+            now.checked_sub_signed(window)
+                .unwrap_or(DateTime::<Utc>::MIN_UTC),
+        );
+        ledger
+    }
+
+    /// Record the start of a request, depositing into the budget
+    pub(crate) fn record_request(&self, now: DateTime<Utc>) {
+        self.ledger(now).requests.push_back(now);
+    }
+
+    /// Withdraw one retry from the budget
+    ///
+    /// Returns `false`, recording nothing, if the budget is exhausted.
+    pub(crate) fn try_withdraw(&self, now: DateTime<Utc>) -> bool {
+        let mut ledger = self.ledger(now);
+        let earned = (ledger.requests.len() as f64 * self.budget.ratio).floor() as usize;
+        let allowed = earned.saturating_add(self.budget.min_retries as usize);
+        if ledger.retries.len() >= allowed {
+            return false;
+        }
+        ledger.retries.push_back(now);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_withdrawals_expire_with_window() {
+        let budget = RetryBudget::custom()
+            .ratio(0.5)
+            .min_retries(0)
+            .build()
+            .unwrap();
+        let tracker = RetryBudgetTracker::new(budget);
+        let start = Utc::now();
+
+        tracker.record_request(start);
+        assert!(!tracker.try_withdraw(start));
+        tracker.record_request(start);
+        assert!(tracker.try_withdraw(start));
+        assert!(!tracker.try_withdraw(start));
+
+        // The deposits expire along with the withdrawal
+        let later = start + chrono::Duration::from_std(DEFAULT_WINDOW).unwrap() * 2;
+        assert!(!tracker.try_withdraw(later));
+        tracker.record_request(later);
+        tracker.record_request(later);
+        assert!(tracker.try_withdraw(later));
+    }
+
+    #[test]
+    fn test_builder_validation() {
+        assert!(RetryBudget::custom().ratio(-0.1).build().is_err());
+        assert!(RetryBudget::custom().ratio(f64::NAN).build().is_err());
+        assert!(
+            RetryBudget::custom()
+                .window(Duration::ZERO)
+                .build()
+                .is_err()
+        );
+        assert_eq!(RetryBudget::custom().build().unwrap(), RetryBudget::new());
+    }
+}
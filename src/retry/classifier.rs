@@ -1 +1,62 @@
 //! Error classification for retry logic
+
+use crate::error::Error;
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+
+/// What to do after a failed request attempt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryDecision {
+    /// Retry after exactly this delay (e.g. until a rate limit window resets)
+    RetryAfter(Duration),
+
+    /// Retry after the next exponential backoff interval
+    Backoff,
+
+    /// The error is permanent; do not retry
+    DoNotRetry,
+}
+
+/// Classify an error into a retry decision
+///
+/// Built on [`Error::is_retryable`] and [`Error::retry_after_at`], so the
+/// classification stays consistent with those helpers. Waits are computed
+/// relative to `now`.
+pub fn classify(error: &Error, now: DateTime<Utc>) -> RetryDecision {
+    if !error.is_retryable() {
+        return RetryDecision::DoNotRetry;
+    }
+    match error.retry_after_at(now) {
+        Some(delay) => RetryDecision::RetryAfter(delay),
+        None => RetryDecision::Backoff,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ApiErrorDetail;
+
+    #[test]
+    fn test_classify() {
+        let now = Utc::now();
+        let server_error: Error = ApiErrorDetail::new("503", "unavailable")
+            .with_status(503)
+            .into();
+        assert_eq!(classify(&server_error, now), RetryDecision::Backoff);
+
+        let not_found = Error::NotFound("tweet".to_string());
+        assert_eq!(classify(&not_found, now), RetryDecision::DoNotRetry);
+
+        let rate_limited = Error::RateLimitExceeded {
+            reset_at: now + chrono::Duration::seconds(30),
+            endpoint: "GET /2/tweets/:id".to_string(),
+            remaining: 0,
+            limit: 900,
+        };
+        assert_eq!(
+            classify(&rate_limited, now),
+            RetryDecision::RetryAfter(Duration::from_secs(30))
+        );
+    }
+}
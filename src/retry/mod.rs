@@ -4,6 +4,8 @@
 //! - Intelligent error classification
 //! - Exponential backoff with jitter
 //! - Request deduplication
+//! - A client-wide retry budget
 
+pub mod budget;
 pub mod classifier;
 pub mod policy;
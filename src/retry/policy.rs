@@ -2,6 +2,9 @@
 
 use std::time::Duration;
 
+/// Randomization applied to backoff intervals when jitter is enabled (±50%)
+const JITTER_RANDOMIZATION_FACTOR: f64 = 0.5;
+
 /// Retry policy configuration for failed requests
 ///
 /// Configures the exponential backoff retry behavior for transient errors.
@@ -91,6 +94,24 @@ impl RetryPolicy {
     pub fn custom() -> RetryPolicyBuilder {
         RetryPolicyBuilder::default()
     }
+
+    /// Create a fresh backoff schedule following this policy
+    ///
+    /// The schedule never gives up on its own; the number of attempts is
+    /// bounded by [`max_retries`](Self::max_retries).
+    pub(crate) fn backoff(&self) -> backoff::ExponentialBackoff {
+        backoff::ExponentialBackoffBuilder::new()
+            .with_initial_interval(self.initial_interval)
+            .with_max_interval(self.max_interval)
+            .with_multiplier(self.multiplier)
+            .with_randomization_factor(if self.jitter {
+                JITTER_RANDOMIZATION_FACTOR
+            } else {
+                0.0
+            })
+            .with_max_elapsed_time(None)
+            .build()
+    }
 }
 
 /// Builder for creating custom retry policies with validation
@@ -0,0 +1,132 @@
+//! Compliance streams
+//!
+//! The compliance streams deliver, in real time, the events a store of tweets
+//! and users must apply to stay compliant: deletions, edits, withholdings,
+//! suspensions and so on. Each stream is split into partitions; connect once
+//! per partition to receive every event.
+
+use crate::client::{Client, HttpClient};
+use crate::endpoints::paths;
+use crate::error::{Error, Result};
+use crate::models::compliance::ComplianceMessage;
+use crate::streaming::reconnect::ReconnectingStream;
+use crate::streaming::{PARTITION_PARAM, StreamOptions};
+
+/// Number of partitions of each compliance stream
+pub const COMPLIANCE_PARTITIONS: u8 = 4;
+
+/// Which compliance stream to connect to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ComplianceKind {
+    /// Tweet events (delete, edit, withheld, drop, ...)
+    Tweets,
+    /// User events (user_delete, user_suspend, user_protect, ...)
+    Users,
+}
+
+impl ComplianceKind {
+    /// Path of the stream
+    fn path(self) -> &'static str {
+        match self {
+            ComplianceKind::Tweets => paths::TWEETS_COMPLIANCE_STREAM,
+            ComplianceKind::Users => paths::USERS_COMPLIANCE_STREAM,
+        }
+    }
+}
+
+impl<H: HttpClient + Clone + 'static> Client<H> {
+    /// Connect to one partition of a compliance stream, reconnecting after
+    /// disconnects
+    ///
+    /// Requires an auth provider supporting app-only authentication.
+    /// `partition` ranges from 1 to [`COMPLIANCE_PARTITIONS`]. Reconnects like
+    /// [`filtered_stream_reconnecting`](Self::filtered_stream_reconnecting).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidRequest`] if `partition` is out of range.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use tokio_stream::StreamExt;
+    /// use x_api_client::models::compliance::ComplianceEvent;
+    /// use x_api_client::streaming::StreamOptions;
+    /// use x_api_client::streaming::compliance::ComplianceKind;
+    ///
+    /// let mut stream = client.compliance_stream(ComplianceKind::Tweets, 1, &StreamOptions::new())?;
+    /// while let Some(message) = stream.next().await {
+    ///     if let ComplianceEvent::Delete(event) = message?.data {
+    ///         store.remove_tweet(&event.tweet.id);
+    ///     }
+    /// }
+    /// ```
+    pub fn compliance_stream(
+        &self,
+        kind: ComplianceKind,
+        partition: u8,
+        options: &StreamOptions,
+    ) -> Result<ReconnectingStream<ComplianceMessage>> {
+        if !(1..=COMPLIANCE_PARTITIONS).contains(&partition) {
+            return Err(Error::InvalidRequest(format!(
+                "Compliance stream partition must be between 1 and {}, got {}",
+                COMPLIANCE_PARTITIONS, partition
+            )));
+        }
+        let query = vec![(PARTITION_PARAM.to_string(), partition.to_string())];
+        Ok(self.reconnecting_stream(kind.path(), query, options))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::bearer::BearerTokenProvider;
+    use crate::models::compliance::ComplianceEvent;
+    use std::sync::Arc;
+    use tokio_stream::StreamExt;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_delete_event_parsed_from_stream() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/tweets/compliance/stream"))
+            .and(query_param("partition", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(concat!(
+                r#"{"data":{"delete":{"tweet":{"id":"1404493583843463170","author_id":"1395034425421447169"},"event_at":"2021-06-14T17:10:43.483Z"}}}"#,
+                "\r\n"
+            )))
+            .mount(&server)
+            .await;
+
+        let client = Client::builder()
+            .auth(Arc::new(BearerTokenProvider::new("token")))
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+        assert!(
+            client
+                .compliance_stream(ComplianceKind::Tweets, 5, &StreamOptions::new())
+                .is_err()
+        );
+
+        let mut stream = client
+            .compliance_stream(ComplianceKind::Tweets, 2, &StreamOptions::new())
+            .unwrap();
+        let event = stream.next().await.unwrap().unwrap().data;
+        match &event {
+            ComplianceEvent::Delete(delete) => {
+                assert_eq!(delete.tweet.id, "1404493583843463170");
+                assert_eq!(delete.tweet.author_id, "1395034425421447169");
+            }
+            other => panic!("expected a delete event, got {:?}", other),
+        }
+        assert_eq!(
+            event.event_at().to_rfc3339(),
+            "2021-06-14T17:10:43.483+00:00"
+        );
+    }
+}
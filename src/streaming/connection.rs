@@ -0,0 +1,174 @@
+//! Streaming connection reader
+//!
+//! X streams deliver one JSON message per line (`\r\n` delimited) and send an
+//! empty line as a keep-alive heartbeat. The reader task splits the body into
+//! messages and forwards them through a bounded channel.
+//!
+//! Every stream ends with an error telling how the connection ended:
+//! - [`Error::StreamDisconnected`] with [`EOF_REASON`] when the server closed
+//!   the body cleanly
+//! - [`Error::StreamConnection`] when the transport failed, or the body
+//!   ended in the middle of a message
+
+use crate::error::{Error, Result};
+use tokio::sync::mpsc;
+use tokio_stream::{Stream, StreamExt};
+
+/// Reason carried by [`Error::StreamDisconnected`] when the server closed the
+/// stream cleanly
+pub const EOF_REASON: &str = "eof";
+
+/// Spawn a task reading messages from a streaming response body
+///
+/// Each non-empty line is sent as one message. The channel holds at most
+/// `capacity` messages; when it is full the task stops polling `body`, which
+/// in turn stops reading from the connection (backpressure). The task ends
+/// when the body ends or fails, sending the matching error as the last
+/// message (see the [module docs](self)), or when the receiver is dropped.
+pub(crate) fn spawn_reader<S, B, E>(body: S, capacity: usize) -> mpsc::Receiver<Result<String>>
+where
+    S: Stream<Item = std::result::Result<B, E>> + Send + 'static,
+    B: AsRef<[u8]> + Send,
+    E: std::fmt::Display + Send,
+{
+    let (tx, rx) = mpsc::channel(capacity);
+
+    tokio::spawn(async move {
+        tokio::pin!(body);
+        let mut buffer: Vec<u8> = Vec::new();
+
+        while let Some(chunk) = body.next().await {
+            let chunk = match chunk {
+                Ok(chunk) => chunk,
+                Err(e) => {
+                    // The receiver may already be gone; nothing left to notify then
+                    let _ = tx.send(Err(Error::StreamConnection(e.to_string()))).await;
+                    return;
+                }
+            };
+            buffer.extend_from_slice(chunk.as_ref());
+
+            while let Some(end) = buffer.iter().position(|&b| b == b'\n') {
+                let line: Vec<u8> = buffer.drain(..=end).collect();
+                let line = line.trim_ascii();
+                if line.is_empty() {
+                    // Keep-alive heartbeat
+                    continue;
+                }
+
+                let message = String::from_utf8(line.to_vec()).map_err(|e| {
+                    Error::InvalidResponse(format!("Stream message is not valid UTF-8: {}", e))
+                });
+                if tx.send(message).await.is_err() {
+                    // Consumer dropped the stream
+                    return;
+                }
+            }
+        }
+
+        let end = if buffer.trim_ascii().is_empty() {
+            Error::StreamDisconnected(EOF_REASON.to_string())
+        } else {
+            Error::StreamConnection(format!(
+                "Stream ended mid-message ({} bytes unterminated)",
+                buffer.len()
+            ))
+        };
+        let _ = tx.send(Err(end)).await;
+    });
+
+    rx
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::Infallible;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Let the reader task run until it blocks
+    async fn settle() {
+        for _ in 0..20 {
+            tokio::task::yield_now().await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_splits_lines_and_skips_heartbeats() {
+        let chunks = vec![
+            Ok::<_, Infallible>("{\"a\":1}\r\n\r\n{\"b\"".to_string()),
+            Ok(":2}\r\n".to_string()),
+        ];
+        let mut rx = spawn_reader(tokio_stream::iter(chunks), 8);
+
+        assert_eq!(rx.recv().await.unwrap().unwrap(), "{\"a\":1}");
+        assert_eq!(rx.recv().await.unwrap().unwrap(), "{\"b\":2}");
+    }
+
+    #[tokio::test]
+    async fn test_clean_eof_is_last_message() {
+        let chunks = vec![Ok::<_, Infallible>("{\"a\":1}\r\n\r\n".to_string())];
+        let mut rx = spawn_reader(tokio_stream::iter(chunks), 8);
+
+        assert!(rx.recv().await.unwrap().is_ok());
+        match rx.recv().await.unwrap() {
+            Err(Error::StreamDisconnected(reason)) => assert_eq!(reason, EOF_REASON),
+            other => panic!("expected clean EOF, got {:?}", other),
+        }
+        assert!(rx.recv().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_transport_error_is_last_message() {
+        let chunks = vec![
+            Ok("{\"a\":1}\r\n{\"b\"".to_string()),
+            Err("connection reset"),
+        ];
+        let mut rx = spawn_reader(tokio_stream::iter(chunks), 8);
+
+        assert!(rx.recv().await.unwrap().is_ok());
+        assert!(matches!(
+            rx.recv().await.unwrap(),
+            Err(Error::StreamConnection(_))
+        ));
+        assert!(rx.recv().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_eof_mid_message_is_connection_error() {
+        let chunks = vec![Ok::<_, Infallible>("{\"a\":1}\r\n{\"b\"".to_string())];
+        let mut rx = spawn_reader(tokio_stream::iter(chunks), 8);
+
+        assert!(rx.recv().await.unwrap().is_ok());
+        assert!(matches!(
+            rx.recv().await.unwrap(),
+            Err(Error::StreamConnection(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_full_channel_pauses_reader() {
+        const CAPACITY: usize = 2;
+
+        let polled = Arc::new(AtomicUsize::new(0));
+        let counter = polled.clone();
+        let body = tokio_stream::iter(0..100).map(move |i| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Ok::<_, Infallible>(format!("{{\"n\":{}}}\r\n", i))
+        });
+
+        let mut rx = spawn_reader(body, CAPACITY);
+        settle().await;
+
+        // CAPACITY messages are buffered and one more is waiting to be sent
+        assert_eq!(polled.load(Ordering::SeqCst), CAPACITY + 1);
+        settle().await;
+        assert_eq!(polled.load(Ordering::SeqCst), CAPACITY + 1);
+
+        // Draining one message lets the reader advance by exactly one chunk
+        assert_eq!(rx.recv().await.unwrap().unwrap(), "{\"n\":0}");
+        settle().await;
+        assert_eq!(polled.load(Ordering::SeqCst), CAPACITY + 2);
+    }
+}
@@ -1 +1,534 @@
 //! Filtered stream implementation
+
+use crate::client::{Client, HttpClient};
+use crate::endpoints::paths;
+use crate::error::{Error, Result};
+use crate::models::common::Includes;
+use crate::models::tweet::Tweet;
+use crate::streaming::StreamOptions;
+use crate::streaming::connection::spawn_reader;
+use crate::streaming::notice::parse_message;
+use crate::streaming::reconnect::ReconnectingStream;
+use serde::{Deserialize, Serialize};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tokio::sync::broadcast;
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_stream::{Stream, StreamExt};
+
+/// A rule that matched a streamed tweet
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MatchingRule {
+    /// ID of the rule
+    pub id: String,
+
+    /// Tag given to the rule when it was created
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+}
+
+/// A tweet delivered by a stream
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreamTweet {
+    /// The tweet
+    pub data: Tweet,
+
+    /// Expanded objects referenced by the tweet
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub includes: Option<Includes>,
+
+    /// Filtered stream rules the tweet matched
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub matching_rules: Vec<MatchingRule>,
+}
+
+/// Item broadcast by [`Client::filtered_stream_broadcast`]
+///
+/// [`Error`] is not `Clone`, so errors are shared between receivers.
+pub type BroadcastMessage = std::result::Result<StreamTweet, Arc<Error>>;
+
+/// Stream of tweets from the filtered stream endpoint
+///
+/// Messages are read by a background task into a bounded channel (see
+/// [`StreamOptions`]). Dropping the stream closes the connection. The last
+/// item is an error telling whether the server closed the connection or it
+/// failed (see [`connection`](crate::streaming::connection)). An operational
+/// disconnect message from X is yielded as
+/// [`Error::StreamDisconnectNotice`](crate::Error::StreamDisconnectNotice).
+pub struct FilteredStream {
+    messages: ReceiverStream<Result<String>>,
+    skip_withheld: bool,
+    strict: bool,
+}
+
+impl FilteredStream {
+    /// Create a stream over a response body
+    pub(crate) fn new<S, B, E>(body: S, options: &StreamOptions, strict: bool) -> Self
+    where
+        S: Stream<Item = std::result::Result<B, E>> + Send + 'static,
+        B: AsRef<[u8]> + Send,
+        E: std::fmt::Display + Send,
+    {
+        Self {
+            messages: ReceiverStream::new(spawn_reader(body, options.channel_capacity())),
+            skip_withheld: options.skip_withheld(),
+            strict,
+        }
+    }
+}
+
+impl Stream for FilteredStream {
+    type Item = Result<StreamTweet>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            let Some(message) = std::task::ready!(Pin::new(&mut self.messages).poll_next(cx))
+            else {
+                return Poll::Ready(None);
+            };
+            let strict = self.strict;
+            let parsed = message.and_then(|message| parse_message::<StreamTweet>(&message, strict));
+            if self.skip_withheld && parsed.as_ref().is_ok_and(StreamTweet::is_withheld) {
+                continue;
+            }
+            return Poll::Ready(Some(parsed));
+        }
+    }
+}
+
+impl StreamTweet {
+    /// Whether the tweet is withheld (see [`Tweet::is_withheld`])
+    pub(crate) fn is_withheld(&self) -> bool {
+        self.data.is_withheld()
+    }
+}
+
+/// Unparsed body of the filtered stream
+///
+/// Yields the body in chunks as they arrive, with no framing into messages.
+/// Returned by [`Client::filtered_stream_raw`]; dropping it closes the
+/// connection.
+pub struct RawStream {
+    chunks: Pin<Box<dyn Stream<Item = Result<Vec<u8>>> + Send>>,
+}
+
+impl Stream for RawStream {
+    type Item = Result<Vec<u8>>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.chunks.as_mut().poll_next(cx)
+    }
+}
+
+impl<H: HttpClient + Clone> Client<H> {
+    /// Connect to the filtered stream
+    ///
+    /// Delivers tweets matching the rules configured for the app. Requires an
+    /// auth provider supporting app-only authentication.
+    ///
+    /// The consumer must keep up with the stream: see [`StreamOptions`] for
+    /// how backpressure is applied and what happens when a consumer stalls.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use tokio_stream::StreamExt;
+    /// use x_api_client::streaming::StreamOptions;
+    ///
+    /// let mut stream = client.filtered_stream(&StreamOptions::new()).await?;
+    /// while let Some(tweet) = stream.next().await {
+    ///     println!("{}", tweet?.data.text);
+    /// }
+    /// ```
+    pub async fn filtered_stream(&self, options: &StreamOptions) -> Result<FilteredStream> {
+        let response = self.open_stream(paths::TWEETS_SEARCH_STREAM, &[]).await?;
+        Ok(FilteredStream::new(
+            response.bytes_stream(),
+            options,
+            self.strict_deserialization(),
+        ))
+    }
+
+    /// Connect to the filtered stream, yielding the body unparsed
+    ///
+    /// For consumers that archive or forward the stream as received. The
+    /// body is decompressed unless disabled with
+    /// [`StreamOptionsBuilder::decompress`](crate::streaming::StreamOptionsBuilder::decompress),
+    /// in which case the chunks are the compressed bytes sent by the server.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Config`] if decompression is disabled and the HTTP
+    /// client cannot leave bodies compressed.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use tokio_stream::StreamExt;
+    /// use x_api_client::streaming::StreamOptions;
+    ///
+    /// let options = StreamOptions::custom().decompress(false).build()?;
+    /// let mut stream = client.filtered_stream_raw(&options).await?;
+    /// while let Some(chunk) = stream.next().await {
+    ///     archive.write_all(&chunk?)?;
+    /// }
+    /// ```
+    pub async fn filtered_stream_raw(&self, options: &StreamOptions) -> Result<RawStream> {
+        let response = self
+            .open_stream_with(paths::TWEETS_SEARCH_STREAM, &[], options.decompress())
+            .await?;
+        let chunks = response
+            .bytes_stream()
+            .map(|chunk| chunk.map(|chunk| chunk.to_vec()).map_err(Error::from));
+        Ok(RawStream {
+            chunks: Box::pin(chunks),
+        })
+    }
+}
+
+impl<H: HttpClient + Clone + 'static> Client<H> {
+    /// Connect to the filtered stream, reconnecting after disconnects
+    ///
+    /// Like [`filtered_stream`](Self::filtered_stream), but the connection
+    /// is re-established with X's recommended backoff whenever it ends or
+    /// fails (see [`ReconnectingStream`]). Each attempt is authenticated
+    /// anew. Connects on first poll.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use tokio_stream::StreamExt;
+    /// use x_api_client::streaming::StreamOptions;
+    ///
+    /// let mut stream = client.filtered_stream_reconnecting(&StreamOptions::new());
+    /// while let Some(tweet) = stream.next().await {
+    ///     println!("{}", tweet?.data.text);
+    /// }
+    /// ```
+    pub fn filtered_stream_reconnecting(
+        &self,
+        options: &StreamOptions,
+    ) -> ReconnectingStream<StreamTweet> {
+        let stream = self.reconnecting_stream(paths::TWEETS_SEARCH_STREAM, Vec::new(), options);
+        if options.skip_withheld() {
+            stream.skipping(StreamTweet::is_withheld)
+        } else {
+            stream
+        }
+    }
+
+    /// Share one filtered stream connection between several consumers
+    ///
+    /// Runs [`filtered_stream_reconnecting`](Self::filtered_stream_reconnecting)
+    /// in a background task and broadcasts each tweet to every receiver. X
+    /// allows a single filtered stream connection per app, so this is the
+    /// way to feed several consumers. Create more receivers with
+    /// [`resubscribe`](broadcast::Receiver::resubscribe); each one sees the
+    /// tweets broadcast after it was created.
+    ///
+    /// Each receiver buffers up to `capacity` tweets. A receiver that falls
+    /// further behind gets [`RecvError::Lagged`](broadcast::error::RecvError::Lagged)
+    /// with the number of tweets it missed, then continues with the oldest
+    /// tweet still buffered; other receivers are unaffected.
+    ///
+    /// Stream errors are broadcast too, shared behind an [`Arc`]. The task
+    /// ends, closing the channel, when every receiver has been dropped or
+    /// after broadcasting an error reconnecting cannot fix (such as an
+    /// authentication failure), which is then the last item receivers see.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Config`] if `capacity` is 0.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use x_api_client::streaming::StreamOptions;
+    ///
+    /// let mut archiver = client.filtered_stream_broadcast(&StreamOptions::new(), 1024)?;
+    /// let mut alerts = archiver.resubscribe();
+    /// tokio::spawn(async move {
+    ///     while let Ok(message) = alerts.recv().await {
+    ///         match message {
+    ///             Ok(tweet) => println!("{}", tweet.data.text),
+    ///             Err(error) => eprintln!("stream error: {}", error),
+    ///         }
+    ///     }
+    /// });
+    /// ```
+    pub fn filtered_stream_broadcast(
+        &self,
+        options: &StreamOptions,
+        capacity: usize,
+    ) -> Result<broadcast::Receiver<BroadcastMessage>> {
+        if capacity == 0 {
+            return Err(Error::Config(
+                "Broadcast capacity must be at least 1".to_string(),
+            ));
+        }
+
+        let (sender, receiver) = broadcast::channel(capacity);
+        let mut stream = self.filtered_stream_reconnecting(options);
+        tokio::spawn(async move {
+            while let Some(message) = stream.next().await {
+                if sender.send(message.map_err(Arc::new)).is_err() {
+                    // Every receiver was dropped
+                    break;
+                }
+            }
+        });
+        Ok(receiver)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::bearer::BearerTokenProvider;
+    use std::sync::Arc;
+    use tokio_stream::StreamExt;
+
+    #[tokio::test]
+    async fn test_filtered_stream_yields_tweets() {
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let body = concat!(
+            r#"{"data":{"id":"1","text":"first"},"matching_rules":[{"id":"10","tag":"rust"}]}"#,
+            "\r\n\r\n",
+            r#"{"data":{"id":"2","text":"second"},"matching_rules":[{"id":"10","tag":"rust"}]}"#,
+            "\r\n",
+        );
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/tweets/search/stream"))
+            .and(header("authorization", "Bearer test"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body))
+            .mount(&server)
+            .await;
+
+        let client = Client::builder()
+            .auth(Arc::new(BearerTokenProvider::new("test")))
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+        let options = StreamOptions::custom().channel_capacity(1).build().unwrap();
+
+        let mut messages: Vec<Result<StreamTweet>> = client
+            .filtered_stream(&options)
+            .await
+            .unwrap()
+            .collect()
+            .await;
+
+        // The server closing the body ends the stream with a clean EOF
+        assert!(matches!(
+            messages.pop(),
+            Some(Err(crate::error::Error::StreamDisconnected(reason)))
+                if reason == crate::streaming::connection::EOF_REASON
+        ));
+        let tweets: Vec<StreamTweet> = messages.into_iter().collect::<Result<_>>().unwrap();
+        assert_eq!(tweets.len(), 2);
+        assert_eq!(tweets[0].data.text, "first");
+        assert_eq!(tweets[1].matching_rules[0].tag.as_deref(), Some("rust"));
+    }
+
+    #[tokio::test]
+    async fn test_strict_client_rejects_unknown_stream_fields() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let body = concat!(
+            r#"{"data":{"id":"1","text":"first","new_field":true}}"#,
+            "\r\n"
+        );
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/tweets/search/stream"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body))
+            .mount(&server)
+            .await;
+
+        let client = Client::builder()
+            .auth(Arc::new(BearerTokenProvider::new("test")))
+            .base_url(server.uri())
+            .strict_deserialization(true)
+            .build()
+            .unwrap();
+        let mut stream = client.filtered_stream(&StreamOptions::new()).await.unwrap();
+
+        assert!(matches!(
+            stream.next().await,
+            Some(Err(crate::error::Error::Serialization(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_skip_withheld_drops_withheld_tweets() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let body = concat!(
+            r#"{"data":{"id":"1","text":"withheld","withheld":{"country_codes":["DE"]}}}"#,
+            "\r\n",
+            r#"{"data":{"id":"2","text":"sensitive","possibly_sensitive":true}}"#,
+            "\r\n",
+        );
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/tweets/search/stream"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body))
+            .mount(&server)
+            .await;
+
+        let client = Client::builder()
+            .auth(Arc::new(BearerTokenProvider::new("test")))
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+
+        let ids = |messages: Vec<Result<StreamTweet>>| -> Vec<String> {
+            messages
+                .into_iter()
+                .filter_map(|message| message.ok())
+                .map(|tweet| tweet.data.id)
+                .collect()
+        };
+        let all = client
+            .filtered_stream(&StreamOptions::new())
+            .await
+            .unwrap()
+            .collect()
+            .await;
+        assert_eq!(ids(all), vec!["1", "2"]);
+
+        let options = StreamOptions::custom().skip_withheld(true).build().unwrap();
+        let kept = client
+            .filtered_stream(&options)
+            .await
+            .unwrap()
+            .collect()
+            .await;
+        assert_eq!(ids(kept), vec!["2"]);
+
+        let mut reconnecting = client.filtered_stream_reconnecting(&options);
+        let tweet = reconnecting.next().await.unwrap().unwrap();
+        assert_eq!(tweet.data.id, "2");
+        assert!(tweet.data.is_sensitive());
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_delivers_every_tweet_to_each_receiver() {
+        use tokio::sync::broadcast::error::RecvError;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let body = concat!(
+            r#"{"data":{"id":"1","text":"first"}}"#,
+            "\r\n",
+            r#"{"data":{"id":"2","text":"second"}}"#,
+            "\r\n",
+        );
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/tweets/search/stream"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body))
+            .up_to_n_times(1)
+            .with_priority(1)
+            .mount(&server)
+            .await;
+        // Reconnecting fails for good, which ends the broadcast
+        Mock::given(method("GET"))
+            .and(path("/2/tweets/search/stream"))
+            .respond_with(ResponseTemplate::new(401))
+            .mount(&server)
+            .await;
+
+        let client = Client::builder()
+            .auth(Arc::new(BearerTokenProvider::new("test")))
+            .clock(Arc::new(crate::util::clock::MockClock::new(
+                chrono::Utc::now(),
+            )))
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+
+        let mut first = client
+            .filtered_stream_broadcast(&StreamOptions::new(), 16)
+            .unwrap();
+        let mut second = first.resubscribe();
+
+        for receiver in [&mut first, &mut second] {
+            assert_eq!(receiver.recv().await.unwrap().unwrap().data.text, "first");
+            assert_eq!(receiver.recv().await.unwrap().unwrap().data.text, "second");
+            // The 401 reaches subscribers before the channel closes
+            let error = receiver.recv().await.unwrap().unwrap_err();
+            assert!(
+                matches!(&*error, Error::Api(detail) if detail.status() == Some(401)),
+                "unexpected error: {}",
+                error
+            );
+            assert!(matches!(receiver.recv().await, Err(RecvError::Closed)));
+        }
+
+        assert!(
+            client
+                .filtered_stream_broadcast(&StreamOptions::new(), 0)
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_raw_stream_without_decompression_keeps_compressed_bytes() {
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        const PLAIN: &[u8] = b"{\"data\":{\"id\":\"1\",\"text\":\"hi\"}}\r\n";
+        // PLAIN compressed with gzip
+        const GZIPPED: &[u8] = &[
+            0x1f, 0x8b, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x03, 0xab, 0x56, 0x4a, 0x49,
+            0x2c, 0x49, 0x54, 0xb2, 0xaa, 0x56, 0xca, 0x4c, 0x51, 0xb2, 0x52, 0x32, 0x54, 0xd2,
+            0x51, 0x2a, 0x49, 0xad, 0x28, 0x01, 0x32, 0x33, 0x32, 0x95, 0x6a, 0x6b, 0x79, 0xb9,
+            0x00, 0x63, 0xe1, 0xf3, 0x18, 0x21, 0x00, 0x00, 0x00,
+        ];
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/tweets/search/stream"))
+            .and(header("accept-encoding", "gzip"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-encoding", "gzip")
+                    .set_body_bytes(GZIPPED),
+            )
+            .mount(&server)
+            .await;
+
+        let client = Client::builder()
+            .auth(Arc::new(BearerTokenProvider::new("test")))
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+
+        async fn read_all(stream: RawStream) -> Vec<u8> {
+            let chunks: Vec<Vec<u8>> = stream.collect::<Result<_>>().await.unwrap();
+            chunks.concat()
+        }
+
+        let raw = StreamOptions::custom().decompress(false).build().unwrap();
+        let stream = client.filtered_stream_raw(&raw).await.unwrap();
+        assert_eq!(read_all(stream).await, GZIPPED);
+
+        let stream = client
+            .filtered_stream_raw(&StreamOptions::new())
+            .await
+            .unwrap();
+        assert_eq!(read_all(stream).await, PLAIN);
+    }
+
+    #[test]
+    fn test_zero_channel_capacity_rejected() {
+        assert!(StreamOptions::custom().channel_capacity(0).build().is_err());
+    }
+}
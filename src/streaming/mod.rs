@@ -3,11 +3,278 @@
 //! This module provides production-ready implementation of:
 //! - Filtered streams with rule management
 //! - Volume streams (1% and 10% sample)
+//! - Compliance streams
 //! - Automatic reconnection with backoff
+//! - Routing of tweets by matching rule tag
+//! - Stream health counters
 //! - Heartbeat monitoring
-//! - Backfill support for missed data
+//! - Backfill support for missed data (see [`StreamOptionsBuilder::resume_from`])
 
+pub mod compliance;
+pub mod connection;
 pub mod filtered;
+pub mod notice;
 pub mod reconnect;
+pub mod router;
 pub mod rules;
 pub mod sample;
+pub mod stats;
+
+use crate::error::Error;
+use chrono::{DateTime, Utc};
+use std::fmt;
+use std::sync::Arc;
+
+/// Default number of messages buffered between the reader task and the consumer
+pub const DEFAULT_CHANNEL_CAPACITY: usize = 1024;
+
+/// Longest downtime streams can backfill, in minutes
+pub const MAX_BACKFILL_MINUTES: i64 = 5;
+
+/// Query parameter requesting backfill on connect
+pub(crate) const BACKFILL_MINUTES_PARAM: &str = "backfill_minutes";
+
+/// Query parameter selecting the partition of a partitioned stream
+pub(crate) const PARTITION_PARAM: &str = "partition";
+
+/// Streaming connection options
+///
+/// Fields are private to maintain encapsulation and allow future changes.
+/// Use `new()` for defaults or `custom()` to configure.
+///
+/// # Backpressure
+///
+/// Messages are read from the connection by a background task and handed to
+/// the consumer through a bounded channel of `channel_capacity` messages.
+/// When the channel is full the reader stops reading from the connection
+/// until the consumer catches up, so memory use stays bounded.
+///
+/// A consumer that stalls for too long will cause X to disconnect the stream
+/// once its server-side buffer fills up. Keep per-message processing short,
+/// or hand messages off to worker tasks.
+///
+/// # Giving up
+///
+/// By default a stream reconnects for as long as its disconnects are
+/// retryable. With [`max_reconnects`](StreamOptionsBuilder::max_reconnects)
+/// set, it gives up after that many consecutive failed reconnects: the
+/// [`on_give_up`](StreamOptionsBuilder::on_give_up) callback is invoked with
+/// the last error, which then ends the stream.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreamOptions {
+    /// Capacity of the reader-to-consumer channel (default: 1024)
+    channel_capacity: usize,
+
+    /// Consecutive reconnects before giving up (default: unlimited)
+    max_reconnects: Option<u32>,
+
+    /// Invoked with the last error when giving up
+    on_give_up: Option<GiveUpCallback>,
+
+    /// Whether raw streams decompress the body (default: true)
+    decompress: bool,
+
+    /// Time of the last message seen before a restart (default: none)
+    resume_from: Option<DateTime<Utc>>,
+
+    /// Whether tweet streams drop withheld tweets (default: false)
+    skip_withheld: bool,
+}
+
+/// Callback invoked when a stream gives up reconnecting
+#[derive(Clone)]
+pub(crate) struct GiveUpCallback(Arc<dyn Fn(&Error) + Send + Sync>);
+
+impl GiveUpCallback {
+    /// Invoke the callback with the error ending the stream
+    pub(crate) fn call(&self, error: &Error) {
+        (self.0)(error)
+    }
+}
+
+impl fmt::Debug for GiveUpCallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("GiveUpCallback")
+    }
+}
+
+impl PartialEq for GiveUpCallback {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl StreamOptions {
+    /// Create stream options with default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the capacity of the reader-to-consumer channel
+    pub fn channel_capacity(&self) -> usize {
+        self.channel_capacity
+    }
+
+    /// Get the number of consecutive failed reconnects before giving up
+    ///
+    /// `None` means reconnecting never gives up.
+    pub fn max_reconnects(&self) -> Option<u32> {
+        self.max_reconnects
+    }
+
+    /// Get whether raw streams decompress the body
+    pub fn decompress(&self) -> bool {
+        self.decompress
+    }
+
+    /// Get the time of the last message seen before a restart
+    pub fn resume_from(&self) -> Option<DateTime<Utc>> {
+        self.resume_from
+    }
+
+    /// Get whether tweet streams drop withheld tweets
+    pub fn skip_withheld(&self) -> bool {
+        self.skip_withheld
+    }
+
+    /// Get the callback invoked when giving up
+    pub(crate) fn on_give_up(&self) -> Option<GiveUpCallback> {
+        self.on_give_up.clone()
+    }
+
+    /// Create custom stream options with builder pattern
+    pub fn custom() -> StreamOptionsBuilder {
+        StreamOptionsBuilder::default()
+    }
+}
+
+impl Default for StreamOptions {
+    fn default() -> Self {
+        Self {
+            channel_capacity: DEFAULT_CHANNEL_CAPACITY,
+            max_reconnects: None,
+            on_give_up: None,
+            decompress: true,
+            resume_from: None,
+            skip_withheld: false,
+        }
+    }
+}
+
+/// Builder for creating custom stream options
+#[derive(Debug)]
+pub struct StreamOptionsBuilder {
+    channel_capacity: usize,
+    max_reconnects: Option<u32>,
+    on_give_up: Option<GiveUpCallback>,
+    decompress: bool,
+    resume_from: Option<DateTime<Utc>>,
+    skip_withheld: bool,
+}
+
+impl Default for StreamOptionsBuilder {
+    fn default() -> Self {
+        Self {
+            channel_capacity: DEFAULT_CHANNEL_CAPACITY,
+            max_reconnects: None,
+            on_give_up: None,
+            decompress: true,
+            resume_from: None,
+            skip_withheld: false,
+        }
+    }
+}
+
+impl StreamOptionsBuilder {
+    /// Set the number of messages buffered before the reader pauses
+    pub fn channel_capacity(mut self, capacity: usize) -> Self {
+        self.channel_capacity = capacity;
+        self
+    }
+
+    /// Give up after `max` consecutive failed reconnects
+    ///
+    /// The count restarts whenever a connection succeeds. With `0` the
+    /// stream gives up on the first disconnect.
+    pub fn max_reconnects(mut self, max: u32) -> Self {
+        self.max_reconnects = Some(max);
+        self
+    }
+
+    /// Set whether raw streams decompress the body
+    ///
+    /// With `false`, [`filtered_stream_raw`](crate::Client::filtered_stream_raw)
+    /// requests `gzip` and yields the bytes exactly as received, for
+    /// consumers that handle decompression themselves. Parsed streams always
+    /// decompress.
+    ///
+    /// Default: true
+    pub fn decompress(mut self, decompress: bool) -> Self {
+        self.decompress = decompress;
+        self
+    }
+
+    /// Resume after a restart, given the time of the last message seen
+    ///
+    /// Persist the `created_at` of the last message processed, and pass it
+    /// here on startup: the first connection of a reconnecting stream then
+    /// requests backfill of the downtime, up to [`MAX_BACKFILL_MINUTES`].
+    /// If the downtime was longer, the stream first yields
+    /// [`Error::StreamGap`] (and logs a warning) telling which period may be
+    /// missing. Expect duplicates of messages seen just before the restart.
+    ///
+    /// Backfill requires a Pro or Enterprise access level.
+    pub fn resume_from(mut self, last_seen: DateTime<Utc>) -> Self {
+        self.resume_from = Some(last_seen);
+        self
+    }
+
+    /// Drop tweets with a `withheld` object instead of yielding them
+    ///
+    /// Applies to the filtered and 10% sample streams, for consumers that
+    /// must not process content withheld in some countries (see
+    /// [`Tweet::is_withheld`]).
+    /// Dropped tweets still count in the stream's
+    /// [`stats`](crate::streaming::reconnect::ReconnectingStream::stats).
+    ///
+    /// Default: false
+    ///
+    /// [`Tweet::is_withheld`]: crate::models::tweet::Tweet::is_withheld
+    pub fn skip_withheld(mut self, skip: bool) -> Self {
+        self.skip_withheld = skip;
+        self
+    }
+
+    /// Set a callback invoked with the last error when the stream gives up
+    ///
+    /// Only called once [`max_reconnects`](Self::max_reconnects) is
+    /// exhausted, not for errors that end the stream without reconnecting
+    /// (such as authentication failures).
+    pub fn on_give_up(mut self, callback: impl Fn(&Error) + Send + Sync + 'static) -> Self {
+        self.on_give_up = Some(GiveUpCallback(Arc::new(callback)));
+        self
+    }
+
+    /// Build the stream options
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - channel_capacity is 0 (must be at least 1)
+    pub fn build(self) -> crate::error::Result<StreamOptions> {
+        if self.channel_capacity == 0 {
+            return Err(crate::error::Error::Config(
+                "Stream channel capacity must be at least 1".to_string(),
+            ));
+        }
+
+        Ok(StreamOptions {
+            channel_capacity: self.channel_capacity,
+            max_reconnects: self.max_reconnects,
+            on_give_up: self.on_give_up,
+            decompress: self.decompress,
+            resume_from: self.resume_from,
+            skip_withheld: self.skip_withheld,
+        })
+    }
+}
@@ -0,0 +1,199 @@
+//! Operational messages sent on streaming connections
+//!
+//! Before closing a connection for operational reasons, X sends a message
+//! made only of errors, such as:
+//!
+//! ```json
+//! {"errors":[{"title":"operational-disconnect","disconnect_type":"UpstreamOperationalDisconnect","detail":"This stream has been disconnected upstream for operational reasons."}]}
+//! ```
+//!
+//! Streams surface it as [`Error::StreamDisconnectNotice`], whose
+//! [`DisconnectType`] tells whether to reconnect right away or back off.
+
+use crate::error::{Error, Result};
+use crate::util::serde_helpers::from_json_str;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+/// `title` of operational disconnect errors
+pub const OPERATIONAL_DISCONNECT_TITLE: &str = "operational-disconnect";
+
+/// Reason X gives for an operational disconnect
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+#[non_exhaustive]
+pub enum DisconnectType {
+    /// Maintenance or deploy upstream of the streaming servers
+    UpstreamOperationalDisconnect,
+    /// Upstream connection lost unexpectedly
+    UpstreamUncleanDisconnect,
+    /// Maintenance or deploy of the streaming servers
+    OperationalDisconnect,
+    /// Connection closed on purpose (e.g. replaced by a newer connection)
+    ForceDisconnect,
+    /// A type this version does not know about
+    Unknown(String),
+}
+
+impl DisconnectType {
+    /// Value sent in `disconnect_type`
+    pub fn as_str(&self) -> &str {
+        match self {
+            DisconnectType::UpstreamOperationalDisconnect => "UpstreamOperationalDisconnect",
+            DisconnectType::UpstreamUncleanDisconnect => "UpstreamUncleanDisconnect",
+            DisconnectType::OperationalDisconnect => "OperationalDisconnect",
+            DisconnectType::ForceDisconnect => "ForceDisconnect",
+            DisconnectType::Unknown(value) => value,
+        }
+    }
+
+    /// Whether reconnecting right away is expected to succeed
+    ///
+    /// True for disconnects caused by X's own operations. Forced and unknown
+    /// disconnects should back off as for HTTP errors.
+    pub fn reconnect_immediately(&self) -> bool {
+        matches!(
+            self,
+            DisconnectType::UpstreamOperationalDisconnect
+                | DisconnectType::UpstreamUncleanDisconnect
+                | DisconnectType::OperationalDisconnect
+        )
+    }
+}
+
+impl From<String> for DisconnectType {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "UpstreamOperationalDisconnect" => DisconnectType::UpstreamOperationalDisconnect,
+            "UpstreamUncleanDisconnect" => DisconnectType::UpstreamUncleanDisconnect,
+            "OperationalDisconnect" => DisconnectType::OperationalDisconnect,
+            "ForceDisconnect" => DisconnectType::ForceDisconnect,
+            _ => DisconnectType::Unknown(value),
+        }
+    }
+}
+
+impl From<DisconnectType> for String {
+    fn from(value: DisconnectType) -> Self {
+        value.as_str().to_string()
+    }
+}
+
+impl fmt::Display for DisconnectType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Operational disconnect message received on a stream
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StreamDisconnectNotice {
+    /// Always [`OPERATIONAL_DISCONNECT_TITLE`]
+    pub title: String,
+
+    /// Reason for the disconnect
+    pub disconnect_type: DisconnectType,
+
+    /// Human-readable explanation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+
+    /// Problem type URI
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub type_uri: Option<String>,
+
+    /// Forward compatibility: capture unknown fields
+    #[serde(flatten)]
+    pub additional_fields: HashMap<String, serde_json::Value>,
+}
+
+impl fmt::Display for StreamDisconnectNotice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.detail {
+            Some(detail) => write!(f, "{} ({})", self.disconnect_type, detail),
+            None => write!(f, "{}", self.disconnect_type),
+        }
+    }
+}
+
+/// Errors-only message, as sent for operational notices
+#[derive(Deserialize)]
+struct ErrorsMessage {
+    errors: Vec<serde_json::Value>,
+}
+
+/// Parse `line` as an operational disconnect message, if it is one
+pub(crate) fn disconnect_notice(line: &str) -> Option<StreamDisconnectNotice> {
+    let message: ErrorsMessage = serde_json::from_str(line).ok()?;
+    message
+        .errors
+        .into_iter()
+        .find(|error| error["title"] == OPERATIONAL_DISCONNECT_TITLE)
+        .and_then(|error| serde_json::from_value(error).ok())
+}
+
+/// Parse a stream message, turning disconnect notices into errors
+///
+/// Unknown fields of models are rejected if `strict` (see
+/// [`ClientBuilder::strict_deserialization`](crate::client::ClientBuilder::strict_deserialization)).
+///
+/// # Errors
+///
+/// Returns [`Error::StreamDisconnectNotice`] for an operational disconnect
+/// message, or the parse error if `line` is neither a `T` nor a notice.
+pub(crate) fn parse_message<T: DeserializeOwned>(line: &str, strict: bool) -> Result<T> {
+    from_json_str(line, strict).map_err(|error| match disconnect_notice(line) {
+        Some(notice) => Error::StreamDisconnectNotice(Box::new(notice)),
+        None => error.into(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::streaming::filtered::StreamTweet;
+
+    #[test]
+    fn test_operational_disconnect_parsed_into_notice() {
+        let line = r#"{"errors":[{"title":"operational-disconnect","disconnect_type":"UpstreamOperationalDisconnect","detail":"This stream has been disconnected upstream for operational reasons.","type":"https://api.twitter.com/2/problems/operational-disconnect"}]}"#;
+
+        match parse_message::<StreamTweet>(line, false) {
+            Err(Error::StreamDisconnectNotice(notice)) => {
+                assert_eq!(
+                    notice.disconnect_type,
+                    DisconnectType::UpstreamOperationalDisconnect
+                );
+                assert!(notice.disconnect_type.reconnect_immediately());
+                assert_eq!(
+                    notice.type_uri.as_deref(),
+                    Some("https://api.twitter.com/2/problems/operational-disconnect")
+                );
+            }
+            other => panic!("expected disconnect notice, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_unknown_disconnect_type_kept() {
+        let line =
+            r#"{"errors":[{"title":"operational-disconnect","disconnect_type":"SomethingNew"}]}"#;
+        let notice = disconnect_notice(line).unwrap();
+        assert_eq!(
+            notice.disconnect_type,
+            DisconnectType::Unknown("SomethingNew".to_string())
+        );
+        assert!(!notice.disconnect_type.reconnect_immediately());
+        assert_eq!(
+            serde_json::to_value(&notice).unwrap()["disconnect_type"],
+            "SomethingNew"
+        );
+    }
+
+    #[test]
+    fn test_other_messages_are_not_notices() {
+        assert!(disconnect_notice(r#"{"data":{"id":"1","text":"hi"}}"#).is_none());
+        assert!(disconnect_notice(r#"{"errors":[{"title":"ConnectionException"}]}"#).is_none());
+    }
+}
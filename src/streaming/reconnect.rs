@@ -1 +1,804 @@
 //! Reconnection logic for streaming
+//!
+//! Implements the reconnect guidance published for X streaming endpoints,
+//! which prescribes a different backoff strategy per disconnect cause:
+//! - Network (TCP/IP level) errors: back off linearly, +250ms per attempt, up to 16s
+//! - HTTP errors: back off exponentially, starting at 5s and doubling, up to 320s
+//! - HTTP 420/429 (rate limited): back off exponentially, starting at 1 minute and doubling
+//!
+//! [`ReconnectingStream`] applies this guidance automatically. Every attempt
+//! builds and signs a new request, so OAuth 1.0a nonces and timestamps (or
+//! OAuth 2.0 tokens) are fresh rather than replayed, while connections are
+//! reused from the HTTP client's pool.
+
+use crate::client::{Client, HttpClient};
+use crate::error::{Error, Result};
+use crate::streaming::connection::{EOF_REASON, spawn_reader};
+use crate::streaming::notice::{disconnect_notice, parse_message};
+use crate::streaming::stats::StreamStatsHandle;
+use crate::streaming::{BACKFILL_MINUTES_PARAM, MAX_BACKFILL_MINUTES, StreamOptions};
+use crate::util::lock_unpoisoned;
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::de::DeserializeOwned;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::sync::Notify;
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_stream::{Stream, StreamExt};
+
+/// Linear step for network-level errors
+const NETWORK_STEP: Duration = Duration::from_millis(250);
+
+/// Maximum delay for network-level errors
+const NETWORK_MAX_DELAY: Duration = Duration::from_secs(16);
+
+/// Initial delay for HTTP errors
+const HTTP_INITIAL_DELAY: Duration = Duration::from_secs(5);
+
+/// Maximum delay for HTTP errors
+const HTTP_MAX_DELAY: Duration = Duration::from_secs(320);
+
+/// Initial delay for rate-limited (420/429) responses
+const RATE_LIMIT_INITIAL_DELAY: Duration = Duration::from_secs(60);
+
+/// Maximum delay for rate-limited responses (one rate-limit window)
+const RATE_LIMIT_MAX_DELAY: Duration = Duration::from_secs(15 * 60);
+
+/// Cause of a stream disconnect, used to select the backoff strategy
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DisconnectCause {
+    /// TCP/IP level failure (connection reset, timeout, unexpected EOF)
+    Network,
+    /// Non-success HTTP response other than rate limiting
+    Http,
+    /// HTTP 420 (legacy "enhance your calm") or 429 (too many requests)
+    RateLimited,
+}
+
+impl DisconnectCause {
+    /// Classify an error into the disconnect cause that governs backoff
+    pub fn classify(error: &Error) -> Self {
+        match error {
+            Error::RateLimitExceeded { .. } => DisconnectCause::RateLimited,
+            Error::Api(detail) => match detail.status() {
+                Some(420) | Some(429) => DisconnectCause::RateLimited,
+                _ => DisconnectCause::Http,
+            },
+            Error::Network(_)
+            | Error::Timeout(_)
+            | Error::Io(_)
+            | Error::StreamConnection(_)
+            | Error::StreamDisconnected(_) => DisconnectCause::Network,
+            Error::StreamDisconnectNotice(notice)
+                if notice.disconnect_type.reconnect_immediately() =>
+            {
+                DisconnectCause::Network
+            }
+            _ => DisconnectCause::Http,
+        }
+    }
+}
+
+/// Stream reconnect backoff following X's per-cause guidance
+///
+/// Each cause keeps its own attempt counter. Call [`reset`](Self::reset)
+/// once a connection has been successfully established.
+///
+/// # Example
+///
+/// ```rust
+/// use x_api_client::error::Error;
+/// use x_api_client::streaming::reconnect::StreamBackoff;
+/// use std::time::Duration;
+///
+/// let mut backoff = StreamBackoff::new();
+/// let err = Error::StreamDisconnected("connection reset".to_string());
+/// assert_eq!(backoff.next_delay(&err), Duration::from_millis(250));
+/// assert_eq!(backoff.next_delay(&err), Duration::from_millis(500));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StreamBackoff {
+    network_attempts: u32,
+    http_attempts: u32,
+    rate_limit_attempts: u32,
+    last_delay: Option<Duration>,
+}
+
+impl StreamBackoff {
+    /// Create a backoff with no recorded attempts
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Compute the delay before the next reconnect attempt after `error`
+    pub fn next_delay(&mut self, error: &Error) -> Duration {
+        self.next_delay_for(DisconnectCause::classify(error))
+    }
+
+    /// Compute the delay before the next reconnect attempt for a known cause
+    pub fn next_delay_for(&mut self, cause: DisconnectCause) -> Duration {
+        let delay = match cause {
+            DisconnectCause::Network => {
+                self.network_attempts = self.network_attempts.saturating_add(1);
+                NETWORK_STEP
+                    .saturating_mul(self.network_attempts)
+                    .min(NETWORK_MAX_DELAY)
+            }
+            DisconnectCause::Http => {
+                self.http_attempts = self.http_attempts.saturating_add(1);
+                exponential(HTTP_INITIAL_DELAY, self.http_attempts, HTTP_MAX_DELAY)
+            }
+            DisconnectCause::RateLimited => {
+                self.rate_limit_attempts = self.rate_limit_attempts.saturating_add(1);
+                exponential(
+                    RATE_LIMIT_INITIAL_DELAY,
+                    self.rate_limit_attempts,
+                    RATE_LIMIT_MAX_DELAY,
+                )
+            }
+        };
+        self.last_delay = Some(delay);
+        delay
+    }
+
+    /// Total number of reconnect attempts across all causes
+    pub fn attempts(&self) -> u32 {
+        self.network_attempts
+            .saturating_add(self.http_attempts)
+            .saturating_add(self.rate_limit_attempts)
+    }
+
+    /// Delay computed for the latest attempt, `None` since the last reset
+    pub fn current_delay(&self) -> Option<Duration> {
+        self.last_delay
+    }
+
+    /// Reset all attempt counters after a successful connection
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Shared control over the [`StreamBackoff`] of a [`ReconnectingStream`]
+///
+/// Clones control the same stream. Operators can watch the backoff during
+/// an incident and [`reset`](Self::reset) it once connectivity is back,
+/// instead of waiting out a long delay.
+#[derive(Debug, Clone, Default)]
+pub struct StreamBackoffHandle {
+    backoff: Arc<Mutex<StreamBackoff>>,
+    wake: Arc<Notify>,
+}
+
+impl StreamBackoffHandle {
+    /// Copy of the current backoff state
+    pub fn snapshot(&self) -> StreamBackoff {
+        lock_unpoisoned(&self.backoff).clone()
+    }
+
+    /// Reconnect attempts since the last successful connection or reset
+    pub fn attempts(&self) -> u32 {
+        lock_unpoisoned(&self.backoff).attempts()
+    }
+
+    /// Delay of the pending or latest reconnect attempt
+    pub fn current_delay(&self) -> Option<Duration> {
+        lock_unpoisoned(&self.backoff).current_delay()
+    }
+
+    /// Restart the backoff from its initial delays
+    ///
+    /// A reconnect attempt waiting out its delay is started right away.
+    pub fn reset(&self) {
+        lock_unpoisoned(&self.backoff).reset();
+        self.wake.notify_waiters();
+    }
+}
+
+/// `initial * 2^(attempt - 1)`, capped at `max`
+fn exponential(initial: Duration, attempt: u32, max: Duration) -> Duration {
+    let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+    initial.saturating_mul(factor).min(max)
+}
+
+/// Whether a stream should be reconnected after it ended with `error`
+///
+/// Authentication failures, client errors and the like end the stream,
+/// since reconnecting would fail the same way.
+fn is_reconnectable(error: &Error) -> bool {
+    match error {
+        Error::StreamConnection(_) | Error::Io(_) => true,
+        _ => {
+            error.is_retryable() || DisconnectCause::classify(error) == DisconnectCause::RateLimited
+        }
+    }
+}
+
+/// Minutes of backfill covering the downtime since `last_seen`
+///
+/// Capped at [`MAX_BACKFILL_MINUTES`]; `None` if there was no downtime.
+fn backfill_minutes(last_seen: DateTime<Utc>, now: DateTime<Utc>) -> Option<i64> {
+    let downtime = now - last_seen;
+    if downtime <= TimeDelta::zero() {
+        return None;
+    }
+    let mut minutes = downtime.num_minutes();
+    if downtime > TimeDelta::minutes(minutes) {
+        minutes += 1;
+    }
+    Some(minutes.min(MAX_BACKFILL_MINUTES))
+}
+
+/// Stream of messages that reconnects after disconnects
+///
+/// Connects on first poll. When the connection ends or fails, waits for the
+/// delay given by [`StreamBackoff`] and connects again; the backoff is reset
+/// once a connection succeeds. Messages that fail to parse, and
+/// [operational disconnect notices](crate::streaming::notice), are yielded as
+/// errors without ending the stream. If the stream ends with an error that
+/// reconnecting cannot fix (see [`Error::is_retryable`]), that error is the
+/// last item.
+///
+/// With [`max_reconnects`](crate::streaming::StreamOptionsBuilder::max_reconnects) set, the stream gives up
+/// after that many consecutive failed reconnects, ending with the last
+/// error after invoking the `on_give_up` callback.
+///
+/// With [`resume_from`](crate::streaming::StreamOptionsBuilder::resume_from)
+/// set, connection attempts request backfill of the downtime until one
+/// succeeds.
+///
+/// Message and reconnect counters are available through
+/// [`stats`](Self::stats), and the reconnect backoff through
+/// [`backoff`](Self::backoff).
+pub struct ReconnectingStream<T> {
+    messages: Pin<Box<dyn Stream<Item = Result<String>> + Send>>,
+    stats: StreamStatsHandle,
+    backoff: StreamBackoffHandle,
+    skip: Option<fn(&T) -> bool>,
+    strict: bool,
+    _message: PhantomData<fn() -> T>,
+}
+
+impl<T> ReconnectingStream<T> {
+    /// Handle on the counters of this stream
+    ///
+    /// The handle stays valid, and keeps updating, after being moved to
+    /// another task.
+    pub fn stats(&self) -> StreamStatsHandle {
+        self.stats.clone()
+    }
+
+    /// Handle on the reconnect backoff of this stream
+    ///
+    /// Like [`stats`](Self::stats), the handle can be moved to another task.
+    pub fn backoff(&self) -> StreamBackoffHandle {
+        self.backoff.clone()
+    }
+
+    /// Restart the reconnect backoff from its initial delays
+    ///
+    /// See [`StreamBackoffHandle::reset`].
+    pub fn reset_backoff(&self) {
+        self.backoff.reset();
+    }
+
+    /// Drop parsed messages for which `skip` returns true
+    pub(crate) fn skipping(mut self, skip: fn(&T) -> bool) -> Self {
+        self.skip = Some(skip);
+        self
+    }
+}
+
+impl<T: DeserializeOwned> Stream for ReconnectingStream<T> {
+    type Item = Result<T>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            let Some(message) = std::task::ready!(self.messages.as_mut().poll_next(cx)) else {
+                return Poll::Ready(None);
+            };
+            let strict = self.strict;
+            let parsed = message.and_then(|message| parse_message(&message, strict));
+            if let (Ok(item), Some(skip)) = (&parsed, self.skip) {
+                if skip(item) {
+                    continue;
+                }
+            }
+            return Poll::Ready(Some(parsed));
+        }
+    }
+}
+
+impl<H: HttpClient + Clone + 'static> Client<H> {
+    /// Open a [`ReconnectingStream`] on the streaming endpoint `path` with
+    /// query parameters `query`
+    pub(crate) fn reconnecting_stream<T>(
+        &self,
+        path: &'static str,
+        query: Vec<(String, String)>,
+        options: &StreamOptions,
+    ) -> ReconnectingStream<T> {
+        let client = self.clone();
+        let capacity = options.channel_capacity();
+        let stats = StreamStatsHandle::new(self.clock().now());
+        let recorder = stats.clone();
+        let backoff = StreamBackoffHandle::default();
+        let control = backoff.clone();
+        let max_reconnects = options.max_reconnects();
+        let on_give_up = options.on_give_up();
+        let resume_from = options.resume_from();
+        let messages = async_stream::stream! {
+            let mut backfill_query = query.clone();
+            if let Some(last_seen) = resume_from {
+                let now = client.clock().now();
+                if let Some(minutes) = backfill_minutes(last_seen, now) {
+                    backfill_query.push((BACKFILL_MINUTES_PARAM.to_string(), minutes.to_string()));
+                    let backfilled_from = now - TimeDelta::minutes(minutes);
+                    if backfilled_from > last_seen {
+                        tracing::warn!(
+                            path,
+                            %last_seen,
+                            %backfilled_from,
+                            "stream downtime exceeds backfill, messages may be missing"
+                        );
+                        yield Err(Error::StreamGap { last_seen, backfilled_from });
+                    }
+                }
+            }
+            let mut connected = false;
+            loop {
+                let attempt_query = if connected { &query } else { &backfill_query };
+                // open_stream signs a new request on every attempt
+                let error = match client.open_stream(path, attempt_query).await {
+                    Ok(response) => {
+                        connected = true;
+                        lock_unpoisoned(&backoff.backoff).reset();
+                        recorder.record_connected();
+                        let mut lines =
+                            ReceiverStream::new(spawn_reader(response.bytes_stream(), capacity));
+                        loop {
+                            match lines.next().await {
+                                Some(Ok(line)) => {
+                                    // X closes the connection right after a notice
+                                    let notice = disconnect_notice(&line);
+                                    if notice.is_none() {
+                                        recorder.record_message(line.len(), client.clock().now());
+                                    }
+                                    yield Ok(line);
+                                    if let Some(notice) = notice {
+                                        break Error::StreamDisconnectNotice(Box::new(notice));
+                                    }
+                                }
+                                Some(Err(error)) => break error,
+                                None => break Error::StreamDisconnected(EOF_REASON.to_string()),
+                            }
+                        }
+                    }
+                    Err(error) => error,
+                };
+
+                if !is_reconnectable(&error) {
+                    yield Err(error);
+                    break;
+                }
+                if max_reconnects.is_some_and(|max| backoff.attempts() >= max) {
+                    tracing::error!(
+                        path,
+                        error = %error,
+                        attempts = backoff.attempts(),
+                        "stream reconnects exhausted, giving up"
+                    );
+                    if let Some(callback) = &on_give_up {
+                        callback.call(&error);
+                    }
+                    yield Err(error);
+                    break;
+                }
+                // Listen before computing the delay so a concurrent reset is not missed
+                let woken = backoff.wake.notified();
+                tokio::pin!(woken);
+                woken.as_mut().enable();
+                let delay = lock_unpoisoned(&backoff.backoff).next_delay(&error);
+                recorder.record_reconnect(delay);
+                tracing::warn!(
+                    path,
+                    error = %error,
+                    delay_ms = delay.as_millis() as u64,
+                    "stream disconnected, reconnecting"
+                );
+                tokio::select! {
+                    _ = client.clock().sleep(delay) => {}
+                    _ = woken => tracing::info!(path, "stream backoff reset, reconnecting now"),
+                }
+            }
+        };
+
+        ReconnectingStream {
+            messages: Box::pin(messages),
+            stats,
+            backoff: control,
+            skip: None,
+            strict: self.strict_deserialization(),
+            _message: PhantomData,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ApiErrorDetail;
+
+    fn http_error(status: u16) -> Error {
+        ApiErrorDetail::new(status.to_string(), "stream error")
+            .with_status(status)
+            .into()
+    }
+
+    fn delays(backoff: &mut StreamBackoff, error: &Error, n: usize) -> Vec<u64> {
+        (0..n)
+            .map(|_| backoff.next_delay(error).as_millis() as u64)
+            .collect()
+    }
+
+    #[test]
+    fn test_network_error_backs_off_linearly() {
+        let mut backoff = StreamBackoff::new();
+        let err = Error::StreamDisconnected("connection reset".to_string());
+
+        assert_eq!(delays(&mut backoff, &err, 4), vec![250, 500, 750, 1000]);
+
+        // Capped at 16 seconds
+        for _ in 0..100 {
+            backoff.next_delay(&err);
+        }
+        assert_eq!(backoff.next_delay(&err), Duration::from_secs(16));
+    }
+
+    #[test]
+    fn test_http_503_backs_off_exponentially_from_5s() {
+        let mut backoff = StreamBackoff::new();
+        let err = http_error(503);
+
+        assert_eq!(
+            delays(&mut backoff, &err, 8),
+            vec![
+                5_000, 10_000, 20_000, 40_000, 80_000, 160_000, 320_000, 320_000
+            ]
+        );
+    }
+
+    #[test]
+    fn test_http_420_and_429_back_off_from_one_minute() {
+        for status in [420, 429] {
+            let mut backoff = StreamBackoff::new();
+            let err = http_error(status);
+
+            assert_eq!(
+                delays(&mut backoff, &err, 5),
+                vec![60_000, 120_000, 240_000, 480_000, 900_000]
+            );
+        }
+    }
+
+    #[test]
+    fn test_rate_limit_error_classified_as_rate_limited() {
+        let err = Error::RateLimitExceeded {
+            reset_at: chrono::Utc::now(),
+            endpoint: "/2/tweets/search/stream".to_string(),
+            remaining: 0,
+            limit: 50,
+        };
+        assert_eq!(
+            DisconnectCause::classify(&err),
+            DisconnectCause::RateLimited
+        );
+    }
+
+    #[test]
+    fn test_reset_restarts_sequences() {
+        let mut backoff = StreamBackoff::new();
+        let err = http_error(500);
+
+        backoff.next_delay(&err);
+        backoff.next_delay(&err);
+        assert_eq!(backoff.attempts(), 2);
+
+        backoff.reset();
+        assert_eq!(backoff.attempts(), 0);
+        assert_eq!(backoff.next_delay(&err), Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_auth_failure_is_not_reconnectable() {
+        assert!(is_reconnectable(&Error::StreamConnection(
+            "reset".to_string()
+        )));
+        assert!(is_reconnectable(&http_error(503)));
+        assert!(is_reconnectable(&http_error(420)));
+        assert!(!is_reconnectable(&http_error(400)));
+        assert!(!is_reconnectable(&Error::Authentication(
+            "invalid token".to_string()
+        )));
+    }
+
+    #[tokio::test]
+    async fn test_reconnect_signs_each_attempt_with_new_nonce() {
+        use crate::streaming::filtered::StreamTweet;
+        use crate::util::clock::MockClock;
+        use std::sync::Arc;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        // OAuth 1.0a cannot sign the app-only streams, so use a user-context path
+        const STREAM_PATH: &str = "/2/users/12/stream";
+
+        fn nonce(request: &wiremock::Request) -> String {
+            let header = request.headers["authorization"].to_str().unwrap();
+            header
+                .split(',')
+                .find_map(|param| param.trim().strip_prefix("oauth_nonce=\""))
+                .and_then(|value| value.strip_suffix('"'))
+                .expect("oauth_nonce in Authorization header")
+                .to_string()
+        }
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(STREAM_PATH))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(STREAM_PATH))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string("{\"data\":{\"id\":\"1\",\"text\":\"back\"}}\r\n"),
+            )
+            .mount(&server)
+            .await;
+
+        let clock = MockClock::new(chrono::Utc::now());
+        let client = Client::builder()
+            .oauth1("ck", "cs", "at", "ats")
+            .clock(Arc::new(clock.clone()))
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+
+        let mut stream = client.reconnecting_stream::<StreamTweet>(
+            STREAM_PATH,
+            Vec::new(),
+            &StreamOptions::new(),
+        );
+        let tweet = stream.next().await.unwrap().unwrap();
+        assert_eq!(tweet.data.text, "back");
+        assert_eq!(clock.sleeps(), vec![Duration::from_secs(5)]);
+
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 2);
+        assert_ne!(nonce(&requests[0]), nonce(&requests[1]));
+    }
+
+    #[tokio::test]
+    async fn test_stats_count_messages_and_reconnects() {
+        use crate::streaming::filtered::StreamTweet;
+        use crate::util::clock::{Clock, MockClock};
+        use std::sync::Arc;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        const STREAM_PATH: &str = "/2/users/12/stream";
+        const FIRST: &str = r#"{"data":{"id":"1","text":"one"}}"#;
+        const SECOND: &str = r#"{"data":{"id":"2","text":"two"}}"#;
+        const THIRD: &str = r#"{"data":{"id":"3","text":"three"}}"#;
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(STREAM_PATH))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_string(format!("{FIRST}\r\n\r\n{SECOND}\r\n")),
+            )
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(STREAM_PATH))
+            .respond_with(ResponseTemplate::new(200).set_body_string(format!("{THIRD}\r\n")))
+            .mount(&server)
+            .await;
+
+        let clock = MockClock::new(chrono::Utc::now());
+        let client = Client::builder()
+            .oauth1("ck", "cs", "at", "ats")
+            .clock(Arc::new(clock.clone()))
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+
+        let mut stream = client.reconnecting_stream::<StreamTweet>(
+            STREAM_PATH,
+            Vec::new(),
+            &StreamOptions::new(),
+        );
+        let stats = stream.stats();
+        assert_eq!(stats.snapshot().tweets_received, 0);
+
+        for _ in 0..3 {
+            stream.next().await.unwrap().unwrap();
+        }
+
+        // The first connection ended cleanly, so one network reconnect at 250ms
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot.tweets_received, 3);
+        assert_eq!(
+            snapshot.bytes_received,
+            (FIRST.len() + SECOND.len() + THIRD.len()) as u64
+        );
+        assert_eq!(snapshot.reconnects, 1);
+        assert_eq!(snapshot.current_backoff, None);
+        assert_eq!(snapshot.last_tweet_at, Some(clock.now()));
+        assert_eq!(clock.sleeps(), vec![Duration::from_millis(250)]);
+        assert_eq!(snapshot.tweets_per_second(clock.now()), 12.0);
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_reconnects() {
+        use crate::streaming::filtered::StreamTweet;
+        use crate::util::clock::MockClock;
+        use std::sync::{Arc, Mutex};
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        const STREAM_PATH: &str = "/2/users/12/stream";
+        const MAX_RECONNECTS: u32 = 3;
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(STREAM_PATH))
+            .respond_with(ResponseTemplate::new(503))
+            .expect(u64::from(MAX_RECONNECTS) + 1)
+            .mount(&server)
+            .await;
+
+        let clock = MockClock::new(chrono::Utc::now());
+        let client = Client::builder()
+            .oauth1("ck", "cs", "at", "ats")
+            .clock(Arc::new(clock.clone()))
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+
+        let given_up = Arc::new(Mutex::new(Vec::new()));
+        let recorder = given_up.clone();
+        let options = StreamOptions::custom()
+            .max_reconnects(MAX_RECONNECTS)
+            .on_give_up(move |error| recorder.lock().unwrap().push(error.to_string()))
+            .build()
+            .unwrap();
+
+        let mut stream =
+            client.reconnecting_stream::<StreamTweet>(STREAM_PATH, Vec::new(), &options);
+        let last = stream.next().await.unwrap().unwrap_err();
+        assert!(matches!(&last, Error::Api(detail) if detail.status() == Some(503)));
+        assert!(stream.next().await.is_none());
+
+        assert_eq!(*given_up.lock().unwrap(), vec![last.to_string()]);
+        assert_eq!(stream.stats().snapshot().reconnects, MAX_RECONNECTS);
+        assert_eq!(
+            clock.sleeps(),
+            vec![
+                Duration::from_secs(5),
+                Duration::from_secs(10),
+                Duration::from_secs(20)
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_backoff_handle_counts_attempts_and_resets() {
+        use crate::streaming::filtered::StreamTweet;
+        use crate::util::clock::MockClock;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        const STREAM_PATH: &str = "/2/users/12/stream";
+        const MAX_RECONNECTS: u32 = 2;
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(STREAM_PATH))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&server)
+            .await;
+
+        let client = Client::builder()
+            .oauth1("ck", "cs", "at", "ats")
+            .clock(Arc::new(MockClock::new(Utc::now())))
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+        let options = StreamOptions::custom()
+            .max_reconnects(MAX_RECONNECTS)
+            .build()
+            .unwrap();
+
+        let mut stream =
+            client.reconnecting_stream::<StreamTweet>(STREAM_PATH, Vec::new(), &options);
+        let backoff = stream.backoff();
+        assert_eq!(backoff.attempts(), 0);
+        assert_eq!(backoff.current_delay(), None);
+
+        stream.next().await.unwrap().unwrap_err();
+        assert_eq!(backoff.attempts(), MAX_RECONNECTS);
+        assert_eq!(backoff.current_delay(), Some(Duration::from_secs(10)));
+
+        stream.reset_backoff();
+        assert_eq!(backoff.attempts(), 0);
+        assert_eq!(backoff.current_delay(), None);
+        assert_eq!(
+            backoff.snapshot().next_delay(&http_error(503)),
+            Duration::from_secs(5)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resume_beyond_backfill_reports_gap() {
+        use crate::streaming::filtered::StreamTweet;
+        use crate::util::clock::MockClock;
+        use std::sync::Arc;
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        const STREAM_PATH: &str = "/2/users/12/stream";
+        const DOWNTIME_MINUTES: i64 = 12;
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(STREAM_PATH))
+            .and(query_param(
+                BACKFILL_MINUTES_PARAM,
+                MAX_BACKFILL_MINUTES.to_string(),
+            ))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string("{\"data\":{\"id\":\"1\",\"text\":\"hi\"}}\r\n"),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let now = Utc::now();
+        let client = Client::builder()
+            .oauth1("ck", "cs", "at", "ats")
+            .clock(Arc::new(MockClock::new(now)))
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+        let last_seen = now - TimeDelta::minutes(DOWNTIME_MINUTES);
+        let options = StreamOptions::custom()
+            .resume_from(last_seen)
+            .max_reconnects(0)
+            .build()
+            .unwrap();
+
+        let mut stream =
+            client.reconnecting_stream::<StreamTweet>(STREAM_PATH, Vec::new(), &options);
+        let gap = stream.next().await.unwrap().unwrap_err();
+        assert!(matches!(
+            gap,
+            Error::StreamGap { last_seen: seen, backfilled_from }
+                if seen == last_seen
+                    && backfilled_from == now - TimeDelta::minutes(MAX_BACKFILL_MINUTES)
+        ));
+        assert_eq!(stream.next().await.unwrap().unwrap().data.id, "1");
+
+        assert_eq!(backfill_minutes(now - TimeDelta::seconds(90), now), Some(2));
+        assert_eq!(backfill_minutes(now, now), None);
+    }
+}
@@ -0,0 +1,159 @@
+//! Routing of filtered stream tweets by rule tag
+//!
+//! A [`RuleRouter`] hands each [`StreamTweet`] to the handlers registered for
+//! the tags of its `matching_rules`, so consumers of a stream with many rules
+//! can keep one handler per concern.
+
+use crate::streaming::filtered::StreamTweet;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Handler receiving routed tweets
+type Handler = Box<dyn Fn(StreamTweet) + Send + Sync>;
+
+/// Dispatches stream tweets to handlers by matching rule tag
+///
+/// A tweet goes to the handler of every distinct tag among its
+/// `matching_rules`, once per tag. Tweets matching no tag with a handler
+/// (including tweets from untagged rules) go to the
+/// [`fallback`](Self::fallback) handler, if set.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use tokio_stream::StreamExt;
+/// use x_api_client::streaming::router::RuleRouter;
+/// use x_api_client::streaming::StreamOptions;
+///
+/// let router = RuleRouter::new()
+///     .route("rust", |tweet| println!("rust: {}", tweet.data.text))
+///     .route("go", |tweet| println!("go: {}", tweet.data.text))
+///     .fallback(|tweet| println!("other: {}", tweet.data.text));
+///
+/// let mut stream = client.filtered_stream_reconnecting(&StreamOptions::new());
+/// while let Some(tweet) = stream.next().await {
+///     router.dispatch(tweet?);
+/// }
+/// ```
+#[derive(Default)]
+pub struct RuleRouter {
+    handlers: HashMap<String, Handler>,
+    fallback: Option<Handler>,
+}
+
+impl RuleRouter {
+    /// Create a router without handlers
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Send tweets matching a rule tagged `tag` to `handler`
+    ///
+    /// Replaces any handler already registered for `tag`.
+    pub fn route(
+        mut self,
+        tag: impl Into<String>,
+        handler: impl Fn(StreamTweet) + Send + Sync + 'static,
+    ) -> Self {
+        self.handlers.insert(tag.into(), Box::new(handler));
+        self
+    }
+
+    /// Send tweets no other handler receives to `handler`
+    pub fn fallback(mut self, handler: impl Fn(StreamTweet) + Send + Sync + 'static) -> Self {
+        self.fallback = Some(Box::new(handler));
+        self
+    }
+
+    /// Hand `tweet` to the handlers of its matching rule tags
+    ///
+    /// Returns the number of tag handlers called; `0` means the tweet went
+    /// to the fallback handler, or was dropped if there is none.
+    pub fn dispatch(&self, tweet: StreamTweet) -> usize {
+        let mut handlers: Vec<&Handler> = Vec::new();
+        let mut tags: Vec<&str> = Vec::new();
+        for tag in tweet.matching_rules.iter().filter_map(|r| r.tag.as_deref()) {
+            if tags.contains(&tag) {
+                continue;
+            }
+            tags.push(tag);
+            if let Some(handler) = self.handlers.get(tag) {
+                handlers.push(handler);
+            }
+        }
+
+        let routed = handlers.len();
+        match handlers.split_last() {
+            Some((last, rest)) => {
+                for handler in rest {
+                    handler(tweet.clone());
+                }
+                last(tweet);
+            }
+            None => {
+                if let Some(fallback) = &self.fallback {
+                    fallback(tweet);
+                }
+            }
+        }
+        routed
+    }
+}
+
+impl fmt::Debug for RuleRouter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RuleRouter")
+            .field("tags", &self.handlers.keys().collect::<Vec<_>>())
+            .field("fallback", &self.fallback.is_some())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    fn tweet(id: &str, tags: &[&str]) -> StreamTweet {
+        let rules: Vec<_> = tags
+            .iter()
+            .map(|tag| serde_json::json!({"id": format!("rule-{}", tag), "tag": tag}))
+            .collect();
+        serde_json::from_value(serde_json::json!({
+            "data": {"id": id, "text": id},
+            "matching_rules": rules
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_tweets_routed_by_tag() {
+        let received: Arc<Mutex<Vec<(&str, String)>>> = Arc::default();
+        let handler = |name: &'static str| {
+            let received = received.clone();
+            move |tweet: StreamTweet| received.lock().unwrap().push((name, tweet.data.id))
+        };
+        let router = RuleRouter::new()
+            .route("rust", handler("rust"))
+            .route("go", handler("go"))
+            .fallback(handler("fallback"));
+
+        assert_eq!(router.dispatch(tweet("1", &["rust"])), 1);
+        assert_eq!(router.dispatch(tweet("2", &["go"])), 1);
+        assert_eq!(router.dispatch(tweet("3", &["rust", "go", "rust"])), 2);
+        assert_eq!(router.dispatch(tweet("4", &["zig"])), 0);
+
+        let mut received = received.lock().unwrap().clone();
+        received.sort();
+        assert_eq!(
+            received,
+            vec![
+                ("fallback", "4".to_string()),
+                ("go", "2".to_string()),
+                ("go", "3".to_string()),
+                ("rust", "1".to_string()),
+                ("rust", "3".to_string()),
+            ]
+        );
+    }
+}
@@ -1 +1,815 @@
 //! Stream rule management
+
+use crate::builder::request::Body;
+use crate::client::{Client, HttpClient};
+use crate::endpoints::paths;
+use crate::error::{Error, Result};
+use crate::models::common::{ApiError, ApiResponse, RuleId};
+use crate::pagination::cursor::{PageQuery, PaginationSpec};
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt;
+
+/// Query parameter validating rules without creating them
+const DRY_RUN_PARAM: &str = "dry_run";
+
+/// Per-rule error field with a short description of the problem
+const TITLE_FIELD: &str = "title";
+
+/// Per-rule error field listing the problems found
+const DETAILS_FIELD: &str = "details";
+
+/// Response meta field counting the rules created and deleted
+const SUMMARY_FIELD: &str = "summary";
+
+/// Rule caps of a filtered stream access tier
+///
+/// The API rejects rule changes that exceed the tier's caps; [`add_rules`]
+/// checks them client-side to fail before the round trip.
+///
+/// [`add_rules`]: crate::Client::add_rules
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum StreamTier {
+    /// Essential access: 5 rules of up to 512 characters
+    Essential,
+    /// Elevated access: 25 rules of up to 512 characters
+    Elevated,
+    /// Pro access: 1,000 rules of up to 1,024 characters
+    #[default]
+    Pro,
+    /// Enterprise access: 25,000 rules of up to 2,048 characters
+    Enterprise,
+}
+
+impl StreamTier {
+    /// Maximum number of rules the tier allows
+    pub fn max_rules(self) -> usize {
+        match self {
+            StreamTier::Essential => 5,
+            StreamTier::Elevated => 25,
+            StreamTier::Pro => 1_000,
+            StreamTier::Enterprise => 25_000,
+        }
+    }
+
+    /// Maximum length of a rule value, in characters
+    pub fn max_rule_length(self) -> usize {
+        match self {
+            StreamTier::Essential | StreamTier::Elevated => 512,
+            StreamTier::Pro => 1_024,
+            StreamTier::Enterprise => 2_048,
+        }
+    }
+
+    /// Check a batch of rules against the tier's caps
+    ///
+    /// Only the batch itself is counted; rules already active on the stream
+    /// still count towards the server-side cap.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidRequest`] if the batch has more rules than the
+    /// tier allows or a rule value is too long.
+    pub fn validate(self, rules: &[StreamRule]) -> Result<()> {
+        if rules.len() > self.max_rules() {
+            return Err(Error::InvalidRequest(format!(
+                "{} rules exceed the {:?} tier cap of {}",
+                rules.len(),
+                self,
+                self.max_rules()
+            )));
+        }
+
+        for rule in rules {
+            let length = rule.value.chars().count();
+            if length > self.max_rule_length() {
+                return Err(Error::InvalidRequest(format!(
+                    "Rule of {} characters exceeds the {:?} tier limit of {}: {}",
+                    length,
+                    self,
+                    self.max_rule_length(),
+                    rule.value
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A filtered stream rule to add
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StreamRule {
+    /// Rule query (e.g. `"rust lang:en -is:retweet"`)
+    pub value: String,
+
+    /// Optional label reported in `matching_rules`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+}
+
+impl StreamRule {
+    /// Create an untagged rule
+    pub fn new(value: impl Into<String>) -> Self {
+        Self {
+            value: value.into(),
+            tag: None,
+        }
+    }
+
+    /// Label the rule
+    pub fn with_tag(mut self, tag: impl Into<String>) -> Self {
+        self.tag = Some(tag.into());
+        self
+    }
+}
+
+/// A rule active on the filtered stream
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Rule {
+    /// ID assigned by the API
+    pub id: RuleId,
+
+    /// Rule query
+    pub value: String,
+
+    /// Label reported in `matching_rules`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+}
+
+/// Why the API rejected one rule
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleDiagnostic {
+    /// Value of the rejected rule
+    pub value: String,
+
+    /// Short description of the problem (e.g. `UnprocessableEntity`)
+    pub title: Option<String>,
+
+    /// Specific problems found in the rule (e.g. `Unbalanced parentheses`)
+    pub details: Vec<String>,
+}
+
+impl RuleDiagnostic {
+    /// Diagnostic for a per-rule API error, if it names a rule value
+    fn from_api_error(error: &ApiError) -> Option<Self> {
+        let field = |name: &str| error.additional_fields.get(name);
+        Some(Self {
+            value: error.value.clone()?,
+            title: field(TITLE_FIELD)
+                .and_then(serde_json::Value::as_str)
+                .map(str::to_string),
+            details: field(DETAILS_FIELD)
+                .and_then(serde_json::Value::as_array)
+                .map(|details| {
+                    details
+                        .iter()
+                        .filter_map(serde_json::Value::as_str)
+                        .map(str::to_string)
+                        .collect()
+                })
+                .unwrap_or_default(),
+        })
+    }
+}
+
+/// Rules rejected by the API
+///
+/// Returned as [`Error::RuleValidation`] by [`add_rules`](crate::Client::add_rules)
+/// (including dry runs) when the API reports per-rule errors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleValidationError {
+    /// Rules the API rejected, and why
+    pub rejected: Vec<RuleDiagnostic>,
+
+    /// Rules of the same request the API accepted (or, on a dry run, would
+    /// accept)
+    pub accepted: Vec<Rule>,
+}
+
+impl fmt::Display for RuleValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Rules rejected:")?;
+        for (i, rule) in self.rejected.iter().enumerate() {
+            let separator = if i == 0 { " " } else { "; " };
+            write!(f, "{}'{}'", separator, rule.value)?;
+            if !rule.details.is_empty() {
+                write!(f, " ({})", rule.details.join(", "))?;
+            } else if let Some(title) = &rule.title {
+                write!(f, " ({})", title)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Options for [`add_rules`](crate::Client::add_rules)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AddRulesOptions {
+    tier: StreamTier,
+    dry_run: bool,
+}
+
+impl AddRulesOptions {
+    /// Create options for the default tier ([`StreamTier::Pro`])
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Validate against the caps of `tier`
+    pub fn tier(mut self, tier: StreamTier) -> Self {
+        self.tier = tier;
+        self
+    }
+
+    /// Only validate the rules server-side, without creating them
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Get the tier whose caps are enforced
+    pub fn get_tier(&self) -> StreamTier {
+        self.tier
+    }
+
+    /// Check whether this is a dry run
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+}
+
+/// Changes made by [`sync_rules`](crate::Client::sync_rules)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RuleSyncReport {
+    /// Rules created (or, on a dry run, that would be created)
+    pub added: Vec<Rule>,
+
+    /// Rules deleted (or, on a dry run, that would be deleted)
+    pub deleted: Vec<Rule>,
+
+    /// Active rules that were already desired
+    pub unchanged: Vec<Rule>,
+
+    /// Whether the changes were only validated
+    pub dry_run: bool,
+}
+
+impl RuleSyncReport {
+    /// Check whether the sync changed (or would change) anything
+    pub fn is_noop(&self) -> bool {
+        self.added.is_empty() && self.deleted.is_empty()
+    }
+}
+
+/// Counts reported in the `meta.summary` of a rule change
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct RuleSummary {
+    /// Rules created
+    pub created: u32,
+    /// Rules that could not be created
+    pub not_created: u32,
+    /// Rules deleted
+    pub deleted: u32,
+    /// Rules that could not be deleted
+    pub not_deleted: u32,
+}
+
+/// Result of [`modify_rules`](crate::Client::modify_rules)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RuleModification {
+    /// Rules active after the change (or, on a dry run, that would be)
+    pub rules: Vec<Rule>,
+
+    /// Rules created (or, on a dry run, that would be created)
+    pub added: Vec<Rule>,
+
+    /// Counts reported by the API
+    pub summary: RuleSummary,
+}
+
+#[derive(Serialize)]
+struct AddRulesBody<'a> {
+    add: &'a [StreamRule],
+}
+
+#[derive(Serialize)]
+struct DeleteRulesBody<'a> {
+    delete: RuleIds<'a>,
+}
+
+#[derive(Serialize)]
+struct ModifyRulesBody<'a> {
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    add: &'a [StreamRule],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    delete: Option<RuleIds<'a>>,
+}
+
+#[derive(Serialize)]
+struct RuleIds<'a> {
+    ids: &'a [RuleId],
+}
+
+impl<H: HttpClient + Clone> Client<H> {
+    /// Add filtered stream rules
+    ///
+    /// Rules are checked against the caps of the configured [`StreamTier`]
+    /// before the request is sent. Returns the created rules (or, on a dry
+    /// run, the rules that would be created).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidRequest`] if the rules exceed the tier's caps,
+    /// or [`Error::RuleValidation`] listing the rejected rules if the API
+    /// rejects any of them.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use x_api_client::streaming::rules::{AddRulesOptions, StreamRule, StreamTier};
+    ///
+    /// let rules = vec![StreamRule::new("rust lang:en").with_tag("rust")];
+    /// let created = client
+    ///     .add_rules(&rules, &AddRulesOptions::new().tier(StreamTier::Elevated))
+    ///     .await?;
+    /// ```
+    pub async fn add_rules(
+        &self,
+        rules: &[StreamRule],
+        opts: &AddRulesOptions,
+    ) -> Result<Vec<Rule>> {
+        opts.tier.validate(rules)?;
+        self.post_rules(&AddRulesBody { add: rules }, opts.dry_run)
+            .await
+    }
+
+    /// Delete filtered stream rules by ID
+    ///
+    /// With `dry_run`, the deletion is only validated server-side.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidRequest`] if the API rejects any of the IDs.
+    pub async fn delete_rules(&self, ids: &[RuleId], dry_run: bool) -> Result<()> {
+        let body = DeleteRulesBody {
+            delete: RuleIds { ids },
+        };
+        self.post_rules(&body, dry_run).await?;
+        Ok(())
+    }
+
+    /// Add and delete filtered stream rules in one request
+    ///
+    /// Saves a round trip over [`add_rules`](Self::add_rules) followed by
+    /// [`delete_rules`](Self::delete_rules). The rules to add are checked
+    /// against the caps of the configured [`StreamTier`] first. The active
+    /// rules are listed beforehand so that the resulting rule set can be
+    /// returned along with the API's summary.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidRequest`] if there is nothing to change or the
+    /// rules exceed the tier's caps, or [`Error::RuleValidation`] listing the
+    /// rejected rules if the API rejects any of them.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use x_api_client::streaming::rules::{AddRulesOptions, StreamRule};
+    ///
+    /// let result = client
+    ///     .modify_rules(
+    ///         vec![StreamRule::new("rust lang:en")],
+    ///         vec!["1165037377523306497".to_string()],
+    ///         &AddRulesOptions::new(),
+    ///     )
+    ///     .await?;
+    /// println!("+{} -{}", result.summary.created, result.summary.deleted);
+    /// println!("{} rules active", result.rules.len());
+    /// ```
+    pub async fn modify_rules(
+        &self,
+        add: Vec<StreamRule>,
+        delete: Vec<RuleId>,
+        opts: &AddRulesOptions,
+    ) -> Result<RuleModification> {
+        if add.is_empty() && delete.is_empty() {
+            return Err(Error::InvalidRequest(
+                "No rules to add or delete".to_string(),
+            ));
+        }
+        opts.tier.validate(&add)?;
+
+        let current = self.get_rules().await?;
+        self.apply_rule_changes(current, &add, &delete, opts.dry_run)
+            .await
+    }
+
+    /// Send one combined add/delete request against the `current` rules
+    async fn apply_rule_changes(
+        &self,
+        current: Vec<Rule>,
+        add: &[StreamRule],
+        delete: &[RuleId],
+        dry_run: bool,
+    ) -> Result<RuleModification> {
+        let body = ModifyRulesBody {
+            add,
+            delete: (!delete.is_empty()).then_some(RuleIds { ids: delete }),
+        };
+        let response = self.post_rules_response(&body, dry_run).await?;
+        let summary = match response
+            .meta
+            .and_then(|mut meta| meta.additional_fields.remove(SUMMARY_FIELD))
+        {
+            Some(summary) => serde_json::from_value(summary)?,
+            None => RuleSummary::default(),
+        };
+        let added = response.data.unwrap_or_default();
+        let rules = current
+            .into_iter()
+            .filter(|rule| !delete.contains(&rule.id))
+            .chain(added.iter().cloned())
+            .collect();
+        Ok(RuleModification {
+            rules,
+            added,
+            summary,
+        })
+    }
+
+    /// Get every rule active on the filtered stream
+    pub async fn get_rules(&self) -> Result<Vec<Rule>> {
+        self.get_all_pages(
+            paths::TWEETS_SEARCH_STREAM_RULES,
+            PaginationSpec::PAGINATION_TOKEN,
+            PageQuery::default(),
+            None,
+            None,
+        )
+        .await
+    }
+
+    /// Make the active rules match `desired`
+    ///
+    /// Lists the current rules, then deletes those whose value is not desired
+    /// and adds desired values that are not active yet in one combined
+    /// request (see [`modify_rules`](Self::modify_rules)). Rules are matched by
+    /// value only; a rule whose tag differs is left as is. Running it again
+    /// with the same input changes nothing.
+    ///
+    /// The desired set is checked against the tier's caps up front, since it
+    /// becomes the complete rule set. With
+    /// [`dry_run`](AddRulesOptions::dry_run), changes are validated
+    /// server-side but not applied.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use x_api_client::streaming::rules::{AddRulesOptions, StreamRule};
+    ///
+    /// let report = client
+    ///     .sync_rules(vec![StreamRule::new("rust lang:en")], &AddRulesOptions::new())
+    ///     .await?;
+    /// println!("+{} -{}", report.added.len(), report.deleted.len());
+    /// ```
+    pub async fn sync_rules(
+        &self,
+        desired: Vec<StreamRule>,
+        opts: &AddRulesOptions,
+    ) -> Result<RuleSyncReport> {
+        opts.tier.validate(&desired)?;
+
+        let current = self.get_rules().await?;
+        let desired_values: HashSet<&str> = desired.iter().map(|r| r.value.as_str()).collect();
+        let current_values: HashSet<&str> = current.iter().map(|r| r.value.as_str()).collect();
+
+        let (unchanged, deleted): (Vec<Rule>, Vec<Rule>) = current
+            .iter()
+            .cloned()
+            .partition(|rule| desired_values.contains(rule.value.as_str()));
+        let mut seen = HashSet::new();
+        let to_add: Vec<StreamRule> = desired
+            .iter()
+            .filter(|rule| !current_values.contains(rule.value.as_str()))
+            .filter(|rule| seen.insert(rule.value.as_str()))
+            .cloned()
+            .collect();
+
+        let ids: Vec<RuleId> = deleted.iter().map(|rule| rule.id.clone()).collect();
+        let added = if to_add.is_empty() && ids.is_empty() {
+            Vec::new()
+        } else {
+            self.apply_rule_changes(current, &to_add, &ids, opts.dry_run)
+                .await?
+                .added
+        };
+
+        Ok(RuleSyncReport {
+            added,
+            deleted,
+            unchanged,
+            dry_run: opts.dry_run,
+        })
+    }
+
+    /// POST a rule change, failing on any per-rule error
+    ///
+    /// Errors naming rule values are reported as [`Error::RuleValidation`].
+    async fn post_rules<B: Serialize>(&self, body: &B, dry_run: bool) -> Result<Vec<Rule>> {
+        Ok(self
+            .post_rules_response(body, dry_run)
+            .await?
+            .data
+            .unwrap_or_default())
+    }
+
+    /// Like [`post_rules`](Self::post_rules), keeping the response envelope
+    async fn post_rules_response<B: Serialize>(
+        &self,
+        body: &B,
+        dry_run: bool,
+    ) -> Result<ApiResponse<Vec<Rule>>> {
+        let query = if dry_run {
+            vec![(DRY_RUN_PARAM.to_string(), true.to_string())]
+        } else {
+            Vec::new()
+        };
+        let body = Body::json(body)?;
+        let mut response = self
+            .send::<Vec<Rule>>(
+                Method::POST,
+                paths::TWEETS_SEARCH_STREAM_RULES,
+                &query,
+                Some(&body),
+                None,
+            )
+            .await?;
+
+        if let Some(errors) = response.errors.take().filter(|errors| !errors.is_empty()) {
+            let rejected: Option<Vec<_>> =
+                errors.iter().map(RuleDiagnostic::from_api_error).collect();
+            if let Some(rejected) = rejected {
+                return Err(Error::RuleValidation(Box::new(RuleValidationError {
+                    rejected,
+                    accepted: response.data.unwrap_or_default(),
+                })));
+            }
+            let messages: Vec<_> = errors.into_iter().map(|e| e.message).collect();
+            return Err(Error::InvalidRequest(format!(
+                "Rules rejected: {}",
+                messages.join("; ")
+            )));
+        }
+        Ok(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::bearer::BearerTokenProvider;
+    use std::sync::Arc;
+    use wiremock::matchers::{body_json, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn client(base_url: String) -> Client {
+        Client::builder()
+            .auth(Arc::new(BearerTokenProvider::new("test")))
+            .base_url(base_url)
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_add_rules_posts_batch() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/2/tweets/search/stream/rules"))
+            .and(query_param("dry_run", "true"))
+            .and(body_json(serde_json::json!({
+                "add": [{"value": "rust lang:en", "tag": "rust"}]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [{"id": "1", "value": "rust lang:en", "tag": "rust"}],
+                "meta": {"summary": {"created": 1, "not_created": 0}}
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let rules = vec![StreamRule::new("rust lang:en").with_tag("rust")];
+        let created = client(server.uri())
+            .add_rules(&rules, &AddRulesOptions::new().dry_run(true))
+            .await
+            .unwrap();
+        assert_eq!(created.len(), 1);
+        assert_eq!(created[0].id, "1");
+    }
+
+    #[tokio::test]
+    async fn test_modify_rules_sends_combined_body() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/tweets/search/stream/rules"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [
+                    {"id": "6", "value": "ziglang"},
+                    {"id": "7", "value": "golang"},
+                    {"id": "8", "value": "python"}
+                ]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/2/tweets/search/stream/rules"))
+            .and(body_json(serde_json::json!({
+                "add": [{"value": "rust lang:en", "tag": "rust"}],
+                "delete": {"ids": ["7", "8"]}
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [{"id": "9", "value": "rust lang:en", "tag": "rust"}],
+                "meta": {
+                    "sent": "2024-01-01T00:00:00.000Z",
+                    "summary": {"created": 1, "not_created": 0, "deleted": 2, "not_deleted": 0}
+                }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let result = client(server.uri())
+            .modify_rules(
+                vec![StreamRule::new("rust lang:en").with_tag("rust")],
+                vec!["7".to_string(), "8".to_string()],
+                &AddRulesOptions::new(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result.added[0].id, "9");
+        let ids: Vec<&str> = result.rules.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["6", "9"]);
+        assert_eq!(
+            result.summary,
+            RuleSummary {
+                created: 1,
+                not_created: 0,
+                deleted: 2,
+                not_deleted: 0,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_reports_rejected_rule() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/2/tweets/search/stream/rules"))
+            .and(query_param("dry_run", "true"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [{"id": "1", "value": "rust lang:en"}],
+                "errors": [{
+                    "value": "(rust",
+                    "details": ["Unbalanced parentheses"],
+                    "title": "UnprocessableEntity",
+                    "type": "https://api.twitter.com/2/problems/invalid-rules"
+                }],
+                "meta": {"summary": {"created": 1, "not_created": 1, "valid": 1, "invalid": 1}}
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let rules = vec![StreamRule::new("rust lang:en"), StreamRule::new("(rust")];
+        let err = client(server.uri())
+            .add_rules(&rules, &AddRulesOptions::new().dry_run(true))
+            .await
+            .unwrap_err();
+
+        let Error::RuleValidation(validation) = err else {
+            panic!("expected RuleValidation, got {:?}", err);
+        };
+        assert_eq!(
+            validation.rejected,
+            vec![RuleDiagnostic {
+                value: "(rust".to_string(),
+                title: Some("UnprocessableEntity".to_string()),
+                details: vec!["Unbalanced parentheses".to_string()],
+            }]
+        );
+        assert_eq!(validation.accepted.len(), 1);
+        assert_eq!(validation.accepted[0].value, "rust lang:en");
+        assert_eq!(
+            validation.to_string(),
+            "Rules rejected: '(rust' (Unbalanced parentheses)"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_over_long_rule_rejected_before_request() {
+        // No server: validation must fail before any request is made
+        let client = client("http://127.0.0.1:9".to_string());
+        let opts = AddRulesOptions::new().tier(StreamTier::Elevated);
+
+        let at_limit = vec![StreamRule::new("a".repeat(512))];
+        assert!(StreamTier::Elevated.validate(&at_limit).is_ok());
+
+        let too_long = vec![StreamRule::new("a".repeat(513))];
+        let err = client.add_rules(&too_long, &opts).await.unwrap_err();
+        assert!(matches!(err, Error::InvalidRequest(_)));
+
+        // The same rule fits the higher tier
+        assert!(StreamTier::Pro.validate(&too_long).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_rule_count_cap() {
+        let client = client("http://127.0.0.1:9".to_string());
+        let rules: Vec<_> = (0..6)
+            .map(|i| StreamRule::new(format!("rule{}", i)))
+            .collect();
+
+        let err = client
+            .add_rules(&rules, &AddRulesOptions::new().tier(StreamTier::Essential))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidRequest(_)));
+        assert!(StreamTier::Essential.validate(&rules[..5]).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_sync_rules_adds_and_deletes_by_value() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/tweets/search/stream/rules"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [
+                    {"id": "1", "value": "rust lang:en", "tag": "rust"},
+                    {"id": "2", "value": "golang"}
+                ],
+                "meta": {"result_count": 2}
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/2/tweets/search/stream/rules"))
+            .and(body_json(serde_json::json!({
+                "add": [{"value": "ziglang"}],
+                "delete": {"ids": ["2"]}
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [{"id": "3", "value": "ziglang"}],
+                "meta": {"summary": {"created": 1, "not_created": 0, "deleted": 1, "not_deleted": 0}}
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let desired = vec![StreamRule::new("rust lang:en"), StreamRule::new("ziglang")];
+        let report = client(server.uri())
+            .sync_rules(desired, &AddRulesOptions::new())
+            .await
+            .unwrap();
+
+        let ids = |rules: &[Rule]| rules.iter().map(|r| r.id.clone()).collect::<Vec<_>>();
+        assert_eq!(ids(&report.added), vec!["3"]);
+        assert_eq!(ids(&report.deleted), vec!["2"]);
+        assert_eq!(ids(&report.unchanged), vec!["1"]);
+        assert!(!report.dry_run);
+        assert!(!report.is_noop());
+    }
+
+    #[tokio::test]
+    async fn test_sync_rules_already_in_sync() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/tweets/search/stream/rules"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [{"id": "1", "value": "rust"}]
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let report = client(server.uri())
+            .sync_rules(
+                vec![StreamRule::new("rust")],
+                &AddRulesOptions::new().dry_run(true),
+            )
+            .await
+            .unwrap();
+        assert!(report.is_noop());
+        assert!(report.dry_run);
+    }
+}
@@ -1 +1,182 @@
 //! Sample streams (1% and 10% volume)
+//!
+//! The 10% sample stream is split into [`SAMPLE10_PARTITIONS`] partitions;
+//! the full 10% is only received when connected to all of them.
+//! [`Client::sample10_all_partitions`] does that and merges the partitions
+//! into one stream.
+
+use crate::client::{Client, HttpClient};
+use crate::endpoints::paths;
+use crate::error::Result;
+use crate::models::common::TweetId;
+use crate::streaming::filtered::StreamTweet;
+use crate::streaming::reconnect::ReconnectingStream;
+use crate::streaming::{PARTITION_PARAM, StreamOptions};
+use std::collections::{HashSet, VecDeque};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio_stream::{Stream, StreamMap};
+
+/// Number of partitions of the 10% sample stream
+pub const SAMPLE10_PARTITIONS: u8 = 2;
+
+/// Number of recent tweet ids remembered to drop duplicates across partitions
+pub const SAMPLE10_DEDUP_WINDOW: usize = 10_000;
+
+/// Tweets from every partition of the 10% sample stream
+///
+/// Returned by [`Client::sample10_all_partitions`]. Each partition is a
+/// [`ReconnectingStream`] that reconnects on its own, so a disconnect of one
+/// partition does not interrupt the others. A tweet seen among the last
+/// [`SAMPLE10_DEDUP_WINDOW`] tweets is yielded only once. Errors are yielded
+/// as they come, from whichever partition they occur on.
+pub struct Sample10Stream {
+    partitions: StreamMap<u8, ReconnectingStream<StreamTweet>>,
+    seen: RecentIds,
+}
+
+impl Sample10Stream {
+    /// Stream of one partition, until polled past its end
+    pub fn partition(&self, partition: u8) -> Option<&ReconnectingStream<StreamTweet>> {
+        self.partitions
+            .iter()
+            .find_map(|(key, stream)| (*key == partition).then_some(stream))
+    }
+}
+
+impl Stream for Sample10Stream {
+    type Item = Result<StreamTweet>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            match Pin::new(&mut self.partitions).poll_next(cx) {
+                Poll::Ready(Some((_, Ok(tweet)))) => {
+                    if self.seen.insert(&tweet.data.id) {
+                        return Poll::Ready(Some(Ok(tweet)));
+                    }
+                }
+                Poll::Ready(Some((_, Err(e)))) => return Poll::Ready(Some(Err(e))),
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+/// Bounded set of the most recently seen tweet ids
+#[derive(Debug, Default)]
+struct RecentIds {
+    ids: HashSet<TweetId>,
+    order: VecDeque<TweetId>,
+}
+
+impl RecentIds {
+    /// Remember `id`, returning false if it was already among the recent ids
+    fn insert(&mut self, id: &str) -> bool {
+        if self.ids.contains(id) {
+            return false;
+        }
+        if self.order.len() == SAMPLE10_DEDUP_WINDOW {
+            if let Some(oldest) = self.order.pop_front() {
+                self.ids.remove(&oldest);
+            }
+        }
+        self.ids.insert(id.to_string());
+        self.order.push_back(id.to_string());
+        true
+    }
+}
+
+impl<H: HttpClient + Clone + 'static> Client<H> {
+    /// Connect to every partition of the 10% sample stream
+    ///
+    /// Opens one reconnecting connection per partition (see
+    /// [`Sample10Stream`]) and merges them, dropping tweets delivered by
+    /// more than one partition. Requires an auth provider supporting
+    /// app-only authentication and access to the 10% stream.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use tokio_stream::StreamExt;
+    /// use x_api_client::streaming::StreamOptions;
+    ///
+    /// let mut stream = client.sample10_all_partitions(&StreamOptions::new());
+    /// while let Some(tweet) = stream.next().await {
+    ///     println!("{}", tweet?.data.text);
+    /// }
+    /// ```
+    pub fn sample10_all_partitions(&self, options: &StreamOptions) -> Sample10Stream {
+        let mut partitions = StreamMap::new();
+        for partition in 1..=SAMPLE10_PARTITIONS {
+            let query = vec![(PARTITION_PARAM.to_string(), partition.to_string())];
+            let stream = self.reconnecting_stream(paths::TWEETS_SAMPLE10_STREAM, query, options);
+            let stream = if options.skip_withheld() {
+                stream.skipping(StreamTweet::is_withheld)
+            } else {
+                stream
+            };
+            partitions.insert(partition, stream);
+        }
+        Sample10Stream {
+            partitions,
+            seen: RecentIds::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::bearer::BearerTokenProvider;
+    use crate::util::clock::MockClock;
+    use std::sync::Arc;
+    use tokio_stream::StreamExt;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn body(ids: &[&str]) -> ResponseTemplate {
+        let lines: String = ids
+            .iter()
+            .map(|id| format!("{{\"data\":{{\"id\":\"{id}\",\"text\":\"tweet {id}\"}}}}\r\n"))
+            .collect();
+        ResponseTemplate::new(200).set_body_string(lines)
+    }
+
+    #[tokio::test]
+    async fn test_partitions_merged_and_deduplicated() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/2/tweets/sample10/stream"))
+            .and(query_param("partition", "1"))
+            .respond_with(body(&["1", "2", "3"]))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/2/tweets/sample10/stream"))
+            .and(query_param("partition", "2"))
+            .respond_with(body(&["3", "4", "2"]))
+            .mount(&server)
+            .await;
+
+        let client = Client::builder()
+            .auth(Arc::new(BearerTokenProvider::new("token")))
+            .clock(Arc::new(MockClock::new(chrono::Utc::now())))
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+        let stream = client.sample10_all_partitions(&StreamOptions::new());
+        assert!(stream.partition(1).is_some());
+        assert!(stream.partition(2).is_some());
+
+        // Partitions reconnect and replay their tweets, which must not
+        // come out again
+        let mut ids: Vec<String> = stream
+            .take(4)
+            .map(|tweet| tweet.unwrap().data.id)
+            .collect()
+            .await;
+        ids.sort();
+        assert_eq!(ids, vec!["1", "2", "3", "4"]);
+    }
+}
@@ -0,0 +1,96 @@
+//! Stream health counters
+//!
+//! A [`ReconnectingStream`](crate::streaming::reconnect::ReconnectingStream)
+//! keeps [`StreamStats`] up to date as messages arrive and connections drop.
+//! Take a [`StreamStatsHandle`] before handing the stream to its consumer to
+//! watch it from elsewhere, e.g. to alert when no tweet arrived for a while.
+
+use crate::util::lock_unpoisoned;
+use chrono::{DateTime, Utc};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Counters of a stream since it was opened
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StreamStats {
+    /// When the stream was opened
+    pub started_at: DateTime<Utc>,
+
+    /// Messages received, excluding keep-alives and disconnect notices
+    pub tweets_received: u64,
+
+    /// Size of the received messages, excluding line delimiters
+    pub bytes_received: u64,
+
+    /// Reconnect attempts scheduled after a disconnect
+    pub reconnects: u32,
+
+    /// When the last message was received
+    pub last_tweet_at: Option<DateTime<Utc>>,
+
+    /// Delay before the pending reconnect attempt, while disconnected
+    pub current_backoff: Option<Duration>,
+}
+
+impl StreamStats {
+    fn new(started_at: DateTime<Utc>) -> Self {
+        Self {
+            started_at,
+            tweets_received: 0,
+            bytes_received: 0,
+            reconnects: 0,
+            last_tweet_at: None,
+            current_backoff: None,
+        }
+    }
+
+    /// Average message rate between the start of the stream and `now`
+    pub fn tweets_per_second(&self, now: DateTime<Utc>) -> f64 {
+        let elapsed = (now - self.started_at).as_seconds_f64();
+        if elapsed <= 0.0 {
+            return 0.0;
+        }
+        self.tweets_received as f64 / elapsed
+    }
+}
+
+/// Shared view of the [`StreamStats`] of a stream
+///
+/// Clones observe the same stream.
+#[derive(Debug, Clone)]
+pub struct StreamStatsHandle {
+    stats: Arc<Mutex<StreamStats>>,
+}
+
+impl StreamStatsHandle {
+    pub(crate) fn new(started_at: DateTime<Utc>) -> Self {
+        Self {
+            stats: Arc::new(Mutex::new(StreamStats::new(started_at))),
+        }
+    }
+
+    /// Copy of the current counters
+    pub fn snapshot(&self) -> StreamStats {
+        lock_unpoisoned(&self.stats).clone()
+    }
+
+    /// Record a message of `bytes` bytes received at `at`
+    pub(crate) fn record_message(&self, bytes: usize, at: DateTime<Utc>) {
+        let mut stats = lock_unpoisoned(&self.stats);
+        stats.tweets_received = stats.tweets_received.saturating_add(1);
+        stats.bytes_received = stats.bytes_received.saturating_add(bytes as u64);
+        stats.last_tweet_at = Some(at);
+    }
+
+    /// Record a reconnect attempt scheduled after `delay`
+    pub(crate) fn record_reconnect(&self, delay: Duration) {
+        let mut stats = lock_unpoisoned(&self.stats);
+        stats.reconnects = stats.reconnects.saturating_add(1);
+        stats.current_backoff = Some(delay);
+    }
+
+    /// Record a successful connection
+    pub(crate) fn record_connected(&self) {
+        lock_unpoisoned(&self.stats).current_backoff = None;
+    }
+}
@@ -0,0 +1,234 @@
+//! Bounded in-memory cache with expiring entries
+//!
+//! Used to remember lookups that rarely change, such as the user ID behind a
+//! username (see [`ClientBuilder::username_cache`](crate::client::ClientBuilder::username_cache)),
+//! so repeating them does not spend rate limit. Entries expire after a
+//! time-to-live, and the least recently used entry is evicted once the cache
+//! is full.
+
+use crate::error::{Error, Result};
+use crate::util::lock_unpoisoned;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Default maximum number of entries
+const DEFAULT_CAPACITY: usize = 1024;
+
+/// Default time an entry stays valid
+const DEFAULT_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Cache configuration
+///
+/// # Example
+///
+/// ```rust
+/// use x_api_client::util::cache::CacheConfig;
+/// use std::time::Duration;
+///
+/// let config = CacheConfig::custom()
+///     .capacity(500)
+///     .ttl(Duration::from_secs(10 * 60))
+///     .build()?;
+/// assert_eq!(config.capacity(), 500);
+/// # Ok::<(), x_api_client::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheConfig {
+    /// Maximum number of entries (default: 1024)
+    capacity: usize,
+
+    /// Time an entry stays valid (default: 1 hour)
+    ttl: Duration,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            capacity: DEFAULT_CAPACITY,
+            ttl: DEFAULT_TTL,
+        }
+    }
+}
+
+impl CacheConfig {
+    /// Create a cache configuration with default values
+    ///
+    /// Defaults:
+    /// - capacity: 1024 entries
+    /// - ttl: 1 hour
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a custom cache configuration with builder pattern
+    pub fn custom() -> CacheConfigBuilder {
+        CacheConfigBuilder::default()
+    }
+
+    /// Get the maximum number of entries
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Get the time an entry stays valid
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+}
+
+/// Builder for creating custom cache configurations with validation
+#[derive(Debug, Default)]
+pub struct CacheConfigBuilder {
+    config: CacheConfig,
+}
+
+impl CacheConfigBuilder {
+    /// Set the maximum number of entries
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.config.capacity = capacity;
+        self
+    }
+
+    /// Set the time an entry stays valid
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.config.ttl = ttl;
+        self
+    }
+
+    /// Build the cache configuration
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - capacity is 0
+    /// - ttl is zero
+    pub fn build(self) -> Result<CacheConfig> {
+        if self.config.capacity == 0 {
+            return Err(Error::Config(
+                "Cache capacity must be at least 1".to_string(),
+            ));
+        }
+
+        if self.config.ttl.is_zero() {
+            return Err(Error::Config("Cache TTL must be non-zero".to_string()));
+        }
+
+        Ok(self.config)
+    }
+}
+
+/// Cache of values by key, shared between client clones
+#[derive(Debug)]
+pub(crate) struct TtlCache<V> {
+    config: CacheConfig,
+    state: Mutex<State<V>>,
+}
+
+/// Entries and the use counter ordering them by recency
+#[derive(Debug)]
+struct State<V> {
+    entries: HashMap<String, Entry<V>>,
+    uses: u64,
+}
+
+#[derive(Debug)]
+struct Entry<V> {
+    value: V,
+    expires_at: DateTime<Utc>,
+    last_used: u64,
+}
+
+impl<V: Clone> TtlCache<V> {
+    pub(crate) fn new(config: CacheConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::new(State {
+                entries: HashMap::new(),
+                uses: 0,
+            }),
+        }
+    }
+
+    /// The value cached for `key`, unless absent or expired at `now`
+    pub(crate) fn get(&self, key: &str, now: DateTime<Utc>) -> Option<V> {
+        let mut state = lock_unpoisoned(&self.state);
+        let expired = now >= state.entries.get(key)?.expires_at;
+        if expired {
+            state.entries.remove(key);
+            return None;
+        }
+        state.uses += 1;
+        let uses = state.uses;
+        let entry = state.entries.get_mut(key)?;
+        entry.last_used = uses;
+        Some(entry.value.clone())
+    }
+
+    /// Cache `value` for `key` from `now`, evicting the least recently used
+    /// entry if the cache is full
+    pub(crate) fn insert(&self, key: String, value: V, now: DateTime<Utc>) {
+        let ttl = chrono::Duration::from_std(self.config.ttl).unwrap_or(chrono::TimeDelta::MAX);
+        let expires_at = now
+            .checked_add_signed(ttl)
+            .unwrap_or(DateTime::<Utc>::MAX_UTC);
+
+        let mut state = lock_unpoisoned(&self.state);
+        if !state.entries.contains_key(&key) && state.entries.len() >= self.config.capacity {
+            state.entries.retain(|_, entry| entry.expires_at > now);
+            if state.entries.len() >= self.config.capacity {
+                let oldest = state
+                    .entries
+                    .iter()
+                    .min_by_key(|(_, entry)| entry.last_used)
+                    .map(|(key, _)| key.clone());
+                if let Some(oldest) = oldest {
+                    state.entries.remove(&oldest);
+                }
+            }
+        }
+        state.uses += 1;
+        let last_used = state.uses;
+        state.entries.insert(
+            key,
+            Entry {
+                value,
+                expires_at,
+                last_used,
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expiry_and_lru_eviction() {
+        let config = CacheConfig::custom()
+            .capacity(2)
+            .ttl(Duration::from_secs(60))
+            .build()
+            .unwrap();
+        let cache = TtlCache::new(config);
+        let start = Utc::now();
+
+        cache.insert("a".to_string(), 1, start);
+        cache.insert("b".to_string(), 2, start);
+        assert_eq!(cache.get("a", start), Some(1));
+
+        // "b" is the least recently used
+        cache.insert("c".to_string(), 3, start);
+        assert_eq!(cache.get("b", start), None);
+        assert_eq!(cache.get("a", start), Some(1));
+        assert_eq!(cache.get("c", start), Some(3));
+
+        let later = start + chrono::Duration::seconds(60);
+        assert_eq!(cache.get("a", later), None);
+
+        assert!(CacheConfig::custom().capacity(0).build().is_err());
+        assert!(CacheConfig::custom().ttl(Duration::ZERO).build().is_err());
+    }
+}
@@ -0,0 +1,132 @@
+//! Time source abstraction
+//!
+//! Rate limit waits and retry backoff read the current time and sleep through
+//! a [`Clock`], so tests can substitute a [`MockClock`] and assert exact waits
+//! without depending on wall-clock timing.
+
+use crate::util::lock_unpoisoned;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Source of the current time and of delays
+#[async_trait]
+pub trait Clock: Send + Sync {
+    /// Current time
+    fn now(&self) -> DateTime<Utc>;
+
+    /// Wait for `duration`
+    async fn sleep(&self, duration: Duration);
+}
+
+/// Clock backed by the system time and the Tokio timer (the default)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+#[async_trait]
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    async fn sleep(&self, duration: Duration) {
+        tokio::time::sleep(duration).await;
+    }
+}
+
+/// Manually driven clock for tests
+///
+/// Sleeping returns immediately, advances the clock by the requested
+/// duration, and records it. Clones share the same state, so a test can keep
+/// a handle while the client owns another.
+///
+/// # Example
+///
+/// ```rust
+/// use x_api_client::util::clock::{Clock, MockClock};
+/// use std::time::Duration;
+///
+/// let start = chrono::Utc::now();
+/// let clock = MockClock::new(start);
+/// clock.advance(Duration::from_secs(5));
+/// assert_eq!(clock.now() - start, chrono::Duration::seconds(5));
+/// ```
+#[derive(Debug, Clone)]
+pub struct MockClock {
+    state: Arc<Mutex<MockState>>,
+}
+
+#[derive(Debug)]
+struct MockState {
+    now: DateTime<Utc>,
+    sleeps: Vec<Duration>,
+}
+
+impl MockClock {
+    /// Create a clock starting at `now`
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(MockState {
+                now,
+                sleeps: Vec::new(),
+            })),
+        }
+    }
+
+    /// Move the clock forward without recording a sleep
+    pub fn advance(&self, duration: Duration) {
+        let mut state = lock_unpoisoned(&self.state);
+        state.now += to_chrono(duration);
+    }
+
+    /// Set the current time
+    pub fn set(&self, now: DateTime<Utc>) {
+        lock_unpoisoned(&self.state).now = now;
+    }
+
+    /// Durations of all sleeps so far, in order
+    pub fn sleeps(&self) -> Vec<Duration> {
+        lock_unpoisoned(&self.state).sleeps.clone()
+    }
+}
+
+#[async_trait]
+impl Clock for MockClock {
+    fn now(&self) -> DateTime<Utc> {
+        lock_unpoisoned(&self.state).now
+    }
+
+    async fn sleep(&self, duration: Duration) {
+        let mut state = lock_unpoisoned(&self.state);
+        state.now += to_chrono(duration);
+        state.sleeps.push(duration);
+    }
+}
+
+/// Convert to a chrono duration, saturating at chrono's maximum
+fn to_chrono(duration: Duration) -> chrono::Duration {
+    chrono::Duration::from_std(duration).unwrap_or(chrono::Duration::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_mock_clock_advances_on_sleep() {
+        let start = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let clock = MockClock::new(start);
+
+        clock.sleep(Duration::from_secs(30)).await;
+        clock.advance(Duration::from_secs(10));
+
+        assert_eq!(clock.now(), start + chrono::Duration::seconds(40));
+        assert_eq!(clock.sleeps(), vec![Duration::from_secs(30)]);
+
+        // Clones share state
+        let handle = clock.clone();
+        handle.set(start);
+        assert_eq!(clock.now(), start);
+    }
+}
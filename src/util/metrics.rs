@@ -0,0 +1,172 @@
+//! Request latency metrics
+//!
+//! Every request attempt sent by the [`Client`](crate::Client) can be
+//! reported to a [`MetricsRecorder`], installed with
+//! [`ClientBuilder::metrics_recorder`](crate::client::ClientBuilder::metrics_recorder).
+//! [`LatencyHistogram`] keeps the measurements in memory and computes
+//! percentiles for SLO tracking.
+
+use crate::util::lock_unpoisoned;
+use reqwest::StatusCode;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Median percentile
+pub const P50: f64 = 50.0;
+
+/// 95th percentile
+pub const P95: f64 = 95.0;
+
+/// Highest percentile
+const P100: f64 = 100.0;
+
+/// Receives the outcome of each request attempt
+///
+/// Called once per attempt, so a retried request is recorded several times.
+/// Streaming connections are not recorded.
+pub trait MetricsRecorder: Send + Sync {
+    /// Record an attempt on `endpoint` (e.g. `GET /2/tweets/:id`)
+    ///
+    /// `status` is `None` if no response was received. `elapsed` runs from
+    /// sending the request to receiving the response headers.
+    fn record(&self, endpoint: &str, status: Option<StatusCode>, elapsed: Duration);
+}
+
+/// In-memory per-endpoint latency histogram
+///
+/// Keeps every measurement until [`reset`](Self::reset), so long-running
+/// applications should read and reset it periodically.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use std::sync::Arc;
+/// use x_api_client::util::metrics::LatencyHistogram;
+///
+/// let histogram = Arc::new(LatencyHistogram::new());
+/// let client = Client::builder()
+///     .oauth1("ck", "cs", "at", "ats")
+///     .metrics_recorder(histogram.clone())
+///     .build()?;
+///
+/// client.get_tweet("1445078208190291973").await?;
+/// println!("p95: {:?}", histogram.p95("GET /2/tweets/:id"));
+/// ```
+#[derive(Debug, Default)]
+pub struct LatencyHistogram {
+    endpoints: Mutex<HashMap<String, EndpointSamples>>,
+}
+
+/// Measurements for one endpoint
+#[derive(Debug, Default)]
+struct EndpointSamples {
+    latencies: Vec<Duration>,
+    statuses: HashMap<Option<StatusCode>, u64>,
+}
+
+impl LatencyHistogram {
+    /// Create an empty histogram
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Endpoints with at least one measurement
+    pub fn endpoints(&self) -> Vec<String> {
+        lock_unpoisoned(&self.endpoints).keys().cloned().collect()
+    }
+
+    /// Number of attempts recorded for `endpoint`
+    pub fn count(&self, endpoint: &str) -> usize {
+        lock_unpoisoned(&self.endpoints)
+            .get(endpoint)
+            .map_or(0, |samples| samples.latencies.len())
+    }
+
+    /// Number of attempts on `endpoint` that ended with `status`
+    ///
+    /// Pass `None` to count attempts that received no response.
+    pub fn status_count(&self, endpoint: &str, status: Option<StatusCode>) -> u64 {
+        lock_unpoisoned(&self.endpoints)
+            .get(endpoint)
+            .and_then(|samples| samples.statuses.get(&status).copied())
+            .unwrap_or(0)
+    }
+
+    /// Latency at `percentile` (0 to 100) for `endpoint`, by nearest rank
+    ///
+    /// Returns `None` if nothing was recorded for `endpoint` or
+    /// `percentile` is out of range.
+    pub fn percentile(&self, endpoint: &str, percentile: f64) -> Option<Duration> {
+        if !(0.0..=P100).contains(&percentile) {
+            return None;
+        }
+        let endpoints = lock_unpoisoned(&self.endpoints);
+        let mut latencies = endpoints.get(endpoint)?.latencies.clone();
+        drop(endpoints);
+        if latencies.is_empty() {
+            return None;
+        }
+        latencies.sort_unstable();
+        let rank = (percentile / P100 * latencies.len() as f64).ceil() as usize;
+        Some(latencies[rank.saturating_sub(1)])
+    }
+
+    /// Median latency for `endpoint`
+    pub fn p50(&self, endpoint: &str) -> Option<Duration> {
+        self.percentile(endpoint, P50)
+    }
+
+    /// 95th percentile latency for `endpoint`
+    pub fn p95(&self, endpoint: &str) -> Option<Duration> {
+        self.percentile(endpoint, P95)
+    }
+
+    /// Discard every measurement
+    pub fn reset(&self) {
+        lock_unpoisoned(&self.endpoints).clear();
+    }
+}
+
+impl MetricsRecorder for LatencyHistogram {
+    fn record(&self, endpoint: &str, status: Option<StatusCode>, elapsed: Duration) {
+        let mut endpoints = lock_unpoisoned(&self.endpoints);
+        let samples = endpoints.entry(endpoint.to_string()).or_default();
+        samples.latencies.push(elapsed);
+        *samples.statuses.entry(status).or_default() += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentiles_by_nearest_rank() {
+        let histogram = LatencyHistogram::new();
+        let endpoint = "GET /2/tweets/:id";
+        for millis in (1..=20).rev() {
+            histogram.record(
+                endpoint,
+                Some(StatusCode::OK),
+                Duration::from_millis(millis),
+            );
+        }
+        histogram.record(endpoint, None, Duration::from_millis(20));
+
+        assert_eq!(histogram.count(endpoint), 21);
+        assert_eq!(histogram.p50(endpoint), Some(Duration::from_millis(11)));
+        assert_eq!(histogram.p95(endpoint), Some(Duration::from_millis(20)));
+        assert_eq!(
+            histogram.percentile(endpoint, 0.0),
+            Some(Duration::from_millis(1))
+        );
+        assert_eq!(histogram.percentile(endpoint, 101.0), None);
+        assert_eq!(histogram.status_count(endpoint, Some(StatusCode::OK)), 20);
+        assert_eq!(histogram.status_count(endpoint, None), 1);
+        assert_eq!(histogram.p50("GET /2/users/:id"), None);
+
+        histogram.reset();
+        assert!(histogram.endpoints().is_empty());
+    }
+}
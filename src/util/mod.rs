@@ -1,3 +1,12 @@
 //! Utility functions and helpers
 
+pub mod cache;
+pub mod clock;
+pub mod metrics;
+pub mod redact;
 pub mod serde_helpers;
+pub mod snowflake;
+pub(crate) mod sync;
+
+pub use snowflake::snowflake_to_datetime;
+pub(crate) use sync::lock_unpoisoned;
@@ -0,0 +1,213 @@
+//! Redaction of secrets and personal data in request logs
+//!
+//! Every request sent by the [`Client`](crate::Client) is logged at `DEBUG`
+//! level through `tracing`. Headers and bodies pass through a
+//! [`BodyRedactor`] first, so audit logs can include request bodies without
+//! leaking credentials or message contents.
+
+use crate::builder::request::Body;
+use std::collections::HashSet;
+
+/// Replacement for redacted values
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Headers masked by [`DefaultRedactor`] (lowercase)
+pub const SENSITIVE_HEADERS: &[&str] = &["authorization", "proxy-authorization", "cookie"];
+
+/// Body fields masked by [`DefaultRedactor`], at any nesting depth
+///
+/// `text` covers tweet and direct message contents.
+pub const SENSITIVE_FIELDS: &[&str] = &[
+    "text",
+    "access_token",
+    "refresh_token",
+    "client_secret",
+    "code_verifier",
+    "oauth_token",
+    "oauth_token_secret",
+    "oauth_verifier",
+    "password",
+];
+
+/// Renders request headers and bodies for logging
+///
+/// Implement this to control what ends up in request logs, then install it
+/// with [`ClientBuilder::body_redactor`](crate::client::ClientBuilder::body_redactor).
+pub trait BodyRedactor: Send + Sync {
+    /// Render the body of a request to `path`
+    fn redact_body(&self, path: &str, body: &Body) -> String;
+
+    /// Render the value of header `name` (lowercase)
+    fn redact_header(&self, name: &str, value: &str) -> String;
+}
+
+/// Redactor masking credentials headers and known sensitive fields (the default)
+///
+/// Masks the [`SENSITIVE_HEADERS`] and every JSON or form field named in
+/// [`SENSITIVE_FIELDS`]. Additional names can be added with
+/// [`with_field`](Self::with_field) and [`with_header`](Self::with_header).
+///
+/// # Example
+///
+/// ```rust
+/// use x_api_client::builder::request::Body;
+/// use x_api_client::util::redact::{BodyRedactor, DefaultRedactor};
+///
+/// let redactor = DefaultRedactor::new().with_field("email");
+/// let body = Body::json(&serde_json::json!({"email": "a@example.com", "lang": "en"}))?;
+/// assert_eq!(
+///     redactor.redact_body("/2/example", &body),
+///     r#"{"email":"[REDACTED]","lang":"en"}"#
+/// );
+/// # Ok::<(), x_api_client::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct DefaultRedactor {
+    fields: HashSet<String>,
+    headers: HashSet<String>,
+}
+
+impl Default for DefaultRedactor {
+    fn default() -> Self {
+        Self {
+            fields: SENSITIVE_FIELDS.iter().map(|f| f.to_string()).collect(),
+            headers: SENSITIVE_HEADERS.iter().map(|h| h.to_string()).collect(),
+        }
+    }
+}
+
+impl DefaultRedactor {
+    /// Create a redactor with the default sensitive headers and fields
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also mask body fields named `name`
+    pub fn with_field(mut self, name: impl Into<String>) -> Self {
+        self.fields.insert(name.into());
+        self
+    }
+
+    /// Also mask header `name` (case-insensitive)
+    pub fn with_header(mut self, name: impl AsRef<str>) -> Self {
+        self.headers.insert(name.as_ref().to_ascii_lowercase());
+        self
+    }
+
+    fn redact_value(&self, value: &mut serde_json::Value) {
+        match value {
+            serde_json::Value::Object(map) => {
+                for (key, value) in map.iter_mut() {
+                    if self.fields.contains(key) {
+                        *value = serde_json::Value::String(REDACTED.to_string());
+                    } else {
+                        self.redact_value(value);
+                    }
+                }
+            }
+            serde_json::Value::Array(items) => {
+                items.iter_mut().for_each(|item| self.redact_value(item));
+            }
+            _ => {}
+        }
+    }
+}
+
+impl BodyRedactor for DefaultRedactor {
+    fn redact_body(&self, _path: &str, body: &Body) -> String {
+        match body {
+            Body::Json(value) => {
+                let mut value = value.clone();
+                self.redact_value(&mut value);
+                value.to_string()
+            }
+            Body::Form(pairs) => pairs
+                .iter()
+                .map(|(key, value)| {
+                    let value = if self.fields.contains(key) {
+                        REDACTED
+                    } else {
+                        value
+                    };
+                    format!("{}={}", key, value)
+                })
+                .collect::<Vec<_>>()
+                .join("&"),
+        }
+    }
+
+    fn redact_header(&self, name: &str, value: &str) -> String {
+        if self.headers.contains(name) {
+            REDACTED.to_string()
+        } else {
+            value.to_string()
+        }
+    }
+}
+
+/// Describe an outgoing request for the request log
+///
+/// Includes the method, path, headers, and body, each passed through
+/// `redactor`. The query string is left out.
+pub(crate) fn describe_request(
+    redactor: &dyn BodyRedactor,
+    request: &reqwest::Request,
+    body: Option<&Body>,
+) -> String {
+    let path = request.url().path();
+    let headers: Vec<String> = request
+        .headers()
+        .iter()
+        .map(|(name, value)| {
+            let value = value.to_str().unwrap_or("<binary>");
+            format!("{}: {}", name, redactor.redact_header(name.as_str(), value))
+        })
+        .collect();
+
+    let mut line = format!("{} {} [{}]", request.method(), path, headers.join(", "));
+    if let Some(body) = body {
+        line.push(' ');
+        line.push_str(&redactor.redact_body(path, body));
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::{AUTHORIZATION, HeaderValue};
+
+    #[test]
+    fn test_create_dm_log_redacts_text_but_keeps_path() {
+        let path = "/2/dm_conversations/with/42/messages";
+        let body = Body::json(&serde_json::json!({
+            "text": "my secret plans",
+            "attachments": [{"media_id": "7"}]
+        }))
+        .unwrap();
+        let url = reqwest::Url::parse(&format!("https://api.twitter.com{}", path)).unwrap();
+        let mut request = reqwest::Request::new(reqwest::Method::POST, url);
+        request.headers_mut().insert(
+            AUTHORIZATION,
+            HeaderValue::from_static("OAuth oauth_token=\"secret\""),
+        );
+
+        let line = describe_request(&DefaultRedactor::new(), &request, Some(&body));
+
+        assert!(line.starts_with("POST /2/dm_conversations/with/42/messages "));
+        assert!(line.contains(r#""text":"[REDACTED]""#));
+        assert!(line.contains(r#""media_id":"7""#));
+        assert!(line.contains("authorization: [REDACTED]"));
+        assert!(!line.contains("my secret plans"));
+        assert!(!line.contains("oauth_token=\"secret\""));
+    }
+
+    #[test]
+    fn test_form_fields_redacted() {
+        let body = Body::form([("oauth_verifier", "abc"), ("x_auth_mode", "client")]);
+        assert_eq!(
+            DefaultRedactor::new().redact_body("/oauth/access_token", &body),
+            "oauth_verifier=[REDACTED]&x_auth_mode=client"
+        );
+    }
+}
@@ -5,9 +5,22 @@
 //! - Booleans that may be strings or actual booleans
 //! - Empty strings vs null vs missing fields
 //! - Arrays that may be null or missing
+//!
+//! It also implements strict deserialization (see
+//! [`ClientBuilder::strict_deserialization`](crate::client::ClientBuilder::strict_deserialization)),
+//! which rejects unknown fields instead of capturing them.
 
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Deserializer};
 use serde_json::Value;
+use std::cell::Cell;
+use std::collections::HashMap;
+
+thread_local! {
+    /// Whether [`deserialize_unknown_fields`] rejects unknown fields, set
+    /// only for the duration of a [`from_json_str`] call
+    static STRICT: Cell<bool> = const { Cell::new(false) };
+}
 
 /// Deserialize an ID field that may be a string or number
 ///
@@ -167,6 +180,50 @@ where
     }
 }
 
+/// Parse `text` as a `T`, rejecting unknown fields of models if `strict`
+///
+/// Every response and stream message is parsed through here, with the
+/// client's setting passed in. Derived deserializers take no arguments, so
+/// the mode reaches [`deserialize_unknown_fields`] through a thread-local
+/// set for this call only; parsing is synchronous, so it cannot leak into
+/// other parses.
+pub(crate) fn from_json_str<T: DeserializeOwned>(
+    text: &str,
+    strict: bool,
+) -> serde_json::Result<T> {
+    struct Restore(bool);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            STRICT.set(self.0);
+        }
+    }
+
+    let _restore = Restore(STRICT.replace(strict));
+    serde_json::from_str(text)
+}
+
+/// Deserialize the fields a model does not know about
+///
+/// Used on the flattened `additional_fields` of top-level models. Fails
+/// when parsed by a strict [`from_json_str`] if there are any.
+pub(crate) fn deserialize_unknown_fields<'de, D>(
+    deserializer: D,
+) -> Result<HashMap<String, Value>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let fields = HashMap::<String, Value>::deserialize(deserializer)?;
+    if STRICT.get() && !fields.is_empty() {
+        let mut names: Vec<&str> = fields.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        return Err(serde::de::Error::custom(format!(
+            "unknown fields: {}",
+            names.join(", ")
+        )));
+    }
+    Ok(fields)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
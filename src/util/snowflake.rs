@@ -0,0 +1,71 @@
+//! Snowflake ID decoding
+//!
+//! Tweet, user, media and most other X IDs are snowflakes: 64-bit integers
+//! whose upper bits hold the creation time in milliseconds since the Twitter
+//! epoch, followed by 10 bits of worker ID and 12 bits of sequence number.
+
+use chrono::{DateTime, Utc};
+
+/// Twitter epoch (2010-11-04T01:42:54.657Z), in Unix milliseconds
+pub const TWITTER_EPOCH_MS: i64 = 1_288_834_974_657;
+
+/// Number of low bits holding the worker ID and sequence number
+const TIMESTAMP_SHIFT: u32 = 22;
+
+/// Smallest ID treated as a snowflake
+///
+/// IDs issued before snowflakes were introduced (sequential tweet IDs up to
+/// about 2.97e10, and early user IDs) do not encode a creation time.
+const FIRST_SNOWFLAKE_ID: u64 = 30_000_000_000;
+
+/// Decode the creation time encoded in a snowflake ID
+///
+/// Returns `None` if `id` is not a decimal integer or predates snowflakes.
+///
+/// # Example
+///
+/// ```rust
+/// use x_api_client::util::snowflake_to_datetime;
+///
+/// let created = snowflake_to_datetime("1445078208190291973").unwrap();
+/// assert_eq!(created.to_rfc3339(), "2021-10-04T17:27:47.744+00:00");
+/// assert_eq!(snowflake_to_datetime("12"), None);
+/// ```
+pub fn snowflake_to_datetime(id: &str) -> Option<DateTime<Utc>> {
+    let id: u64 = id.parse().ok()?;
+    if id < FIRST_SNOWFLAKE_ID {
+        return None;
+    }
+    let offset_ms = i64::try_from(id >> TIMESTAMP_SHIFT).ok()?;
+    DateTime::from_timestamp_millis(TWITTER_EPOCH_MS.checked_add(offset_ms)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_ids() {
+        let vectors = [
+            ("1445078208190291973", 1_633_368_467_744),
+            ("1212092628029698048", 1_577_820_376_771),
+        ];
+        for (id, millis) in vectors {
+            assert_eq!(
+                snowflake_to_datetime(id).unwrap().timestamp_millis(),
+                millis,
+                "id {}",
+                id
+            );
+        }
+    }
+
+    #[test]
+    fn test_non_snowflake_ids() {
+        assert_eq!(snowflake_to_datetime("20"), None);
+        assert_eq!(snowflake_to_datetime("783214"), None);
+        assert_eq!(snowflake_to_datetime(""), None);
+        assert_eq!(snowflake_to_datetime("1DXxyRYNejbKM"), None);
+        assert_eq!(snowflake_to_datetime("-1445078208190291973"), None);
+    }
+}
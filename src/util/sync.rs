@@ -0,0 +1,36 @@
+//! Synchronization helpers
+
+use std::sync::{Mutex, MutexGuard};
+
+/// Lock `mutex`, recovering the data if a previous holder panicked
+///
+/// The crate's mutexes guard plain data updated in single assignments or
+/// map operations, so a panic while holding one cannot leave the data
+/// logically inconsistent. Recovering keeps a panic in one task from
+/// turning every later access into a panic as well.
+pub(crate) fn lock_unpoisoned<T: ?Sized>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_lock_recovers_after_holder_panicked() {
+        let mutex = Arc::new(Mutex::new(1));
+        let holder = Arc::clone(&mutex);
+        let _ = std::thread::spawn(move || {
+            let _guard = holder.lock().unwrap();
+            panic!("holder panicked");
+        })
+        .join();
+
+        assert!(mutex.is_poisoned());
+        *lock_unpoisoned(&mutex) += 1;
+        assert_eq!(*lock_unpoisoned(&mutex), 2);
+    }
+}